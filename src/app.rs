@@ -4,20 +4,50 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Clear},
+    widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Clear},
     Frame, Terminal,
 };
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
 use std::time::{Duration, Instant};
-use crate::scanner::ComObject;
-use crate::error_handling::{Result, Context};
-use crate::com_interop::{self, TypeDetails, Member, AccessMode};
+use crate::scanner::{self, ComObject, ServerKind, AppIdInfo, RegistryNode};
+use crate::error_handling::{Result, Context, InspectError};
+use crate::com_interop::{self, InspectOptions, TypeDetails, Member, AccessMode};
+use crate::export::{render_report, OutputFormat, ReportRow};
+use crate::keymap::KeyMap;
+use crate::processor::{CategoryStats, FilterOptions, GroupBy, Highlighter, Query};
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::thread;
 use arboard::Clipboard;
-use std::collections::{VecDeque, HashSet};
+use std::collections::{VecDeque, HashSet, BTreeSet, BTreeMap};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
 
+/// Search prefix that switches the query from fuzzy matching to a regular expression.
+const REGEX_PREFIX: &str = "re:";
+
+/// Path the `Ctrl+S` export prompt starts from.
+const DEFAULT_EXPORT_PATH: &str = "com_objects.csv";
+
+/// Formats the `Ctrl+S` export can write. The graph is drawn from type details,
+/// which would mean inspecting every object, so `dot` is left out.
+fn export_formats() -> impl Iterator<Item = OutputFormat> {
+    OutputFormat::ALL.into_iter().filter(|format| *format != OutputFormat::Dot)
+}
+
+/// The export file extensions, e.g. `.txt .json .csv`.
+fn export_extensions() -> String {
+    export_formats().map(|format| format!(".{}", format.extension())).collect::<Vec<_>>().join(" ")
+}
+
+/// Braille spinner frames shown while scanning.
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// The app's lifecycle: `Scanning` until the background scan reports back, then
+/// `Browsing`, with `Inspecting` entered and left per object.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppMode {
     Scanning,
@@ -25,6 +55,147 @@ pub enum AppMode {
     Inspecting,
 }
 
+/// Whether printable keys in the object list edit the search query or act as commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    Navigation,
+    Search,
+}
+
+/// Which inspection pane receives navigation keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectFocus {
+    Members,
+    Details,
+}
+
+/// Which browsing pane receives navigation keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusedPane {
+    List,
+    Details,
+}
+
+/// What a submitted [`InputModal`] does with its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    /// Write the filtered objects to the entered path
+    ExportFiltered,
+    /// Select the next category whose name starts with the entered text
+    GoToCategory,
+    /// Filter the list by the entered regular expression
+    RegexFilter,
+}
+
+/// How a key press left an [`InputModal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputOutcome {
+    Editing,
+    Submitted,
+    Cancelled,
+}
+
+/// A single-line text prompt drawn over the UI, capturing every key until `Enter` or `Esc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputModal {
+    pub title: String,
+    /// Shown above the input line
+    pub prompt: String,
+    pub buffer: String,
+    /// Insertion point in `buffer`, counted in characters
+    pub cursor: usize,
+    pub action: InputAction,
+}
+
+impl InputModal {
+    /// Opens a prompt holding `initial`, with the cursor at its end.
+    pub fn new(title: &str, prompt: &str, initial: &str, action: InputAction) -> Self {
+        Self {
+            title: title.to_string(),
+            prompt: prompt.to_string(),
+            buffer: initial.to_string(),
+            cursor: initial.chars().count(),
+            action,
+        }
+    }
+
+    /// Applies an editing key: characters insert at the cursor, `Backspace`/`Delete` remove
+    /// around it, and `←`/`→`/`Home`/`End` move it.
+    pub fn handle_key(&mut self, key: event::KeyEvent) -> InputOutcome {
+        match key.code {
+            KeyCode::Enter => return InputOutcome::Submitted,
+            KeyCode::Esc => return InputOutcome::Cancelled,
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                let at = self.byte_index(self.cursor);
+                self.buffer.insert(at, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_index(self.cursor);
+                self.buffer.remove(at);
+            }
+            KeyCode::Delete if self.cursor < self.buffer.chars().count() => {
+                let at = self.byte_index(self.cursor);
+                self.buffer.remove(at);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.buffer.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.buffer.chars().count(),
+            _ => {}
+        }
+        InputOutcome::Editing
+    }
+
+    /// The input line, with the character under the cursor (a space past the end) reversed.
+    fn input_line(&self) -> Line<'_> {
+        let at = self.byte_index(self.cursor);
+        let (before, rest) = self.buffer.split_at(at);
+        let mut chars = rest.chars();
+        let under = chars.next().map(String::from).unwrap_or_else(|| " ".to_string());
+        Line::from(vec![
+            Span::raw(before),
+            Span::styled(under, Style::default().add_modifier(Modifier::REVERSED)),
+            Span::raw(chars.as_str()),
+        ])
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.buffer.char_indices().nth(char_index).map_or(self.buffer.len(), |(index, _)| index)
+    }
+}
+
+/// Messages sent from the background scan thread to the TUI.
+pub enum ScanUpdate {
+    /// Registry keys processed so far
+    Progress { processed: usize, total: usize },
+    /// The final scan result
+    Finished(Result<Vec<ComObject>>),
+}
+
+/// Browsing state remembered between sessions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default)]
+    pub expanded_categories: BTreeSet<String>,
+    #[serde(default)]
+    pub search_query: String,
+    /// CLSID of the object selected on exit
+    #[serde(default)]
+    pub selected_clsid: Option<String>,
+}
+
+/// Returns the UI state file under the user's config directory.
+pub fn default_state_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rustcom_explorer").join("ui_state.json"))
+}
+
+/// Returns the bookmarks file under the user's config directory.
+pub fn default_bookmarks_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rustcom_explorer").join("bookmarks.json"))
+}
+
 #[derive(Debug, Clone)]
 pub struct Notification {
     pub message: String,
@@ -33,28 +204,91 @@ pub struct Notification {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TreeItem {
-    Category { name: String, count: usize, expanded: bool },
-    Object(ComObject), // Stores the ComObject directly
+    Category { name: String, stats: CategoryStats, expanded: bool },
+    /// An object row; `matched` holds the character positions of its name that the search query matched
+    Object { obj: Box<ComObject>, matched: Vec<usize> },
 }
 
 pub struct App {
     pub objects_list: Vec<ComObject>,
     pub search_query: String,
     pub list_state: ListState,
+    /// Rows visible in the object list, updated on every render
+    pub list_height: usize,
+    /// Screen area of the object list, used to map mouse clicks to rows
+    pub list_area: Rect,
     pub app_mode: AppMode,
+    pub input_mode: InputMode,
+    /// Key help overlay, available in every mode
+    pub show_help: bool,
+    /// Bindings for remappable actions
+    pub keymap: KeyMap,
     pub should_quit: bool,
     
     // Safety Configuration
-    pub unsafe_mode: bool,
+    /// Inspection settings (unsafe mode, apartment, instantiation budget) from the command line
+    pub inspect: InspectOptions,
 
     // Categorization State
+    pub group_by: GroupBy,
     pub expanded_categories: HashSet<String>,
+    /// Object to select once the scan finishes, restored from the previous session
+    pub restore_selection: Option<String>,
+    /// CLSIDs of bookmarked objects
+    pub bookmarks: HashSet<String>,
+    /// Restricts the tree to bookmarked objects
+    pub bookmarks_only: bool,
+
+    // State for Scanning Mode
+    pub scan_receiver: Option<Receiver<ScanUpdate>>,
+    pub scan_started: Option<Instant>,
+    /// How long the background scan took, once it has finished
+    pub scan_duration: Option<Duration>,
+    pub scan_progress: (usize, usize),
+    /// Shown above the list when the scan returned partial results
+    pub scan_warning: Option<String>,
+    /// Shows the selected object's raw `HKCR\CLSID` subtree in place of its details
+    pub show_raw_registry: bool,
+    /// The last subtree read, by CLSID, so it is not re-read on every frame
+    pub raw_registry: Option<(String, std::result::Result<RegistryNode, String>)>,
 
     // State for Inspecting Mode
+    pub inspected_object: Option<ComObject>,
+    /// CLSIDs of inspected objects, most recent last
+    pub inspection_history: Vec<String>,
     pub selected_object: Option<TypeDetails>,
     pub error_message: Option<String>,
     pub inspection_receiver: Option<Receiver<Result<TypeDetails>>>,
+    /// Set to abandon the running inspection thread
+    pub inspection_cancel: Option<Arc<AtomicBool>>,
+    pub inspection_cancelled: bool,
     pub member_list_state: ListState,
+    pub inspect_focus: InspectFocus,
+    /// The browsing pane that `Tab` last moved focus to
+    pub focused_pane: FocusedPane,
+    /// First line shown in the object details paragraph
+    pub details_scroll: u16,
+    /// Rows visible in the member list, updated on every render
+    pub member_list_height: usize,
+    /// Reveals members the type library flags hidden or restricted
+    pub show_hidden: bool,
+    /// Fuzzy filter applied to member names
+    pub member_query: String,
+    /// Whether typed characters edit `member_query`
+    pub member_search_active: bool,
+    /// Member awaiting confirmation before it is invoked
+    pub pending_invoke: Option<String>,
+    /// The open text prompt, if any; it receives every key until closed
+    pub input_modal: Option<InputModal>,
+    /// Object awaiting confirmation before inspection falls back to `CoCreateInstance`
+    pub pending_instantiation: Option<ComObject>,
+    /// Set when the user chose not to be asked again about instantiation this session
+    pub skip_instantiate_confirm: bool,
+    pub invoke_receiver: Option<Receiver<Result<String>>>,
+    /// Outcome of the last invocation, shown in the details pane
+    pub invoke_result: Option<String>,
+    /// Prefix of the last go-to category jump and when it was made, shown briefly in the status bar
+    pub jump_prefix: Option<(String, Instant)>,
     
     // Notification Queue
     pub notifications: VecDeque<Notification>,
@@ -62,32 +296,172 @@ pub struct App {
 }
 
 impl App {
-    pub fn new(mut objects: Vec<ComObject>, unsafe_mode: bool) -> Self {
-        // Sort objects by name to ensure consistent initial order
-        objects.sort_by(|a, b| a.name.cmp(&b.name));
-
-        let mut list_state = ListState::default();
-        if !objects.is_empty() {
-            list_state.select(Some(0));
-        }
-
+    /// Creates an app in `Scanning` mode that waits for results from a background scan.
+    ///
+    /// The app switches to `Browsing` once [`ScanUpdate::Finished`] arrives on `receiver`.
+    pub fn new(receiver: Receiver<ScanUpdate>, inspect: InspectOptions) -> Self {
         Self {
-            objects_list: objects,
+            objects_list: Vec::new(),
             search_query: String::new(),
-            list_state,
-            app_mode: AppMode::Browsing,
+            list_state: ListState::default(),
+            list_height: 0,
+            list_area: Rect::default(),
+            app_mode: AppMode::Scanning,
+            input_mode: InputMode::Navigation,
+            show_help: false,
+            keymap: KeyMap::default(),
             should_quit: false,
-            unsafe_mode,
+            inspect,
+            group_by: GroupBy::default(),
             expanded_categories: HashSet::new(),
+            restore_selection: None,
+            bookmarks: HashSet::new(),
+            bookmarks_only: false,
+            scan_receiver: Some(receiver),
+            scan_started: Some(Instant::now()),
+            scan_duration: None,
+            scan_progress: (0, 0),
+            scan_warning: None,
+            show_raw_registry: false,
+            raw_registry: None,
+            inspected_object: None,
+            inspection_history: Vec::new(),
             selected_object: None,
             error_message: None,
             inspection_receiver: None,
+            inspection_cancel: None,
+            inspection_cancelled: false,
             member_list_state: ListState::default(),
+            inspect_focus: InspectFocus::Members,
+            focused_pane: FocusedPane::List,
+            details_scroll: 0,
+            member_list_height: 0,
+            show_hidden: false,
+            member_query: String::new(),
+            member_search_active: false,
+            pending_invoke: None,
+            input_modal: None,
+            pending_instantiation: None,
+            skip_instantiate_confirm: false,
+            invoke_receiver: None,
+            invoke_result: None,
+            jump_prefix: None,
             notifications: VecDeque::new(),
             current_notification_start: None,
         }
     }
 
+    /// Restores expanded categories, the search query and the selection saved by [`App::save_state`].
+    pub fn load_state(&mut self, path: &Path) -> Result<()> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read UI state '{}'", path.display()))?;
+        let state: UiState = serde_json::from_str(&json).context("UI state file is corrupt")?;
+        self.expanded_categories = state.expanded_categories.into_iter().collect();
+        self.search_query = state.search_query;
+        self.restore_selection = state.selected_clsid;
+        Ok(())
+    }
+
+    /// Writes the current browsing state to `path`, creating parent directories as needed.
+    pub fn save_state(&self, path: &Path) -> Result<()> {
+        let selected_clsid = self.list_state.selected().and_then(|idx| match self.get_view_items().get(idx) {
+            Some(TreeItem::Object { obj, .. }) => Some(obj.clsid.clone()),
+            _ => None,
+        });
+        let state = UiState {
+            expanded_categories: self.expanded_categories.iter().cloned().collect(),
+            search_query: self.search_query.clone(),
+            // Keep the previous selection if the user quit before the scan finished
+            selected_clsid: selected_clsid.or_else(|| self.restore_selection.clone()),
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory '{}'", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&state).context("Failed to serialize UI state")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write UI state '{}'", path.display()))
+    }
+
+    /// Replaces the bookmarks with the CLSIDs saved by [`App::save_bookmarks`].
+    pub fn load_bookmarks(&mut self, path: &Path) -> Result<()> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read bookmarks '{}'", path.display()))?;
+        let clsids: Vec<String> = serde_json::from_str(&json).context("Bookmarks file is corrupt")?;
+        self.bookmarks = clsids.into_iter().collect();
+        Ok(())
+    }
+
+    /// Writes the bookmarked CLSIDs to `path` in sorted order, creating parent directories as needed.
+    pub fn save_bookmarks(&self, path: &Path) -> Result<()> {
+        let clsids: BTreeSet<&String> = self.bookmarks.iter().collect();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory '{}'", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&clsids).context("Failed to serialize bookmarks")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write bookmarks '{}'", path.display()))
+    }
+
+    /// Polls the background scan and switches to `Browsing` once results arrive.
+    fn check_scan_results(&mut self) {
+        loop {
+            let Some(rx) = &self.scan_receiver else {
+                return;
+            };
+
+            match rx.try_recv() {
+                Ok(ScanUpdate::Progress { processed, total }) => {
+                    self.scan_progress = (processed, total);
+                },
+                Ok(ScanUpdate::Finished(result)) => self.finish_scan(result),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.error_message = Some("Scan background task failed unexpectedly.".to_string());
+                    self.scan_receiver = None;
+                }
+            }
+        }
+    }
+
+    fn finish_scan(&mut self, result: Result<Vec<ComObject>>) {
+        self.scan_duration = self.scan_started.map(|start| start.elapsed());
+        match result {
+            Ok(mut objects) => {
+                objects.sort_by(|a, b| a.name.cmp(&b.name));
+                if objects.is_empty() {
+                    self.show_notification("No COM objects found.".to_string(), 3000);
+                    self.list_state.select(None);
+                } else {
+                    self.list_state.select(Some(0));
+                }
+                self.objects_list = objects;
+                self.app_mode = AppMode::Browsing;
+                if let Some(clsid) = self.restore_selection.take() {
+                    let view_items = self.get_view_items();
+                    if let Some(idx) = view_items.iter().position(|item| matches!(item, TreeItem::Object { obj, .. } if obj.clsid == clsid)) {
+                        self.list_state.select(Some(idx));
+                    }
+                }
+            },
+            Err(e) => {
+                let message = format!("{:#}", e);
+                match e.downcast::<scanner::ScanTimedOut>() {
+                    // Browse what was found, but keep the incomplete scan in view
+                    Ok(timed_out) => {
+                        self.scan_warning = Some(format!("{}. The list is incomplete.", message));
+                        self.finish_scan(Ok(timed_out.partial));
+                        return;
+                    }
+                    Err(_) => self.error_message = Some(format!("Failed to scan COM objects: {}", message)),
+                }
+            }
+        }
+        self.scan_receiver = None;
+    }
+
     pub fn show_notification(&mut self, message: String, duration_ms: u64) {
         self.notifications.push_back(Notification {
             message,
@@ -96,6 +470,10 @@ impl App {
     }
 
     fn tick_notifications(&mut self) {
+        // Hold notifications back until the list is on screen
+        if self.app_mode == AppMode::Scanning {
+            return;
+        }
         if let Some(notification) = self.notifications.front() {
             if self.current_notification_start.is_none() {
                 self.current_notification_start = Some(Instant::now());
@@ -109,26 +487,49 @@ impl App {
         }
     }
 
+    /// The compiled pattern when the search query uses the `re:` prefix.
+    pub fn query_regex(&self) -> Option<std::result::Result<Regex, regex::Error>> {
+        self.search_query.strip_prefix(REGEX_PREFIX).map(Regex::new)
+    }
+
+    /// The objects passing the search query and bookmark filter, grouped into categories.
+    fn filtered_groups(&self) -> BTreeMap<String, Vec<ComObject>> {
+        let objects: Vec<ComObject> = if self.bookmarks_only {
+            self.objects_list.iter().filter(|obj| self.bookmarks.contains(&obj.clsid)).cloned().collect()
+        } else {
+            self.objects_list.clone()
+        };
+        let query = match self.query_regex() {
+            Some(Ok(pattern)) => Query::Regex(pattern),
+            // An invalid pattern matches nothing; the list title shows the error
+            Some(Err(_)) => return BTreeMap::new(),
+            None => Query::Fuzzy(self.search_query.clone()),
+        };
+        crate::processor::process(objects, &FilterOptions { query, group_by: self.group_by, ..Default::default() })
+    }
+
     /// Compiles the view items: Filters -> Groups -> Flattens based on expansion.
     /// Returns Vec<TreeItem> with ComObjects stored directly.
     pub fn get_view_items(&self) -> Vec<TreeItem> {
-        let processed = crate::processor::process_objects(self.objects_list.clone(), &self.search_query);
-
         let mut items = Vec::new();
+        // Regex matches are not highlighted
+        let highlight_query = if self.search_query.starts_with(REGEX_PREFIX) { "" } else { &self.search_query };
+        let highlighter = Highlighter::new(highlight_query);
         // BTreeMap iterates keys alphabetically
-        for (category, objs) in processed {
-            let is_searching = !self.search_query.is_empty();
-            let is_expanded = self.expanded_categories.contains(&category) || is_searching;
+        for (category, objs) in self.filtered_groups() {
+            let is_filtering = !self.search_query.is_empty() || self.bookmarks_only;
+            let is_expanded = self.expanded_categories.contains(&category) || is_filtering;
             
             items.push(TreeItem::Category { 
                 name: category.clone(), 
-                count: objs.len(), 
+                stats: crate::processor::category_stats(&objs), 
                 expanded: is_expanded 
             });
 
             if is_expanded {
                 for obj in objs {
-                    items.push(TreeItem::Object(obj));
+                    let matched = highlighter.indices(&obj.name);
+                    items.push(TreeItem::Object { obj: Box::new(obj), matched });
                 }
             }
         }
@@ -144,12 +545,8 @@ impl App {
                     Ok(result) => {
                         match result {
                             Ok(details) => {
-                                if !details.members.is_empty() {
-                                    self.member_list_state.select(Some(0));
-                                } else {
-                                    self.member_list_state.select(None);
-                                }
                                 self.selected_object = Some(details);
+                                self.reset_member_selection();
                             },
                             Err(e) => {
                                 self.error_message = Some(format!("Error: {:#}", e));
@@ -165,6 +562,8 @@ impl App {
                 }
             }
 
+            self.check_invoke_result();
+            self.check_scan_results();
             self.tick_notifications();
 
             // Calculate view items once per frame
@@ -172,29 +571,13 @@ impl App {
 
             terminal.draw(|f| ui_render(f, self, &view_items))?;
 
-            if event::poll(Duration::from_millis(100))?
-                && let Event::Key(key) = event::read()?
-                    && key.kind == KeyEventKind::Press {
-                        match key.code {
-                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.should_quit = true;
-                            }
-                            KeyCode::Esc => {
-                                if self.app_mode == AppMode::Inspecting {
-                                    self.exit_inspection();
-                                } else if !self.search_query.is_empty() {
-                                    self.search_query.clear();
-                                    self.list_state.select(Some(0));
-                                }
-                            }
-                            
-                            _ => match self.app_mode {
-                                AppMode::Browsing => self.handle_browsing_input(key, &view_items),
-                                AppMode::Inspecting => self.handle_inspecting_input(key),
-                                _ => {}
-                            }
-                        }
-                    }
+            if event::poll(Duration::from_millis(100))? {
+                match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_key(key, &view_items),
+                    Event::Mouse(mouse) => self.handle_mouse(mouse, &view_items),
+                    _ => {}
+                }
+            }
 
             if self.should_quit {
                 break;
@@ -203,7 +586,283 @@ impl App {
         Ok(())
     }
 
+    /// Routes a key press through open modals, global keys and then the current mode.
+    fn handle_key(&mut self, key: event::KeyEvent, view_items: &[TreeItem]) {
+        if self.pending_invoke.is_some() {
+            self.handle_invoke_confirmation(key);
+            return;
+        }
+        if self.pending_instantiation.is_some() {
+            self.handle_instantiation_confirmation(key);
+            return;
+        }
+        if self.input_modal.is_some() {
+            self.handle_input_modal(key);
+            return;
+        }
+        let typing = self.input_mode == InputMode::Search || self.member_search_active;
+        // A plain character bound to quit would otherwise be impossible to type into a filter
+        let quit = self.keymap.quit.matches(&key)
+            && !(typing && matches!(key.code, KeyCode::Char(_)) && key.modifiers.difference(KeyModifiers::SHIFT).is_empty());
+        if self.app_mode == AppMode::Scanning {
+            // Nothing to browse yet
+            self.should_quit = quit;
+            return;
+        }
+        if self.show_help {
+            if matches!(key.code, KeyCode::Char('?') | KeyCode::Esc) {
+                self.show_help = false;
+            } else if quit {
+                self.should_quit = true;
+            }
+            return;
+        }
+        if key.code == KeyCode::Char('?') && !typing {
+            self.show_help = true;
+            return;
+        }
+        if quit {
+            self.should_quit = true;
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                if self.app_mode == AppMode::Browsing && self.input_mode == InputMode::Search {
+                    self.input_mode = InputMode::Navigation;
+                } else if self.app_mode == AppMode::Inspecting && self.member_search_active {
+                    self.member_search_active = false;
+                } else if self.app_mode == AppMode::Inspecting && !self.member_query.is_empty() {
+                    self.member_query.clear();
+                    self.reset_member_selection();
+                } else if self.app_mode == AppMode::Inspecting && self.inspection_receiver.is_some() {
+                    self.cancel_inspection();
+                } else if self.app_mode == AppMode::Inspecting {
+                    self.exit_inspection();
+                } else if !self.search_query.is_empty() {
+                    self.search_query.clear();
+                    self.list_state.select(Some(0));
+                }
+            }
+
+            _ => match self.app_mode {
+                AppMode::Browsing => self.handle_browsing_input(key, view_items),
+                AppMode::Inspecting => self.handle_inspecting_input(key),
+                AppMode::Scanning => {}
+            }
+        }
+    }
+
+    /// Clicking a row in the object list selects and activates it; the wheel moves the selection.
+    fn handle_mouse(&mut self, mouse: event::MouseEvent, view_items: &[TreeItem]) {
+        if self.show_help || self.pending_invoke.is_some() || self.pending_instantiation.is_some() || self.input_modal.is_some() || self.app_mode == AppMode::Scanning {
+            return;
+        }
+        let area = self.list_area;
+        let inside = mouse.column > area.x
+            && mouse.column < area.right().saturating_sub(1)
+            && mouse.row > area.y
+            && mouse.row < area.bottom().saturating_sub(1);
+        if !inside {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Rows start below the top border and are offset by the list's scroll position
+                let idx = (mouse.row - area.y - 1) as usize + self.list_state.offset();
+                if idx < view_items.len() {
+                    self.list_state.select(Some(idx));
+                    self.input_mode = InputMode::Navigation;
+                    self.focus_pane(FocusedPane::List);
+                    self.handle_enter_key(view_items);
+                }
+            }
+            MouseEventKind::ScrollDown => self.next_item(view_items.len()),
+            MouseEventKind::ScrollUp => self.previous_item(view_items.len()),
+            _ => {}
+        }
+    }
+
     fn handle_browsing_input(&mut self, key: event::KeyEvent, view_items: &[TreeItem]) {
+        if self.input_mode == InputMode::Navigation && self.focused_pane == FocusedPane::Details {
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.details_scroll = self.details_scroll.saturating_add(1),
+                KeyCode::Up | KeyCode::Char('k') => self.details_scroll = self.details_scroll.saturating_sub(1),
+                KeyCode::PageDown => self.details_scroll = self.details_scroll.saturating_add(5),
+                KeyCode::PageUp => self.details_scroll = self.details_scroll.saturating_sub(5),
+                _ => self.handle_list_input(key, view_items),
+            }
+            return;
+        }
+        self.handle_list_input(key, view_items);
+    }
+
+    /// Browsing keys that act on the object list, whichever pane has focus.
+    fn handle_list_input(&mut self, key: event::KeyEvent, view_items: &[TreeItem]) {
+        match self.input_mode {
+            InputMode::Search => self.handle_search_input(key, view_items),
+            InputMode::Navigation => match key.code {
+                KeyCode::Tab => self.toggle_pane_focus(),
+                // The query filters the list, so typing it moves focus there
+                _ if self.keymap.search.matches(&key) => {
+                    self.focus_pane(FocusedPane::List);
+                    self.input_mode = InputMode::Search;
+                }
+                _ if self.keymap.expand_all.matches(&key) => self.expand_all(view_items),
+                _ if self.keymap.collapse_all.matches(&key) => self.collapse_all(view_items),
+                // Prefilled with the last prefix, so `Enter` alone moves on to the next match
+                _ if self.keymap.go_to.matches(&key) => {
+                    let last = self.jump_prefix.as_ref().map_or("", |(prefix, _)| prefix.as_str());
+                    self.input_modal = Some(InputModal::new(
+                        "Go to Category",
+                        "Start of a category name",
+                        last,
+                        InputAction::GoToCategory,
+                    ));
+                }
+                _ if self.keymap.regex_search.matches(&key) => self.open_regex_prompt(self.search_query.strip_prefix(REGEX_PREFIX).unwrap_or("").to_string()),
+                _ if self.keymap.inspect.matches(&key) => self.handle_enter_key(view_items),
+                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => self.page_down(view_items.len()),
+                KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => self.page_up(view_items.len()),
+                KeyCode::Down | KeyCode::Char('j') => self.next_item(view_items.len()),
+                KeyCode::Up | KeyCode::Char('k') => self.previous_item(view_items.len()),
+                KeyCode::Char('g') => self.jump_top(view_items.len()),
+                KeyCode::Char('G') => self.jump_bottom(view_items.len()),
+                KeyCode::Char('o') => self.cycle_group_by(),
+                KeyCode::Char('b') => self.toggle_bookmark(view_items),
+                KeyCode::Char('F') => self.toggle_bookmarks_only(view_items),
+                KeyCode::Char('r') => self.toggle_raw_registry(view_items),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.input_modal = Some(InputModal::new(
+                        "Export Filtered Objects",
+                        &format!("File name; the extension picks the format ({})", export_extensions()),
+                        DEFAULT_EXPORT_PATH,
+                        InputAction::ExportFiltered,
+                    ));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Passes a key to the open prompt, running its action once the text is submitted.
+    fn handle_input_modal(&mut self, key: event::KeyEvent) {
+        let Some(modal) = self.input_modal.as_mut() else {
+            return;
+        };
+        match modal.handle_key(key) {
+            InputOutcome::Editing => {}
+            InputOutcome::Cancelled => self.input_modal = None,
+            InputOutcome::Submitted => {
+                if let Some(modal) = self.input_modal.take() {
+                    self.submit_input(modal.action, modal.buffer.trim());
+                }
+            }
+        }
+    }
+
+    fn submit_input(&mut self, action: InputAction, text: &str) {
+        match action {
+            InputAction::ExportFiltered => match self.export_filtered(Path::new(text)) {
+                Ok(count) => self.show_notification(format!("Exported {} objects to '{}'", count, text), 2500),
+                Err(e) => self.show_notification(format!("Export failed: {:#}", e), 4000),
+            },
+            InputAction::GoToCategory if !text.is_empty() => {
+                let view_items = self.get_view_items();
+                if !self.jump_to_category(text, &view_items) {
+                    self.show_notification(format!("No category starts with '{}'", text), 2000);
+                }
+            }
+            InputAction::GoToCategory => {}
+            // An empty pattern clears the regex filter
+            InputAction::RegexFilter if text.is_empty() => {
+                if self.search_query.starts_with(REGEX_PREFIX) {
+                    self.search_query.clear();
+                }
+            }
+            InputAction::RegexFilter => match Regex::new(text) {
+                Ok(_) => {
+                    self.search_query = format!("{}{}", REGEX_PREFIX, text);
+                    self.list_state.select(Some(0));
+                }
+                Err(e) => {
+                    self.show_notification(format!("Invalid regex: {}", e), 4000);
+                    // Reopen with the pattern so it can be corrected
+                    self.open_regex_prompt(text.to_string());
+                }
+            },
+        }
+    }
+
+    fn open_regex_prompt(&mut self, pattern: String) {
+        self.input_modal = Some(InputModal::new(
+            "Regex Filter",
+            "Regular expression matched against name, CLSID and description; empty clears it",
+            &pattern,
+            InputAction::RegexFilter,
+        ));
+    }
+
+    /// Writes the objects passing the current filters, in their current grouping, to `path`.
+    ///
+    /// The format follows the file extension; objects are exported without type details.
+    /// Returns the number of objects written.
+    pub fn export_filtered(&self, path: &Path) -> Result<usize> {
+        let format = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(OutputFormat::from_extension)
+            .filter(|format| export_formats().any(|known| known == *format))
+            .ok_or_else(|| InspectError::Generic(format!("Use one of these file extensions: {}", export_extensions())))?;
+
+        let rows: Vec<ReportRow> = self
+            .filtered_groups()
+            .into_iter()
+            .flat_map(|(category, objects)| objects.into_iter().map(move |obj| (category.clone(), obj, None)))
+            .collect();
+        let count = rows.len();
+        std::fs::write(path, render_report(format, rows, false))
+            .with_context(|| format!("Failed to write '{}'", path.display()))?;
+        Ok(count)
+    }
+
+    /// Switches the details pane between the structured view and the raw registry subtree.
+    fn toggle_raw_registry(&mut self, view_items: &[TreeItem]) {
+        let object_selected = matches!(self.list_state.selected().and_then(|idx| view_items.get(idx)), Some(TreeItem::Object { .. }));
+        if !self.show_raw_registry && !object_selected {
+            self.show_notification("Select an object to view its registry key.".to_string(), 2000);
+            return;
+        }
+        self.show_raw_registry = !self.show_raw_registry;
+        self.details_scroll = 0;
+    }
+
+    /// The raw registry subtree of `obj`, read from the view it was found in.
+    fn raw_registry_for(&mut self, obj: &ComObject) -> &std::result::Result<RegistryNode, String> {
+        if self.raw_registry.as_ref().is_none_or(|(clsid, _)| *clsid != obj.clsid) {
+            let tree = scanner::read_clsid_tree(&obj.clsid, obj.bitness).map_err(|e| format!("{:#}", e));
+            self.raw_registry = Some((obj.clsid.clone(), tree));
+        }
+        &self.raw_registry.as_ref().expect("raw registry was just read").1
+    }
+
+    fn toggle_pane_focus(&mut self) {
+        self.focus_pane(match self.focused_pane {
+            FocusedPane::List => FocusedPane::Details,
+            FocusedPane::Details => FocusedPane::List,
+        });
+    }
+
+    /// Moves browsing focus, starting the details pane from the top.
+    fn focus_pane(&mut self, pane: FocusedPane) {
+        if pane != self.focused_pane {
+            self.focused_pane = pane;
+            self.details_scroll = 0;
+        }
+    }
+
+    /// Edits the search query; `Enter` (or `Esc`) returns to navigation and keeps the filter.
+    fn handle_search_input(&mut self, key: event::KeyEvent, view_items: &[TreeItem]) {
         match key.code {
             KeyCode::Char(c) => {
                 self.search_query.push(c);
@@ -219,43 +878,336 @@ impl App {
             }
             KeyCode::Down => self.next_item(view_items.len()),
             KeyCode::Up => self.previous_item(view_items.len()),
-            KeyCode::Enter => self.handle_enter_key(view_items),
+            KeyCode::Enter => self.input_mode = InputMode::Navigation,
             _ => {}
         }
     }
 
     fn handle_inspecting_input(&mut self, key: event::KeyEvent) {
-        if let Some(details) = &self.selected_object {
-            if details.members.is_empty() {
+        let back = key.code == KeyCode::Backspace
+            || (key.code == KeyCode::Left && key.modifiers.contains(KeyModifiers::ALT));
+        if back && !self.member_search_active {
+            self.inspect_previous();
+            return;
+        }
+        if self.selected_object.is_none() {
+            return;
+        }
+
+        if self.member_search_active {
+            match key.code {
+                KeyCode::Char(c) => self.member_query.push(c),
+                KeyCode::Backspace => { let _ = self.member_query.pop(); }
+                KeyCode::Enter => self.member_search_active = false,
+                _ => return,
+            }
+            self.reset_member_selection();
+            return;
+        }
+        if self.keymap.search.matches(&key) {
+            self.member_search_active = true;
+            self.inspect_focus = InspectFocus::Members;
+            return;
+        }
+
+        match key.code {
+            KeyCode::Tab => {
+                self.inspect_focus = match self.inspect_focus {
+                    InspectFocus::Members => InspectFocus::Details,
+                    InspectFocus::Details => InspectFocus::Members,
+                };
+                return;
+            }
+            KeyCode::PageDown if self.inspect_focus == InspectFocus::Details => {
+                self.details_scroll = self.details_scroll.saturating_add(5);
                 return;
             }
+            KeyCode::PageUp if self.inspect_focus == InspectFocus::Details => {
+                self.details_scroll = self.details_scroll.saturating_sub(5);
+                return;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.inspect_focus == InspectFocus::Details => {
+                self.details_scroll = self.details_scroll.saturating_add(1);
+                return;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.inspect_focus == InspectFocus::Details => {
+                self.details_scroll = self.details_scroll.saturating_sub(1);
+                return;
+            }
+            _ => {}
+        }
 
-            match key.code {
-                KeyCode::Down => self.next_member(details.members.len()),
-                KeyCode::Up => self.previous_member(details.members.len()),
-                KeyCode::Char('c') => self.copy_selected_member_to_clipboard(),
-                KeyCode::Char('C') => self.copy_all_members_to_clipboard(),
-                _ => {}
+        if key.code == KeyCode::F(1) {
+            self.open_help_file();
+            return;
+        }
+
+        if key.code == KeyCode::Char('h') {
+            self.show_hidden = !self.show_hidden;
+            self.reset_member_selection();
+            let state = if self.show_hidden { "Showing" } else { "Hiding" };
+            self.show_notification(format!("{} hidden members", state), 1500);
+            return;
+        }
+
+        let count = self.visible_members().len();
+        if count == 0 {
+            return;
+        }
+
+        match key.code {
+            _ if self.keymap.copy_item.matches(&key) => self.copy_selected_member_to_clipboard(),
+            _ if self.keymap.copy_all.matches(&key) => self.copy_all_members_to_clipboard(),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => self.page_down(count),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => self.page_up(count),
+            KeyCode::PageDown => self.page_down(count),
+            KeyCode::PageUp => self.page_up(count),
+            KeyCode::Down | KeyCode::Char('j') => self.next_member(count),
+            KeyCode::Up | KeyCode::Char('k') => self.previous_member(count),
+            KeyCode::Char('g') => self.jump_top(count),
+            KeyCode::Char('G') => self.jump_bottom(count),
+            KeyCode::Char('J') => self.copy_json_to_clipboard(),
+            KeyCode::Char('o') => self.reveal_server(),
+            KeyCode::Char('p') => self.copy_powershell_to_clipboard(),
+            KeyCode::Char('P') => self.copy_server_path(),
+            KeyCode::Char('M') => self.copy_markdown_to_clipboard(),
+            KeyCode::Char('i') => self.request_invoke(),
+            _ => {}
+        }
+    }
+
+    /// Asks for confirmation before invoking the selected member.
+    fn request_invoke(&mut self) {
+        if !self.inspect.allow_unsafe {
+            self.show_notification("Invoking members requires --unsafe".to_string(), 3000);
+            return;
+        }
+        if self.invoke_receiver.is_some() {
+            self.show_notification("An invocation is already running".to_string(), 2000);
+            return;
+        }
+        let Some(member) = self.member_list_state.selected().and_then(|idx| self.visible_members().get(idx).copied()) else {
+            return;
+        };
+        if !member.is_invocable() {
+            self.show_notification("Only parameterless methods and readable properties can be invoked".to_string(), 3000);
+            return;
+        }
+        self.pending_invoke = Some(member.name().to_string());
+    }
+
+    /// 'y' runs the pending invocation; any other key dismisses the prompt.
+    fn handle_invoke_confirmation(&mut self, key: event::KeyEvent) {
+        let Some(member_name) = self.pending_invoke.take() else {
+            return;
+        };
+        if !matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            return;
+        }
+        let Some(clsid) = self.inspected_object.as_ref().map(|obj| obj.clsid.clone()) else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        self.invoke_receiver = Some(rx);
+        self.invoke_result = Some(format!("{} = (running...)", member_name));
+        let options = self.inspect;
+
+        thread::spawn(move || {
+            let result = com_interop::invoke_member(&clsid, &member_name, &options)
+                .map(|value| format!("{} = {}", member_name, value))
+                .with_context(|| format!("{} failed", member_name));
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Polls the background invocation, if one is running.
+    fn check_invoke_result(&mut self) {
+        let Some(rx) = &self.invoke_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.invoke_result = Some(result.unwrap_or_else(|e| format!("{:#}", e)));
+                self.invoke_receiver = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.invoke_result = Some("Invocation background task failed unexpectedly.".to_string());
+                self.invoke_receiver = None;
             }
         }
     }
 
+    /// Members of the inspected type, minus hidden/restricted ones unless `show_hidden` is set,
+    /// narrowed by the member filter.
+    pub fn visible_members(&self) -> Vec<&Member> {
+        let members = self.selected_object
+            .as_ref()
+            .map(|details| {
+                details.members.iter().filter(|m| self.show_hidden || !m.is_hidden()).collect()
+            })
+            .unwrap_or_default();
+        crate::processor::filter_members(members, &self.member_query)
+    }
+
+    /// Selects the first visible member, if any.
+    fn reset_member_selection(&mut self) {
+        let selection = if self.visible_members().is_empty() { None } else { Some(0) };
+        self.member_list_state.select(selection);
+    }
+
     fn next_item(&mut self, count: usize) {
         if count == 0 { return; }
         let new_idx = match self.list_state.selected() {
             Some(i) => if i >= count - 1 { 0 } else { i + 1 },
             None => 0,
         };
-        self.list_state.select(Some(new_idx));
+        self.list_state.select(Some(new_idx));
+    }
+
+    fn previous_item(&mut self, count: usize) {
+        if count == 0 { return; }
+        let new_idx = match self.list_state.selected() {
+            Some(i) => if i == 0 { count - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.list_state.select(Some(new_idx));
+    }
+
+    /// The list that navigation keys act on: members while inspecting, objects otherwise.
+    fn active_list(&mut self) -> (&mut ListState, usize) {
+        match self.app_mode {
+            AppMode::Inspecting => (&mut self.member_list_state, self.member_list_height),
+            _ => (&mut self.list_state, self.list_height),
+        }
+    }
+
+    /// Moves the active list's selection down by half its visible height.
+    pub fn page_down(&mut self, count: usize) {
+        if count == 0 { return; }
+        let (state, height) = self.active_list();
+        let step = (height / 2).max(1);
+        let new_idx = state.selected().map_or(0, |i| (i + step).min(count - 1));
+        state.select(Some(new_idx));
+    }
+
+    /// Moves the active list's selection up by half its visible height.
+    pub fn page_up(&mut self, count: usize) {
+        if count == 0 { return; }
+        let (state, height) = self.active_list();
+        let step = (height / 2).max(1);
+        let new_idx = state.selected().map_or(0, |i| i.saturating_sub(step));
+        state.select(Some(new_idx));
+    }
+
+    /// Selects the next category whose name starts with `prefix` (case-insensitive),
+    /// wrapping around, so repeated jumps cycle through all matching categories.
+    ///
+    /// Returns whether a category matched.
+    fn jump_to_category(&mut self, prefix: &str, view_items: &[TreeItem]) -> bool {
+        self.jump_prefix = Some((prefix.to_string(), Instant::now()));
+        let prefix = prefix.to_lowercase();
+        let starts_with = |item: &TreeItem| match item {
+            TreeItem::Category { name, .. } => name.to_lowercase().starts_with(&prefix),
+            TreeItem::Object { .. } => false,
+        };
+        let start = self.list_state.selected().map_or(0, |idx| idx + 1);
+        let target = (0..view_items.len())
+            .map(|offset| (start + offset) % view_items.len())
+            .find(|&idx| starts_with(&view_items[idx]));
+        if let Some(idx) = target {
+            self.list_state.select(Some(idx));
+        }
+        target.is_some()
+    }
+
+    /// Selects the first entry of the active list.
+    pub fn jump_top(&mut self, count: usize) {
+        if count == 0 { return; }
+        self.active_list().0.select(Some(0));
+    }
+
+    /// Selects the last entry of the active list.
+    pub fn jump_bottom(&mut self, count: usize) {
+        if count == 0 { return; }
+        self.active_list().0.select(Some(count - 1));
+    }
+
+    /// Switches to the next grouping; category names change, so expansion state is reset.
+    fn cycle_group_by(&mut self) {
+        self.group_by = self.group_by.next();
+        self.expanded_categories.clear();
+        let selection = if self.get_view_items().is_empty() { None } else { Some(0) };
+        self.list_state.select(selection);
+        self.show_notification(format!("Grouping by {}", self.group_by.label()), 1500);
+    }
+
+    /// Bookmarks the selected object, or removes its bookmark.
+    fn toggle_bookmark(&mut self, view_items: &[TreeItem]) {
+        let Some(TreeItem::Object { obj, .. }) = self.list_state.selected().and_then(|idx| view_items.get(idx)) else {
+            return;
+        };
+        if self.bookmarks.remove(&obj.clsid) {
+            self.show_notification(format!("Removed bookmark for {}", obj.name), 1500);
+            if self.bookmarks_only {
+                // The object just left the view; keep the cursor on the row it occupied
+                let count = self.get_view_items().len();
+                let idx = self.list_state.selected().map(|idx| idx.min(count.saturating_sub(1)));
+                self.list_state.select(if count == 0 { None } else { idx });
+            }
+        } else {
+            self.bookmarks.insert(obj.clsid.clone());
+            self.show_notification(format!("Bookmarked {}", obj.name), 1500);
+        }
+    }
+
+    /// Shows only bookmarked objects, or the full tree again.
+    fn toggle_bookmarks_only(&mut self, view_items: &[TreeItem]) {
+        let selected = self.list_state.selected().and_then(|idx| view_items.get(idx)).cloned();
+        self.bookmarks_only = !self.bookmarks_only;
+        self.reselect(selected);
+        let message = if self.bookmarks_only { "Showing bookmarks only" } else { "Showing all objects" };
+        self.show_notification(message.to_string(), 1500);
+    }
+
+    /// Expands every category, keeping the current selection in place.
+    fn expand_all(&mut self, view_items: &[TreeItem]) {
+        let selected = self.list_state.selected().and_then(|idx| view_items.get(idx)).cloned();
+        self.expanded_categories = crate::processor::process(self.objects_list.clone(), &FilterOptions::fuzzy("", self.group_by))
+            .into_keys()
+            .collect();
+        self.reselect(selected);
+    }
+
+    /// Collapses every category, moving the selection to the category that held it.
+    fn collapse_all(&mut self, view_items: &[TreeItem]) {
+        let selected = self.list_state.selected().and_then(|idx| view_items.get(..=idx)).and_then(|items| {
+            items.iter().rev().find(|item| matches!(item, TreeItem::Category { .. }))
+        });
+        let category = match selected {
+            Some(TreeItem::Category { name, .. }) => Some(name.clone()),
+            _ => None,
+        };
+        self.expanded_categories.clear();
+        let view_items = self.get_view_items();
+        let idx = view_items
+            .iter()
+            .position(|item| matches!(item, TreeItem::Category { name, .. } if Some(name) == category.as_ref()));
+        self.list_state.select(idx.or(if view_items.is_empty() { None } else { Some(0) }));
     }
 
-    fn previous_item(&mut self, count: usize) {
-        if count == 0 { return; }
-        let new_idx = match self.list_state.selected() {
-            Some(i) => if i == 0 { count - 1 } else { i - 1 },
-            None => 0,
-        };
-        self.list_state.select(Some(new_idx));
+    /// Selects `target` again after the view has been rebuilt, falling back to the first item.
+    fn reselect(&mut self, target: Option<TreeItem>) {
+        let view_items = self.get_view_items();
+        let idx = target.and_then(|target| {
+            view_items.iter().position(|item| match (item, &target) {
+                (TreeItem::Category { name, .. }, TreeItem::Category { name: old, .. }) => name == old,
+                (TreeItem::Object { obj, .. }, TreeItem::Object { obj: old, .. }) => obj.clsid == old.clsid && obj.name == old.name,
+                _ => false,
+            })
+        });
+        self.list_state.select(idx.or(if view_items.is_empty() { None } else { Some(0) }));
     }
 
     fn handle_enter_key(&mut self, view_items: &[TreeItem]) {
@@ -270,8 +1222,8 @@ impl App {
                             self.expanded_categories.insert(name.clone());
                         }
                     },
-                    TreeItem::Object(obj) => {
-                        self.inspect_object(obj.clsid.clone());
+                    TreeItem::Object { obj, .. } => {
+                        self.inspect_object(obj.as_ref().clone());
                     }
                 }
             }
@@ -295,22 +1247,96 @@ impl App {
         self.member_list_state.select(Some(new_idx));
     }
 
-    fn inspect_object(&mut self, clsid: String) {
+    /// Inspects `obj`, first asking for confirmation when inspection would have to instantiate it.
+    fn inspect_object(&mut self, obj: ComObject) {
+        if self.inspect.allow_unsafe && !self.skip_instantiate_confirm && !scanner::has_registered_typelib(&obj) {
+            self.pending_instantiation = Some(obj);
+            return;
+        }
+        self.start_inspection(obj);
+    }
+
+    /// Answers the instantiation prompt: `y` inspects once, `a` also stops asking this session.
+    fn handle_instantiation_confirmation(&mut self, key: event::KeyEvent) {
+        let Some(obj) = self.pending_instantiation.take() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => self.start_inspection(obj),
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                self.skip_instantiate_confirm = true;
+                self.start_inspection(obj);
+            }
+            _ => {}
+        }
+    }
+
+    /// Re-inspects the object inspected before the current one, without asking for confirmation again.
+    fn inspect_previous(&mut self) {
+        if self.inspection_history.len() < 2 {
+            self.show_notification("No earlier object in history.".to_string(), 1500);
+            return;
+        }
+        self.inspection_history.pop();
+        let Some(clsid) = self.inspection_history.last() else {
+            return;
+        };
+        match self.objects_list.iter().find(|obj| &obj.clsid == clsid).cloned() {
+            Some(obj) => self.start_inspection(obj),
+            None => self.show_notification(format!("{} is no longer in the object list.", clsid), 2000),
+        }
+    }
+
+    /// Names of the last few inspected objects joined as a breadcrumb trail.
+    fn history_breadcrumb(&self) -> String {
+        const SHOWN: usize = 3;
+        let start = self.inspection_history.len().saturating_sub(SHOWN);
+        let names: Vec<&str> = self.inspection_history[start..]
+            .iter()
+            .map(|clsid| {
+                self.objects_list.iter().find(|obj| &obj.clsid == clsid).map_or(clsid.as_str(), |obj| obj.name.as_str())
+            })
+            .collect();
+        let trail = names.join(" › ");
+        if start > 0 { format!("… › {}", trail) } else { trail }
+    }
+
+    fn start_inspection(&mut self, obj: ComObject) {
+        if self.inspection_history.last() != Some(&obj.clsid) {
+            self.inspection_history.push(obj.clsid.clone());
+        }
+        self.cancel_pending_inspection();
         self.selected_object = None;
         self.error_message = None;
         self.inspection_receiver = None;
+        self.inspection_cancelled = false;
         self.member_list_state = ListState::default();
+        self.inspect_focus = InspectFocus::Members;
+        self.details_scroll = 0;
+        self.member_query.clear();
+        self.member_search_active = false;
+        self.invoke_receiver = None;
+        self.invoke_result = None;
         
         self.app_mode = AppMode::Inspecting;
 
         let (tx, rx) = mpsc::channel();
         self.inspection_receiver = Some(rx);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.inspection_cancel = Some(cancel.clone());
 
-        let clsid_clone = clsid.clone();
-        let allow_unsafe = self.unsafe_mode;
+        let clsid_clone = obj.clsid.clone();
+        let options = self.inspect;
+        self.inspected_object = Some(obj);
         
         thread::spawn(move || {
-            let _com_guard = match com_interop::initialize_com() {
+            // A blocked CoCreateInstance cannot be interrupted, so cancellation is checked
+            // between steps and the result of a cancelled run is simply dropped
+            if cancel.load(Ordering::Relaxed) {
+                return;
+            }
+
+            let _com_guard = match com_interop::initialize_com(options.apartment) {
                 Ok(guard) => guard,
                 Err(e) => {
                     let _ = tx.send(Err(e));
@@ -318,138 +1344,256 @@ impl App {
                 }
             };
 
-            let result = com_interop::get_type_info(&clsid_clone, allow_unsafe)
+            if cancel.load(Ordering::Relaxed) {
+                return;
+            }
+
+            let result = com_interop::get_type_info_with(&clsid_clone, &options)
                 .context(format!("Failed to inspect object {}. \nThis may be due to permissions or missing registration.", clsid_clone));
             
-            let _ = tx.send(result);
+            if !cancel.load(Ordering::Relaxed) {
+                let _ = tx.send(result);
+            }
         });
     }
 
+    /// Signals the running inspection thread (if any) to stop and stops listening for its result.
+    fn cancel_pending_inspection(&mut self) {
+        if let Some(cancel) = self.inspection_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.inspection_receiver = None;
+    }
+
+    /// Abandons a slow inspection but stays in the inspection view to show that it was cancelled.
+    fn cancel_inspection(&mut self) {
+        self.cancel_pending_inspection();
+        self.inspection_cancelled = true;
+    }
+
     fn exit_inspection(&mut self) {
         if self.app_mode == AppMode::Inspecting {
+            self.cancel_pending_inspection();
+            self.inspection_cancelled = false;
             self.app_mode = AppMode::Browsing;
+            self.inspected_object = None;
             self.selected_object = None;
             self.error_message = None;
             self.inspection_receiver = None;
             self.member_list_state = ListState::default();
+            self.invoke_receiver = None;
+            self.invoke_result = None;
+            // The browsing details pane shares the scroll offset
+            self.details_scroll = 0;
         }
     }
 
     fn copy_selected_member_to_clipboard(&mut self) {
-        if let Some(details) = &self.selected_object
-            && let Some(idx) = self.member_list_state.selected()
-                && let Some(member) = details.members.get(idx) {
+        if let Some(idx) = self.member_list_state.selected()
+                && let Some(member) = self.visible_members().get(idx).copied() {
                     let text_to_copy = match member {
-                        Member::Method { name, signature, .. } => {
-                            format!("{}{}", name, signature)
+                        Member::Method { name, params, return_type, .. } => {
+                            format!("{}{}", name, com_interop::format_signature(params, return_type))
                         },
                         Member::Property { name, value_type, .. } => {
                             format!("{}: {}", name, value_type)
+                        },
+                        Member::Constant { name, value, .. } => {
+                            format!("{} = {}", name, value)
                         }
                     };
 
-                    match Clipboard::new() {
-                        Ok(mut clipboard) => {
-                            if let Err(e) = clipboard.set_text(text_to_copy) {
-                                self.show_notification(format!("Clipboard error: {}", e), 3000);
-                            } else {
-                                self.show_notification("Copied selection!".to_string(), 2000);
-                            }
-                        },
-                        Err(e) => {
-                             self.show_notification(format!("Clipboard init error: {}", e), 3000);
-                        }
-                    }
+                    self.copy_to_clipboard(text_to_copy, "Copied selection!");
                 }
     }
 
     fn copy_all_members_to_clipboard(&mut self) {
-         if let Some(details) = &self.selected_object {
-            let mut buffer = String::new();
-            buffer.push_str(&format!("Type: {}\n", details.name));
-            buffer.push_str(&format!("Description: {}\n", details.description));
-            buffer.push('\n');
-            
-            for member in &details.members {
-                match member {
-                    Member::Method { name, signature, .. } => {
-                        buffer.push_str(&format!("Method {}{}\n", name, signature));
-                    },
-                    Member::Property { name, value_type, access } => {
-                         let access_str = match access {
-                            AccessMode::Read => "Read",
-                            AccessMode::Write => "Write",
-                            AccessMode::ReadWrite => "Read/Write",
-                        };
-                        buffer.push_str(&format!("Property {}: {} [{}]\n", name, value_type, access_str));
-                    }
-                }
+        if let Some(visible) = self.visible_details() {
+            self.copy_to_clipboard(visible.to_text(), "Copied all members!");
+        }
+    }
+
+    /// Copies the inspected type (visible members only) as pretty-printed JSON.
+    fn copy_json_to_clipboard(&mut self) {
+        if let Some(visible) = self.visible_details() {
+            match serde_json::to_string_pretty(&visible) {
+                Ok(json) => self.copy_to_clipboard(json, "Copied as JSON!"),
+                Err(e) => self.show_notification(format!("JSON error: {}", e), 3000),
             }
+        }
+    }
 
-            match Clipboard::new() {
-                Ok(mut clipboard) => {
-                    if let Err(e) = clipboard.set_text(buffer) {
-                        self.show_notification(format!("Clipboard error: {}", e), 3000);
-                    } else {
-                        self.show_notification("Copied all members!".to_string(), 2000);
-                    }
-                },
-                Err(e) => {
-                     self.show_notification(format!("Clipboard init error: {}", e), 3000);
+    /// Copies a PowerShell snippet that creates the inspected object and sketches the visible members.
+    fn copy_powershell_to_clipboard(&mut self) {
+        let Some(visible) = self.visible_details() else {
+            return;
+        };
+        let (progid, clsid) = match &self.inspected_object {
+            Some(obj) => (obj.has_progid.then(|| obj.name.clone()), obj.clsid.clone()),
+            None => (None, String::new()),
+        };
+        let snippet = com_interop::to_powershell_snippet(&visible, progid.as_deref(), &clsid);
+        self.copy_to_clipboard(snippet, "Copied as PowerShell!");
+    }
+
+    /// Copies the visible members as a Markdown table.
+    fn copy_markdown_to_clipboard(&mut self) {
+        if let Some(visible) = self.visible_details() {
+            self.copy_to_clipboard(crate::export::members_to_markdown(&visible), "Copied as Markdown!");
+        }
+    }
+
+    /// The inspected type narrowed to the members currently shown in the list.
+    fn visible_details(&self) -> Option<TypeDetails> {
+        let details = self.selected_object.as_ref()?;
+        Some(TypeDetails {
+            members: self.visible_members().into_iter().cloned().collect(),
+            ..details.clone()
+        })
+    }
+
+    /// Opens Explorer with the inspected object's server binary selected.
+    fn reveal_server(&mut self) {
+        let Some(path) = self.inspected_object.as_ref().and_then(|obj| obj.server_path.clone()) else {
+            self.show_notification("No server path registered for this object.".to_string(), 2000);
+            return;
+        };
+        if let Err(e) = scanner::reveal_in_explorer(&path) {
+            self.show_notification(format!("Cannot open server: {:#}", e), 3000);
+        }
+    }
+
+    /// Opens the help file the inspected type's library points to, at its help context.
+    fn open_help_file(&mut self) {
+        let Some((file, context)) = self.selected_object.as_ref().and_then(|details| Some((details.help_file.clone()?, details.help_context))) else {
+            self.show_notification("No help file registered for this type.".to_string(), 2000);
+            return;
+        };
+        if let Err(e) = com_interop::open_help_file(&file, context) {
+            self.show_notification(format!("Cannot open help: {:#}", e), 3000);
+        }
+    }
+
+    /// Copies the inspected object's raw server registration (path plus any arguments).
+    fn copy_server_path(&mut self) {
+        match self.inspected_object.as_ref().and_then(|obj| obj.server_path.clone()) {
+            Some(path) => self.copy_to_clipboard(path, "Copied server path!"),
+            None => self.show_notification("No server path registered for this object.".to_string(), 2000),
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: String, success_message: &str) {
+        match Clipboard::new() {
+            Ok(mut clipboard) => {
+                if let Err(e) = clipboard.set_text(text) {
+                    self.show_notification(format!("Clipboard error: {}", e), 3000);
+                } else {
+                    self.show_notification(success_message.to_string(), 2000);
                 }
+            },
+            Err(e) => {
+                 self.show_notification(format!("Clipboard init error: {}", e), 3000);
             }
         }
     }
 }
 
 fn ui_render(f: &mut Frame, app: &mut App, view_items: &[TreeItem]) {
+    if app.app_mode == AppMode::Scanning {
+        render_scanning(f, app);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(u16::from(app.scan_warning.is_some())),
             Constraint::Min(0),
             Constraint::Length(1),
         ])
         .split(f.area());
 
+    if let Some(warning) = &app.scan_warning {
+        let banner = Paragraph::new(Span::styled(format!(" ⚠ {}", warning), Style::default().fg(Color::Black).bg(Color::Yellow)))
+            .style(Style::default().bg(Color::Yellow));
+        f.render_widget(banner, chunks[0]);
+    }
+
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Percentage(50),
             Constraint::Percentage(50),
         ])
-        .split(chunks[0]);
+        .split(chunks[1]);
 
     // Left Pane: Object List (Tree View)
     let list_items: Vec<ListItem> = view_items.iter().map(|item| {
         match item {
-            TreeItem::Category { name, count, expanded } => {
+            TreeItem::Category { name, stats, expanded } => {
                 let icon = if *expanded { "▼" } else { "▶" };
                 ListItem::new(Line::from(vec![
                     Span::styled(format!("{} {} ", icon, name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                    Span::styled(format!("({})", count), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!("({})", stats.count), Style::default().fg(Color::DarkGray)),
                 ]))
             },
-            TreeItem::Object(obj) => {
-                ListItem::new(Line::from(vec![
-                    Span::raw("  "), // Indentation
-                    Span::raw(&obj.name),
-                    Span::styled(format!(" ({})", obj.clsid), Style::default().fg(Color::DarkGray)),
-                ]))
+            TreeItem::Object { obj, matched } => {
+                let mut spans = vec![Span::raw("  ")]; // Indentation
+                if app.bookmarks.contains(&obj.clsid) {
+                    spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
+                }
+                spans.extend(highlight_spans(&obj.name, matched, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+                spans.push(Span::styled(format!(" ({})", obj.clsid), Style::default().fg(Color::DarkGray)));
+                // Orphaned registrations point at a server binary that no longer exists
+                let style = if obj.orphaned { Style::default().fg(Color::Red) } else { Style::default() };
+                ListItem::new(Line::from(spans)).style(style)
             }
         }
     }).collect();
 
-    let list_title = if app.search_query.is_empty() {
-        "COM Objects".to_string()
+    let total = app.objects_list.len();
+    let mut list_title = if app.search_query.is_empty() && !app.bookmarks_only {
+        format!("COM Objects — {} total", total)
     } else {
-        format!("COM Objects (Filter: '{}')", app.search_query)
+        // Every category is expanded while filtering, so the visible objects are the matches;
+        // count CLSIDs, since component-category grouping lists an object under each of its categories
+        let matching = view_items
+            .iter()
+            .filter_map(|item| match item {
+                TreeItem::Object { obj, .. } => Some(obj.clsid.as_str()),
+                TreeItem::Category { .. } => None,
+            })
+            .collect::<HashSet<_>>()
+            .len();
+        format!("COM Objects — {} of {}", matching, total)
     };
+    if app.bookmarks_only {
+        list_title.push_str(" [Bookmarks]");
+    }
+    if let Some(duration) = app.scan_duration {
+        list_title.push_str(&format!(" (scanned in {:.1}s)", duration.as_secs_f32()));
+    }
+    if let Some(Err(e)) = app.query_regex() {
+        // regex errors span several lines; the last one names the problem
+        let reason = e.to_string().lines().last().unwrap_or_default().to_string();
+        list_title.push_str(&format!(" [Invalid regex: {}]", reason));
+    }
+    if app.input_mode == InputMode::Search {
+        list_title.push_str(&format!(" (Search: '{}_')", app.search_query));
+    } else if !app.search_query.is_empty() {
+        list_title.push_str(&format!(" (Filter: '{}')", app.search_query));
+    }
 
+    let list_focused = app.app_mode == AppMode::Browsing && app.focused_pane == FocusedPane::List;
     let list = List::new(list_items)
-        .block(Block::default().borders(Borders::ALL).title(list_title))
+        .block(Block::default().borders(Borders::ALL).title(list_title)
+            .border_style(if list_focused { Style::default().fg(Color::Yellow) } else { Style::default() }))
         .highlight_style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD))
         .highlight_symbol(" "); 
     
+    // Borders take one row each
+    app.list_height = main_chunks[0].height.saturating_sub(2) as usize;
+    app.list_area = main_chunks[0];
     f.render_stateful_widget(list, main_chunks[0], &mut app.list_state);
 
     // Right Pane: Details or Inspection
@@ -470,94 +1614,207 @@ fn ui_render(f: &mut Frame, app: &mut App, view_items: &[TreeItem]) {
                 
                 f.render_widget(p, right_pane_area);
             } else if let Some(details) = &app.selected_object {
-                // Split right pane into Metadata and Members
+                // 1. Metadata Block
+                let mut meta_text = vec![
+                    Line::from(vec![Span::styled("Name: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&details.name)]),
+                    Line::from(vec![Span::styled("Description: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&details.description)]),
+                    Line::from(vec![Span::styled("GUID: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&details.guid)]),
+                    Line::from(vec![
+                        Span::styled("Kind: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw(format!("{} ", details.typekind)),
+                        Span::styled(
+                            details.interface_kind.map(|kind| format!("{} ", kind.badge())).unwrap_or_default(),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        Span::raw(" "),
+                        Span::styled("Version: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw(format!("{}.{}", details.version.0, details.version.1)),
+                    ]),
+                ];
+                if let Some(app_id) = app.inspected_object.as_ref().and_then(|obj| obj.app_id.as_ref()) {
+                    meta_text.push(Line::from(vec![Span::styled("DCOM: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(app_id_label(app_id))]));
+                }
+                if let Some(help) = details.help_label() {
+                    meta_text.push(Line::from(vec![Span::styled("Help: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(help)]));
+                }
+                if let Some(result) = &app.invoke_result {
+                    meta_text.push(Line::from(vec![Span::styled("Result: ", Style::default().add_modifier(Modifier::BOLD)), Span::styled(result, Style::default().fg(Color::Green))]));
+                }
+                meta_text.push(Line::from(""));
+                meta_text.push(Line::from(Span::styled("Copy: 'c' (Item) | 'Shift+C' (All) | 'J' (JSON) | 'M' (Markdown) | 'p' (PowerShell) | Hidden: 'h' | Invoke: 'i' | Focus: <Tab> | Scroll: <PgUp>/<PgDn>", Style::default().fg(Color::DarkGray))));
+
+                // Split right pane into Metadata, sized to its wrapped text but at most half the
+                // pane (the rest scrolls), and Members
+                let meta_height = wrapped_height(&meta_text, right_pane_area.width.saturating_sub(2)).saturating_add(2);
                 let right_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([
-                        Constraint::Length(8), // Fixed height for metadata
-                        Constraint::Min(0),    // Remaining for members
+                        Constraint::Length(meta_height.clamp(4, (right_pane_area.height / 2).max(4))),
+                        Constraint::Min(0), // Remaining for members
                     ])
                     .split(right_pane_area);
-
-                // 1. Metadata Block
-                let meta_text = vec![
-                    Line::from(vec![Span::styled("Name: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&details.name)]),
-                    Line::from(vec![Span::styled("Description: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&details.description)]),
-                    Line::from(""),
-                    Line::from(Span::styled("Copy: 'c' (Item) | 'Shift+C' (All)", Style::default().fg(Color::DarkGray))),
-                ];
                 
+                // Wrapped lines can exceed this, but it keeps the last logical line reachable
+                app.details_scroll = app.details_scroll.min(meta_text.len().saturating_sub(1) as u16);
+                let focused = Style::default().fg(Color::Yellow);
                 let meta_block = Paragraph::new(meta_text)
-                    .block(Block::default().borders(Borders::ALL).title("Object Details"))
-                    .wrap(ratatui::widgets::Wrap { trim: true });
+                    .block(Block::default().borders(Borders::ALL).title(format!("Object Details — {}", app.history_breadcrumb()))
+                        .border_style(if app.inspect_focus == InspectFocus::Details { focused } else { Style::default() }))
+                    .wrap(ratatui::widgets::Wrap { trim: true })
+                    .scroll((app.details_scroll, 0));
                 f.render_widget(meta_block, right_chunks[0]);
 
                 // 2. Members List Block
-                let members_list: Vec<ListItem> = details.members.iter().map(|m| {
-                    match m {
-                        Member::Method { name, signature, .. } => {
-                            ListItem::new(Line::from(vec![
+                let visible_members = app.visible_members();
+                let hidden_count = details.members.iter().filter(|m| m.is_hidden()).count();
+                // Borders plus the "> " highlight symbol
+                let doc_width = right_chunks[1].width.saturating_sub(4) as usize;
+                let selected_member = app.member_list_state.selected();
+                let members_list: Vec<ListItem> = visible_members.iter().enumerate().map(|(idx, m)| {
+                    let mut lines = vec![match m {
+                        Member::Method { name, params, return_type, .. } => {
+                            Line::from(vec![
                                 Span::styled("M ", Style::default().fg(Color::Cyan)), 
-                                Span::raw(format!("{}{}", name, signature))
-                            ]))
+                                Span::raw(format!("{}{}", name, com_interop::format_signature(params, return_type)))
+                            ])
                         },
-                        Member::Property { name, value_type, access } => {
+                        Member::Property { name, value_type, access, .. } => {
                             let access_badge = match access {
                                 AccessMode::Read => "R",
                                 AccessMode::Write => "W",
                                 AccessMode::ReadWrite => "RW",
                             };
-                            ListItem::new(Line::from(vec![
+                            Line::from(vec![
                                 Span::styled("P ", Style::default().fg(Color::Green)),
                                 Span::styled(format!("[{}] ", access_badge), Style::default().fg(Color::DarkGray)),
                                 Span::raw(format!("{}: {}", name, value_type))
-                            ]))
+                            ])
+                        },
+                        Member::Constant { name, value, value_type, .. } => {
+                            Line::from(vec![
+                                Span::styled("C ", Style::default().fg(Color::Magenta)),
+                                Span::raw(format!("{}: {} = {}", name, value_type, value))
+                            ])
+                        }
+                    }];
+
+                    // Show the help string beneath the selected member
+                    if selected_member == Some(idx) && !m.doc().is_empty() {
+                        for chunk in wrap_text(m.doc(), doc_width.saturating_sub(2)) {
+                            lines.push(Line::from(Span::styled(
+                                format!("  {}", chunk),
+                                Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+                            )));
                         }
                     }
+
+                    let item = ListItem::new(lines);
+                    if m.is_hidden() {
+                        item.style(Style::default().add_modifier(Modifier::DIM))
+                    } else {
+                        item
+                    }
                 }).collect();
 
+                let mut members_title = match (hidden_count, app.show_hidden) {
+                    (0, _) => "Members".to_string(),
+                    (n, true) => format!("Members ({} hidden shown)", n),
+                    (n, false) => format!("Members ({} hidden)", n),
+                };
+                if app.member_search_active {
+                    members_title.push_str(&format!(" [Filter: '{}_']", app.member_query));
+                } else if !app.member_query.is_empty() {
+                    members_title.push_str(&format!(" [Filter: '{}']", app.member_query));
+                }
                 let members_block = List::new(members_list)
-                    .block(Block::default().borders(Borders::ALL).title("Members")
+                    .block(Block::default().borders(Borders::ALL).title(members_title)
                     .style(Style::default().fg(Color::Yellow)))
                     .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
                     .highlight_symbol("> ");
                 
+                app.member_list_height = right_chunks[1].height.saturating_sub(2) as usize;
                 f.render_stateful_widget(members_block, right_chunks[1], &mut app.member_list_state);
 
+            } else if app.inspection_cancelled {
+                let p = Paragraph::new(vec![
+                    Line::from(Span::styled("Cancelled", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+                    Line::from(""),
+                    Line::from(Span::styled("Press <Esc> to return to the list.", Style::default().fg(Color::Gray))),
+                ])
+                .block(Block::default().borders(Borders::ALL).title("Details"));
+                f.render_widget(p, right_pane_area);
             } else {
-                let p = Paragraph::new("Loading...").block(Block::default().borders(Borders::ALL).title("Details"));
+                let p = Paragraph::new("Loading... (press <Esc> to cancel)").block(Block::default().borders(Borders::ALL).title("Details"));
                 f.render_widget(p, right_pane_area);
             }
         },
-        _ => {
+        // Drawn full-screen by render_scanning
+        AppMode::Scanning => {},
+        AppMode::Browsing => {
             // Browsing Mode Details
+            let details_focused = app.focused_pane == FocusedPane::Details;
+            let raw_object = match app.list_state.selected().and_then(|idx| view_items.get(idx)) {
+                Some(TreeItem::Object { obj, .. }) if app.show_raw_registry => Some(obj),
+                _ => None,
+            };
             let right_pane_block = Block::default()
                 .borders(Borders::ALL)
-                .title("Details");
+                .title(if raw_object.is_some() { "Raw Registry ('r' for details)" } else { "Details" })
+                .border_style(if details_focused { Style::default().fg(Color::Yellow) } else { Style::default() });
 
-            let details_text = if let Some(idx) = app.list_state.selected() {
+            let details_text = if let Some(obj) = raw_object {
+                match app.raw_registry_for(obj) {
+                    Ok(tree) => {
+                        let mut lines = Vec::new();
+                        registry_tree_lines(tree, 0, &mut lines);
+                        lines
+                    }
+                    Err(e) => vec![Line::from(Span::styled(format!("Cannot read the registry key: {}", e), Style::default().fg(Color::Red)))],
+                }
+            } else if let Some(idx) = app.list_state.selected() {
                 if let Some(item) = view_items.get(idx) {
                     match item {
-                        TreeItem::Category { name, count, .. } => vec![
+                        TreeItem::Category { name, stats, .. } => vec![
                             Line::from(Span::styled("Category: ", Style::default().add_modifier(Modifier::BOLD))),
                             Line::from(name.as_str()),
                             Line::from(""),
-                            Line::from(format!("Contains {} objects", count)),
+                            Line::from(format!("Contains {} objects", stats.count)),
+                            Line::from(format!("  With TypeLib:     {}", stats.with_typelib)),
+                            Line::from(format!("  With server path: {}", stats.with_server)),
+                            Line::from(""),
+                            Line::from(Span::styled("Bitness: ", Style::default().add_modifier(Modifier::BOLD))),
+                            Line::from(format!("  Native only: {}  32-bit only: {}  Both views: {}", stats.native, stats.x86, stats.both)),
                             Line::from(""),
                             Line::from(Span::styled("Hint: Press <Enter> to expand/collapse.", Style::default().fg(Color::Gray))),
                         ],
-                        TreeItem::Object(obj) => {
+                        TreeItem::Object { obj, .. } => {
+                            let name_label = if obj.has_progid { "Name: " } else { "Name (no ProgID): " };
                             vec![
-                                Line::from(Span::styled("Name: ", Style::default().add_modifier(Modifier::BOLD))),
+                                Line::from(Span::styled(name_label, Style::default().add_modifier(Modifier::BOLD))),
                                 Line::from(obj.name.as_str()),
                                 Line::from(""),
+                                Line::from(Span::styled("ProgIDs: ", Style::default().add_modifier(Modifier::BOLD))),
+                                Line::from(if obj.progids.is_empty() { "None".to_string() } else { obj.progids.join(", ") }),
+                                Line::from(""),
                                 Line::from(Span::styled("CLSID: ", Style::default().add_modifier(Modifier::BOLD))),
                                 Line::from(obj.clsid.as_str()),
                                 Line::from(""),
                                 Line::from(Span::styled("Description: ", Style::default().add_modifier(Modifier::BOLD))),
                                 Line::from(obj.description.as_str()),
                                 Line::from(""),
-                                Line::from(Span::styled("Hint: Press <Enter> to inspect details.", Style::default().fg(Color::Gray))),
+                                Line::from(Span::styled("Server: ", Style::default().add_modifier(Modifier::BOLD))),
+                                Line::from(server_label(obj)),
+                                Line::from(""),
+                                Line::from(Span::styled("Threading Model: ", Style::default().add_modifier(Modifier::BOLD))),
+                                Line::from(obj.threading_model.as_deref().unwrap_or("N/A")),
+                                Line::from(""),
+                                Line::from(Span::styled("Bitness: ", Style::default().add_modifier(Modifier::BOLD))),
+                                Line::from(format!("{:?}", obj.bitness)),
+                                Line::from(""),
+                                Line::from(Span::styled("Implemented Categories: ", Style::default().add_modifier(Modifier::BOLD))),
+                                Line::from(if obj.categories.is_empty() { "None".to_string() } else { obj.categories.join(", ") }),
+                                Line::from(""),
+                                Line::from(Span::styled("Hint: Press <Enter> to inspect details, 'r' for the raw registry key.", Style::default().fg(Color::Gray))),
                             ]
                         }
                     }
@@ -568,9 +1825,17 @@ fn ui_render(f: &mut Frame, app: &mut App, view_items: &[TreeItem]) {
                 vec![Line::from("No object selected")]
             };
 
+            // Inspection shares the scroll offset, so it only applies while this pane has focus
+            let scroll = if details_focused {
+                app.details_scroll = app.details_scroll.min(details_text.len().saturating_sub(1) as u16);
+                app.details_scroll
+            } else {
+                0
+            };
             let details = Paragraph::new(details_text)
                 .block(right_pane_block)
-                .wrap(ratatui::widgets::Wrap { trim: true });
+                .wrap(ratatui::widgets::Wrap { trim: true })
+                .scroll((scroll, 0));
             
             f.render_widget(details, right_pane_area);
         }
@@ -580,17 +1845,18 @@ fn ui_render(f: &mut Frame, app: &mut App, view_items: &[TreeItem]) {
     let current_selection_name = if let Some(idx) = app.list_state.selected() {
          match view_items.get(idx) {
              Some(TreeItem::Category { name, .. }) => format!("Category: {}", name),
-             Some(TreeItem::Object(obj)) => obj.name.clone(),
+             Some(TreeItem::Object { obj, .. }) => obj.name.clone(),
              None => "Unknown".to_string(),
          }
     } else {
         "None".to_string()
     };
 
-    let mode_str = match app.app_mode {
-        AppMode::Scanning => "SCANNING",
-        AppMode::Browsing => "BROWSING",
-        AppMode::Inspecting => "INSPECTING",
+    let mode_str = match (app.app_mode, app.input_mode) {
+        (AppMode::Scanning, _) => "SCANNING",
+        (AppMode::Browsing, InputMode::Navigation) => "BROWSING",
+        (AppMode::Browsing, InputMode::Search) => "SEARCH",
+        (AppMode::Inspecting, _) => "INSPECTING",
     };
 
     let search_status = if app.search_query.is_empty() {
@@ -599,16 +1865,102 @@ fn ui_render(f: &mut Frame, app: &mut App, view_items: &[TreeItem]) {
         format!(" | Search: '{}'", app.search_query)
     };
 
+    let jump_status = match &app.jump_prefix {
+        Some((prefix, jumped)) if jumped.elapsed() < Duration::from_millis(1500) => format!(" | Go to: '{}'", prefix),
+        _ => String::new(),
+    };
+
     let status_text = format!(
-        "Mode: {} | Obj: {} {} | Unsafe: {} | <Enter>: Expand/Insp | <Esc>: Back", 
+        "Mode: {} | Obj: {} {}{} | Unsafe: {} | </>: Search | <?>: Help | <Esc>: Back", 
         mode_str,
         current_selection_name,
         search_status,
-        app.unsafe_mode
+        jump_status,
+        app.inspect.allow_unsafe
     );
     let status = Paragraph::new(status_text)
         .style(Style::default().bg(Color::DarkGray).fg(Color::White));
-    f.render_widget(status, chunks[1]);
+    f.render_widget(status, chunks[2]);
+
+    // Render Help Overlay
+    if app.show_help {
+        let lines = help_lines(&app.keymap);
+        let area = centered_rect_fixed_height(60, lines.len() as u16 + 2, f.area());
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Help ('?' or <Esc> to close)"))
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    // Render Text Prompt
+    if let Some(modal) = &app.input_modal {
+        let lines = vec![
+            Line::from(modal.prompt.as_str()),
+            Line::from(""),
+            modal.input_line(),
+            Line::from(""),
+            Line::from(Span::styled("<Enter> confirm | <Esc> cancel | ←/→ Home/End move", Style::default().fg(Color::DarkGray))),
+        ];
+        // The popup spans 60% of the screen; borders take a column each side
+        let width = (u32::from(f.area().width) * 60 / 100) as u16;
+        let height = wrapped_height(&lines, width.saturating_sub(2)).saturating_add(2);
+        let area = centered_rect_fixed_height(60, height, f.area());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(modal.title.as_str())
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false });
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    // Render Invocation Confirmation Modal
+    if let Some(member_name) = &app.pending_invoke {
+        let area = centered_rect_fixed_height(60, 6, f.area());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Confirm Invocation")
+            .style(Style::default().bg(Color::Red).fg(Color::White).add_modifier(Modifier::BOLD));
+        let paragraph = Paragraph::new(vec![
+            Line::from(format!("Instantiate the object and call '{}'?", member_name)),
+            Line::from("This executes real code in the COM server."),
+            Line::from(""),
+            Line::from("Press 'y' to invoke, any other key to cancel."),
+        ])
+        .block(block)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .alignment(ratatui::layout::Alignment::Center);
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    // Render Instantiation Confirmation Modal
+    if let Some(obj) = &app.pending_instantiation {
+        let area = centered_rect_fixed_height(60, 9, f.area());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Confirm Instantiation")
+            .style(Style::default().bg(Color::Red).fg(Color::White).add_modifier(Modifier::BOLD));
+        let paragraph = Paragraph::new(vec![
+            Line::from(format!("{} {}", obj.name, obj.clsid)),
+            Line::from("has no registered type library. Inspecting it will call CoCreateInstance,"),
+            Line::from("which may start its server (e.g. an Office application)."),
+            Line::from(""),
+            Line::from("'y' instantiate once, 'a' don't ask again this session,"),
+            Line::from("any other key to cancel."),
+        ])
+        .block(block)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .alignment(ratatui::layout::Alignment::Center);
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
 
     // Render Notification Modal Overlay
     if let Some(notification) = app.notifications.front() {
@@ -629,6 +1981,238 @@ fn ui_render(f: &mut Frame, app: &mut App, view_items: &[TreeItem]) {
     }
 }
 
+/// Draws the full-screen progress view shown until the background scan finishes.
+fn render_scanning(f: &mut Frame, app: &App) {
+    let quit_hint = format!("Press {} to quit.", app.keymap.quit);
+    let area = centered_rect_fixed_height(60, 8, f.area());
+
+    if let Some(err_msg) = &app.error_message {
+        let lines = vec![
+            Line::from(Span::styled("Scan Failed:", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(err_msg.as_str(), Style::default().fg(Color::Red))),
+            Line::from(""),
+            Line::from(quit_hint),
+        ];
+        let p = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("RustCOM Explorer"))
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        f.render_widget(p, area);
+        return;
+    }
+
+    let elapsed = app.scan_started.map(|start| start.elapsed()).unwrap_or_default();
+    let frame = SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()];
+    let (processed, total) = app.scan_progress;
+
+    let block = Block::default().borders(Borders::ALL).title("RustCOM Explorer");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            format!("{} Scanning registry for COM objects...", frame),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        rows[0],
+    );
+    let ratio = if total == 0 { 0.0 } else { (processed as f64 / total as f64).min(1.0) };
+    f.render_widget(
+        Gauge::default()
+            .gauge_style(Style::default().fg(Color::Cyan).bg(Color::Black))
+            .ratio(ratio)
+            .label(format!("{} / {} keys", processed, total)),
+        rows[2],
+    );
+    f.render_widget(
+        Paragraph::new(Span::styled(format!("Elapsed: {:.1}s", elapsed.as_secs_f32()), Style::default().fg(Color::DarkGray))),
+        rows[3],
+    );
+    f.render_widget(Paragraph::new(Span::styled(quit_hint, Style::default().fg(Color::Gray))), rows[4]);
+}
+
+/// Keybindings shown in the help overlay, grouped by mode.
+fn help_lines(keymap: &KeyMap) -> Vec<Line<'static>> {
+    let groups: [(&str, Vec<(String, &str)>); 4] = [
+        ("Browsing", vec![
+            ("↑/↓, j/k".to_string(), "Move selection"),
+            ("g / G".to_string(), "Jump to top / bottom"),
+            ("Ctrl+D / Ctrl+U".to_string(), "Half-page down / up"),
+            (keymap.inspect.to_string(), "Expand category / inspect object"),
+            (format!("{} / {}", keymap.expand_all, keymap.collapse_all), "Expand / collapse all categories"),
+            ("o".to_string(), "Cycle grouping"),
+            ("b".to_string(), "Toggle bookmark on the selected object"),
+            ("F".to_string(), "Show bookmarks only / all objects"),
+            ("Tab".to_string(), "Focus list / details (↑/↓, PgUp/PgDn scroll details)"),
+            ("r".to_string(), "Show the raw registry key / details of the selected object"),
+            ("Ctrl+S".to_string(), "Export the filtered objects to a file"),
+            (keymap.go_to.to_string(), "Go to the next category starting with the typed text"),
+            (keymap.search.to_string(), "Search mode"),
+            (keymap.regex_search.to_string(), "Filter by regular expression"),
+            ("Esc".to_string(), "Clear search"),
+        ]),
+        ("Search", vec![
+            ("Type".to_string(), "Fuzzy filter"),
+            ("Enter / Esc".to_string(), "Back to navigation"),
+        ]),
+        ("Inspecting", vec![
+            ("↑/↓, j/k, g/G".to_string(), "Move selection"),
+            (format!("{} / {}", keymap.copy_item, keymap.copy_all), "Copy member / all members"),
+            ("J / M".to_string(), "Copy as JSON / Markdown table"),
+            ("h".to_string(), "Show or hide hidden members"),
+            ("Backspace / Alt+←".to_string(), "Back to the previously inspected object"),
+            (keymap.search.to_string(), "Filter members by name"),
+            ("i".to_string(), "Invoke member (--unsafe)"),
+            ("p".to_string(), "Copy as PowerShell snippet"),
+            ("o / P".to_string(), "Show server in Explorer / copy its path"),
+            ("F1".to_string(), "Open the type's help file"),
+            ("Tab".to_string(), "Focus details / members"),
+            ("PgUp / PgDn".to_string(), "Scroll the focused pane"),
+            ("Esc".to_string(), "Cancel loading / back"),
+        ]),
+        ("Global", vec![
+            ("?".to_string(), "Toggle this help"),
+            (keymap.quit.to_string(), "Quit"),
+        ]),
+    ];
+
+    let mut lines = Vec::new();
+    for (idx, (mode, bindings)) in groups.into_iter().enumerate() {
+        if idx > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(mode, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
+        for (keys, action) in bindings {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<16}", keys), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(action),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Splits `text` into spans, applying `highlight` to the characters at `indices`.
+fn highlight_spans(text: &str, indices: &[usize], highlight: Style) -> Vec<Span<'static>> {
+    if indices.is_empty() {
+        return vec![Span::raw(text.to_string())];
+    }
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (idx, c) in text.chars().enumerate() {
+        let matched = indices.binary_search(&idx).is_ok();
+        if matched != run_matched && !run.is_empty() {
+            let chunk = std::mem::take(&mut run);
+            spans.push(if run_matched { Span::styled(chunk, highlight) } else { Span::raw(chunk) });
+        }
+        run_matched = matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(if run_matched { Span::styled(run, highlight) } else { Span::raw(run) });
+    }
+    spans
+}
+
+/// Renders a raw registry subtree as indented key lines, each followed by its values.
+fn registry_tree_lines(node: &RegistryNode, depth: usize, lines: &mut Vec<Line<'static>>) {
+    let indent = "  ".repeat(depth);
+    lines.push(Line::from(Span::styled(format!("{}{}", indent, node.name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
+    for (name, data) in &node.values {
+        let name = if name.is_empty() { "(Default)" } else { name };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}  {} = ", indent, name), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(data.clone()),
+        ]));
+    }
+    if let Some(error) = &node.error {
+        lines.push(Line::from(Span::styled(format!("{}  {}", indent, error), Style::default().fg(Color::Red))));
+    }
+    for child in &node.children {
+        registry_tree_lines(child, depth + 1, lines);
+    }
+}
+
+/// Formats the server registration as "[Kind] path" for the details pane.
+fn server_label(obj: &ComObject) -> String {
+    let kind = match obj.server_kind {
+        ServerKind::InProc => "InProc",
+        ServerKind::Local => "Local",
+        ServerKind::None => return "(not registered)".to_string(),
+    };
+    let missing = if obj.orphaned { " (file missing)" } else { "" };
+    format!("[{}] {}{}", kind, obj.server_path.as_deref().unwrap_or("(no path)"), missing)
+}
+
+/// Summarizes DCOM AppID settings on a single line for the inspection view.
+fn app_id_label(info: &AppIdInfo) -> String {
+    let mut parts = vec![format!("AppID {}", info.app_id)];
+    if let Some(service) = &info.local_service {
+        parts.push(format!("Service: {}", service));
+    }
+    if let Some(run_as) = &info.run_as {
+        parts.push(format!("RunAs: {}", run_as));
+    }
+    if let Some(surrogate) = &info.dll_surrogate {
+        let surrogate = if surrogate.is_empty() { "dllhost.exe" } else { surrogate.as_str() };
+        parts.push(format!("Surrogate: {}", surrogate));
+    }
+    parts.join(" | ")
+}
+
+/// Greedily word-wraps `text` to lines of at most `width` characters.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let needed = if current.is_empty() { word.chars().count() } else { current.chars().count() + 1 + word.chars().count() };
+        if needed > width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Rows `lines` take up when word-wrapped to `width` columns. Words longer than a row
+/// are broken across rows, as `Paragraph` does.
+fn wrapped_height(lines: &[Line], width: u16) -> u16 {
+    let width = (width as usize).max(1);
+    let rows: usize = lines
+        .iter()
+        .map(|line| {
+            let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+            wrap_text(&text, width)
+                .iter()
+                .map(|row| row.chars().count().div_ceil(width))
+                .sum::<usize>()
+                .max(1)
+        })
+        .sum();
+    rows.min(u16::MAX as usize) as u16
+}
+
 /// Helper function to create a centered rect of fixed height and percentage width
 fn centered_rect_fixed_height(percent_x: u16, height: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -648,4 +2232,179 @@ fn centered_rect_fixed_height(percent_x: u16, height: u16, r: Rect) -> Rect {
             Constraint::Percentage((100 - percent_x) / 2),
         ])
         .split(popup_layout[1])[1]
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockData;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    /// An app that has finished scanning `fixtures/demo.json`.
+    fn demo_app() -> App {
+        let data = MockData::from_json(include_str!("../fixtures/demo.json")).unwrap();
+        let (tx, rx) = mpsc::channel();
+        tx.send(ScanUpdate::Finished(Ok(data.objects))).unwrap();
+        let mut app = App::new(rx, InspectOptions::default());
+        app.check_scan_results();
+        app
+    }
+
+    /// Renders one frame and returns the screen as text, one line per row.
+    fn render(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        let view_items = app.get_view_items();
+        terminal.draw(|f| ui_render(f, app, &view_items)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        let view_items = app.get_view_items();
+        app.handle_key(event::KeyEvent::from(code), &view_items);
+    }
+
+    #[test]
+    fn test_renders_scan_progress_until_results_arrive() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(ScanUpdate::Progress { processed: 250, total: 1000 }).unwrap();
+        let mut app = App::new(rx, InspectOptions::default());
+        app.check_scan_results();
+
+        assert_eq!(app.app_mode, AppMode::Scanning);
+        assert!(render(&mut app).contains("250"));
+    }
+
+    #[test]
+    fn test_renders_demo_fixture_categories() {
+        let mut app = demo_app();
+        assert_eq!(app.app_mode, AppMode::Browsing);
+
+        let screen = render(&mut app);
+        assert!(screen.contains("Excel"), "{}", screen);
+        assert!(screen.contains("Scripting"), "{}", screen);
+    }
+
+    #[test]
+    fn test_search_narrows_the_rendered_list() {
+        let mut app = demo_app();
+        press(&mut app, KeyCode::Char('/'));
+        for c in "filesys".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+
+        assert_eq!(app.search_query, "filesys");
+        let screen = render(&mut app);
+        assert!(screen.contains("Scripting.FileSystemObject"), "{}", screen);
+        assert!(!screen.contains("Excel.Chart"), "{}", screen);
+    }
+
+    #[test]
+    fn test_go_to_prompt_selects_category_by_prefix() {
+        let mut app = demo_app();
+        press(&mut app, KeyCode::Char(':'));
+        for c in "scr".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.input_modal.is_none());
+        let view_items = app.get_view_items();
+        let selected = app.list_state.selected().and_then(|idx| view_items.get(idx));
+        assert!(matches!(selected, Some(TreeItem::Category { name, .. }) if name == "Scripting"), "{:?}", selected);
+        assert!(render(&mut app).contains("Go to: 'scr'"));
+    }
+
+    #[test]
+    fn test_regex_prompt_sets_a_regex_query() {
+        let mut app = demo_app();
+        press(&mut app, KeyCode::Char('R'));
+        for c in "^WScript".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.search_query, "re:^WScript");
+        let screen = render(&mut app);
+        assert!(screen.contains("WScript.Shell"), "{}", screen);
+        assert!(!screen.contains("Scripting.FileSystemObject"), "{}", screen);
+
+        // An invalid pattern keeps the query and reopens the prompt holding it
+        press(&mut app, KeyCode::Char('R'));
+        press(&mut app, KeyCode::Char('('));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.search_query, "re:^WScript");
+        assert_eq!(app.input_modal.as_ref().map(|modal| modal.buffer.as_str()), Some("^WScript("));
+    }
+
+    #[test]
+    fn test_export_prompt_lists_the_formats_export_accepts() {
+        let mut app = demo_app();
+        app.handle_key(event::KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL), &app.get_view_items());
+        let prompt = app.input_modal.as_ref().map(|modal| modal.prompt.clone()).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("rustcom_explorer_export_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for format in OutputFormat::ALL {
+            let extension = format!(".{}", format.extension());
+            let exported = app.export_filtered(&dir.join(format!("objects{}", extension)));
+            assert_eq!(exported.is_ok(), prompt.contains(&extension), "{}: {}", extension, prompt);
+            if let Err(e) = exported {
+                assert!(e.to_string().contains(&export_extensions()), "{}", e);
+            }
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn modal(initial: &str) -> InputModal {
+        InputModal::new("Title", "Prompt", initial, InputAction::ExportFiltered)
+    }
+
+    fn edit(modal: &mut InputModal, code: KeyCode) -> InputOutcome {
+        modal.handle_key(event::KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_input_modal_cursor_counts_characters() {
+        let mut input = modal("café");
+        assert_eq!(input.cursor, 4);
+
+        edit(&mut input, KeyCode::Left);
+        edit(&mut input, KeyCode::Char('ß'));
+        assert_eq!(input.buffer, "cafßé");
+        assert_eq!(input.cursor, 4);
+
+        edit(&mut input, KeyCode::Home);
+        edit(&mut input, KeyCode::Char('日'));
+        assert_eq!(input.buffer, "日cafßé");
+        edit(&mut input, KeyCode::End);
+        edit(&mut input, KeyCode::Right);
+        assert_eq!(input.cursor, 6);
+    }
+
+    #[test]
+    fn test_input_modal_backspace_and_delete_remove_whole_characters() {
+        let mut input = modal("añ日é");
+        edit(&mut input, KeyCode::Backspace);
+        assert_eq!(input.buffer, "añ日");
+
+        edit(&mut input, KeyCode::Home);
+        edit(&mut input, KeyCode::Right);
+        edit(&mut input, KeyCode::Delete);
+        assert_eq!(input.buffer, "a日");
+        assert_eq!(input.cursor, 1);
+
+        edit(&mut input, KeyCode::Backspace);
+        edit(&mut input, KeyCode::Backspace);
+        assert_eq!(input.buffer, "日");
+        assert_eq!(input.cursor, 0);
+
+        edit(&mut input, KeyCode::End);
+        edit(&mut input, KeyCode::Delete);
+        assert_eq!(input.buffer, "日");
+        assert_eq!(edit(&mut input, KeyCode::Enter), InputOutcome::Submitted);
+        assert_eq!(edit(&mut input, KeyCode::Esc), InputOutcome::Cancelled);
+    }
+}
@@ -1,5 +1,14 @@
 // src/cli.rs
-use clap::{Parser, Subcommand};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
+use clap::{Parser, Subcommand, ValueEnum};
+use crate::com_interop::{self, Apartment, InspectOptions};
+use crate::export::OutputFormat;
+use crate::processor::{FilterOptions, GroupBy, Query, SortKey, SortOrder};
+use crate::pipeline::InventoryOptions;
+use crate::scanner::{Bitness, ScanOptions};
+use crate::style::{self, Styler};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -12,28 +21,459 @@ pub struct Args {
     #[arg(long = "unsafe", global = true, default_value_t = false)]
     pub unsafe_mode: bool,
 
-    /// Enable verbose output logging.
-    #[arg(short, long, global = true, default_value_t = false)]
-    pub verbose: bool,
+    /// Enable verbose logging: `-v` for info, `-vv` for debug.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Also write log events and span timings to this file, without colors.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Suppress progress and status messages on stderr; errors are still reported.
+    #[arg(short, long, global = true, default_value_t = false, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Registry view(s) to scan for COM objects.
+    #[arg(long, value_enum, global = true, default_value_t = BitnessArg::Both)]
+    pub bitness: BitnessArg,
+
+    /// Always scan the registry and never read or write the cache.
+    #[arg(long, global = true, default_value_t = false)]
+    pub no_cache: bool,
+
+    /// Force a rescan and rewrite the cache.
+    #[arg(long, global = true, default_value_t = false)]
+    pub refresh: bool,
+
+    /// Maximum age of cached scan results, in seconds.
+    #[arg(long, global = true, default_value_t = 86400)]
+    pub cache_ttl: u64,
+
+    /// Abort the registry scan after this many seconds, keeping the objects found so far.
+    #[arg(long, global = true)]
+    pub scan_timeout_secs: Option<u64>,
+
+    /// COM apartment used for inspection. `sta` suits STA-only components
+    /// and runs deep inspection on a single thread unless `--jobs` says otherwise.
+    #[arg(long, value_enum, global = true, default_value_t = ApartmentArg::Mta)]
+    pub apartment: ApartmentArg,
+
+    /// Number of worker threads for deep inspection; defaults to one per CPU core (one with
+    /// `--apartment sta`). `--jobs 1` inspects serially, in a deterministic order.
+    #[arg(short, long, global = true, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Give up on unsafe instantiation of a single object after this many milliseconds.
+    #[arg(long, global = true, default_value_t = 5000)]
+    pub instantiate_timeout_ms: u64,
+
+    /// Retry a server launch this many times when it fails with CO_E_SERVER_EXEC_FAILURE or
+    /// RPC_E_CALL_REJECTED, backing off between attempts. Retries share the instantiation timeout.
+    #[arg(long, global = true, default_value_t = com_interop::DEFAULT_INSTANTIATE_RETRIES, value_name = "N")]
+    pub instantiate_retries: u32,
+
+    /// How objects are grouped into categories.
+    #[arg(long, value_enum, global = true, default_value_t = GroupByArg::ProgIdPrefix)]
+    pub group_by: GroupByArg,
+
+    /// Disable colored output (also honored via the NO_COLOR environment variable).
+    #[arg(long, global = true, default_value_t = false)]
+    pub no_color: bool,
+
+    /// Load COM objects and type details from a JSON fixture instead of the registry.
+    /// Works on any platform; the scan cache is bypassed.
+    #[arg(long, global = true, env = "SCAN_MOCK", value_name = "PATH")]
+    pub mock: Option<PathBuf>,
+
+    /// Check that each registered server binary exists and flag the objects whose file is missing.
+    #[arg(long, global = true, default_value_t = false)]
+    pub check_orphans: bool,
+
+    /// Do not restore or save expanded categories, search and selection in the TUI.
+    #[arg(long, global = true, default_value_t = false)]
+    pub no_persist: bool,
+}
+
+impl Args {
+    /// Whether the on-disk scan cache should be used.
+    pub fn use_cache(&self) -> bool {
+        !self.no_cache && self.mock.is_none()
+    }
+
+    /// Styling for log lines written to stderr.
+    pub fn log_style(&self) -> Styler {
+        Styler::for_stderr(style::color_allowed(self.no_color))
+    }
+
+    /// The most detailed log level requested with `-v`/`-vv`; warnings otherwise.
+    pub fn log_level(&self) -> tracing::Level {
+        match self.verbose {
+            0 => tracing::Level::WARN,
+            1 => tracing::Level::INFO,
+            _ => tracing::Level::DEBUG,
+        }
+    }
+
+    /// Styling for reports written to stdout.
+    pub fn output_style(&self) -> Styler {
+        Styler::for_stdout(style::color_allowed(self.no_color))
+    }
+
+    /// The COM apartment selected with `--apartment`.
+    pub fn apartment(&self) -> Apartment {
+        self.apartment.into()
+    }
+
+    /// The grouping selected with `--group-by`.
+    pub fn group_by(&self) -> GroupBy {
+        self.group_by.into()
+    }
+
+    /// Inspection settings derived from the global flags.
+    pub fn inspect_options(&self) -> InspectOptions {
+        InspectOptions {
+            allow_unsafe: self.unsafe_mode,
+            instantiate_timeout: Duration::from_millis(self.instantiate_timeout_ms),
+            apartment: self.apartment(),
+            instantiate_retries: self.instantiate_retries,
+        }
+    }
+
+    /// Registry scan settings derived from the global flags.
+    pub fn scan_options(&self) -> ScanOptions {
+        ScanOptions::builder().bitness(self.bitness.into()).check_orphans(self.check_orphans).build()
+    }
+
+    /// Inventory settings derived from the global flags; subcommands decide on deep inspection themselves.
+    pub fn inventory_options(&self) -> InventoryOptions {
+        InventoryOptions::builder()
+            .scan(self.scan_options())
+            .group_by(self.group_by())
+            .use_cache(self.use_cache())
+            .refresh(self.refresh)
+            .cache_ttl(Duration::from_secs(self.cache_ttl))
+            .inspect(self.inspect_options())
+            .jobs(self.jobs)
+            .timeout(self.scan_timeout_secs.map(Duration::from_secs))
+            .build()
+    }
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// List available COM objects
     List(ListArgs),
+    /// Deeply inspect a single COM object by CLSID or ProgID
+    Inspect(InspectArgs),
+    /// Print the grouped category/object hierarchy as a tree
+    Tree(TreeArgs),
+    /// Print summary statistics about the registered COM objects
+    Stats(StatsArgs),
+    /// Compare two `list --json` exports and report added, removed and changed objects
+    Diff(DiffArgs),
+    /// Wait for COM registrations to change and print added/removed CLSIDs until Ctrl-C
+    Watch,
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions(CompletionsArgs),
 }
 
 #[derive(Parser, Debug)]
 pub struct ListArgs {
     /// Filter objects by name or CLSID
-    #[arg(short, long)]
+    #[arg(short, long, required_if_eq("sort", "score"))]
     pub filter: Option<String>,
 
+    /// Keep only this category (exact name, case-insensitive), e.g. "Word" with the default grouping
+    #[arg(long, value_name = "NAME")]
+    pub category: Option<String>,
+
+    /// Keep objects whose name, CLSID or description matches this regular expression
+    #[arg(long, value_name = "PATTERN", conflicts_with = "filter", value_parser = regex::Regex::new)]
+    pub regex: Option<regex::Regex>,
+
+    /// Keep only the N best matches (or the first N by name when unfiltered)
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Order of objects within each category (`score` requires --filter)
+    #[arg(long, value_enum, default_value_t = SortKeyArg::Name)]
+    pub sort: SortKeyArg,
+
+    /// Reverse the sort order
+    #[arg(long)]
+    pub reverse: bool,
+
     /// Output to file (auto-detects extension)
     #[arg(short, long)]
     pub output: Option<String>,
 
-    /// Export as JSON with deep inspection details
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ListFormat::Text)]
+    pub format: ListFormat,
+
+    /// Export as JSON with deep inspection details (shorthand for `--format json`)
+    #[arg(long)]
+    pub json: bool,
+
+    /// Deep-inspect every object (always on for JSON; adds a member count column to CSV,
+    /// member elements to XML and member lists to HTML)
+    #[arg(long)]
+    pub deep: bool,
+
+    /// List only objects whose server binary is missing (implies --check-orphans)
+    #[arg(long)]
+    pub only_orphans: bool,
+
+    /// List only objects whose TypeLib is registered, i.e. those that can be inspected
+    /// without instantiating them. Only registry keys are probed.
+    #[arg(long)]
+    pub only_with_typelib: bool,
+
+    /// Treat --output as a directory and write one file per category into it, plus an
+    /// `index.json` manifest listing the parts
+    #[arg(long, requires = "output")]
+    pub split_by_category: bool,
+
+    /// Print the JSON Schema of the `--format json` output instead of scanning
+    #[arg(long)]
+    pub schema: bool,
+}
+
+impl ListArgs {
+    /// Resolves the effective output format, honoring the `--json` shorthand.
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json { OutputFormat::Json } else { self.format.into() }
+    }
+
+    /// The ordering selected with `--sort` and `--reverse`.
+    pub fn sort_order(&self) -> SortOrder {
+        SortOrder { key: self.sort.into(), reverse: self.reverse }
+    }
+
+    /// The filter selected with `--filter` or `--regex`, `--category`, `--limit` and the sort flags.
+    pub fn filter_options(&self, group_by: GroupBy) -> FilterOptions {
+        let query = match &self.regex {
+            Some(pattern) => Query::Regex(pattern.clone()),
+            None => Query::Fuzzy(self.filter.clone().unwrap_or_default()),
+        };
+        FilterOptions { query, group_by, category: self.category.clone(), limit: self.limit, order: self.sort_order() }
+    }
+}
+
+/// `list --format` values, mirroring [`OutputFormat`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListFormat {
+    /// Plain text grouped by category
+    Text,
+    /// JSON with deep inspection details
+    Json,
+    /// RFC 4180 CSV, one row per object
+    Csv,
+    /// GitHub-flavored Markdown tables grouped by category
+    Markdown,
+    /// One deep-inspected JSON object per line, streamed as inspection finishes
+    Ndjson,
+    /// XML document of categories and objects, with members when `--deep` is set
+    Xml,
+    /// GraphViz digraph of every inspected coclass and its interfaces
+    Dot,
+    /// Self-contained HTML page with search, with member lists when `--deep` is set
+    Html,
+}
+
+impl From<ListFormat> for OutputFormat {
+    fn from(format: ListFormat) -> Self {
+        match format {
+            ListFormat::Text => OutputFormat::Text,
+            ListFormat::Json => OutputFormat::Json,
+            ListFormat::Csv => OutputFormat::Csv,
+            ListFormat::Markdown => OutputFormat::Markdown,
+            ListFormat::Ndjson => OutputFormat::Ndjson,
+            ListFormat::Xml => OutputFormat::Xml,
+            ListFormat::Dot => OutputFormat::Dot,
+            ListFormat::Html => OutputFormat::Html,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct InspectArgs {
+    /// CLSID (e.g. "{00024500-0000-0000-C000-000000000046}") or ProgID (e.g. "Excel.Application").
+    /// When omitted, one CLSID/ProgID per line is read from `--from-file` or stdin.
+    pub clsid_or_progid: Option<String>,
+
+    /// Read CLSIDs/ProgIDs (one per line) from a file and emit a JSON array of results
+    #[arg(long, value_name = "PATH", conflicts_with = "clsid_or_progid")]
+    pub from_file: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = InspectFormat::Text)]
+    pub format: InspectFormat,
+
+    /// Print the inspection result as JSON (shorthand for `--format json`)
     #[arg(long)]
     pub json: bool,
-}
\ No newline at end of file
+
+    /// Include members the type library flags hidden or restricted
+    #[arg(long)]
+    pub show_hidden: bool,
+
+    /// Inspect every type in the object's type library, grouped by declaring interface
+    #[arg(long)]
+    pub deep: bool,
+}
+
+impl InspectArgs {
+    /// Resolves the effective output format, honoring the `--json` shorthand.
+    pub fn output_format(&self) -> InspectFormat {
+        if self.json { InspectFormat::Json } else { self.format }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InspectFormat {
+    /// Plain-text member listing
+    Text,
+    /// The raw type details as JSON
+    Json,
+    /// MIDL-like interface/coclass sketch
+    Idl,
+    /// windows-rs style trait skeleton
+    Rust,
+    /// Python `.pyi`-style stub for win32com / comtypes scripts
+    Python,
+    /// C# `[ComImport]` interface declarations plus a coclass stub
+    Csharp,
+    /// TypeScript `.d.ts` declarations in a namespace per coclass
+    Typescript,
+    /// PowerShell snippet creating the object, with commented member examples
+    Powershell,
+    /// GraphViz digraph of coclasses and the interfaces they implement
+    Dot,
+}
+
+/// `--bitness` values, mirroring [`Bitness`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitnessArg {
+    /// The process's native view (64-bit on 64-bit Windows)
+    Native,
+    /// The 32-bit view (`Wow6432Node`)
+    X86,
+    /// Both views
+    Both,
+}
+
+impl From<BitnessArg> for Bitness {
+    fn from(bitness: BitnessArg) -> Self {
+        match bitness {
+            BitnessArg::Native => Bitness::Native,
+            BitnessArg::X86 => Bitness::X86,
+            BitnessArg::Both => Bitness::Both,
+        }
+    }
+}
+
+/// `--apartment` values, mirroring [`Apartment`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApartmentArg {
+    /// Multi-threaded apartment (`COINIT_MULTITHREADED`)
+    Mta,
+    /// Single-threaded apartment (`COINIT_APARTMENTTHREADED`), required by many UI and Office components
+    Sta,
+}
+
+impl From<ApartmentArg> for Apartment {
+    fn from(apartment: ApartmentArg) -> Self {
+        match apartment {
+            ApartmentArg::Mta => Apartment::Mta,
+            ApartmentArg::Sta => Apartment::Sta,
+        }
+    }
+}
+
+/// `--group-by` values, mirroring [`GroupBy`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupByArg {
+    /// The ProgID prefix before the first dot
+    ProgIdPrefix,
+    /// The DLL or EXE that serves the class
+    ServerPath,
+    /// Each Implemented Category of the object (objects may appear in several groups)
+    ComponentCategory,
+    /// The registry view the object was found in
+    Bitness,
+}
+
+impl From<GroupByArg> for GroupBy {
+    fn from(group_by: GroupByArg) -> Self {
+        match group_by {
+            GroupByArg::ProgIdPrefix => GroupBy::ProgIdPrefix,
+            GroupByArg::ServerPath => GroupBy::ServerPath,
+            GroupByArg::ComponentCategory => GroupBy::ComponentCategory,
+            GroupByArg::Bitness => GroupBy::Bitness,
+        }
+    }
+}
+
+/// `list --sort` values, mirroring [`SortKey`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKeyArg {
+    /// Object name
+    Name,
+    /// CLSID
+    Clsid,
+    /// Fuzzy match score, best first (requires a query)
+    Score,
+    /// First implemented component category, then name
+    Category,
+}
+
+impl From<SortKeyArg> for SortKey {
+    fn from(key: SortKeyArg) -> Self {
+        match key {
+            SortKeyArg::Name => SortKey::Name,
+            SortKeyArg::Clsid => SortKey::Clsid,
+            SortKeyArg::Score => SortKey::Score,
+            SortKeyArg::Category => SortKey::Category,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct TreeArgs {
+    /// Filter objects by name or CLSID
+    #[arg(short, long)]
+    pub filter: Option<String>,
+
+    /// Tree depth: 1 prints categories only, 2 includes objects
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub depth: u8,
+}
+
+#[derive(Parser, Debug)]
+pub struct StatsArgs {
+    /// Print the statistics as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffArgs {
+    /// The earlier export
+    pub old: PathBuf,
+
+    /// The later export
+    pub new: PathBuf,
+
+    /// Print the changeset as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: clap_complete::Shell,
+}
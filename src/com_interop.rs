@@ -1,118 +1,1601 @@
 // src/com_interop.rs
 use crate::error_handling::{Result, InspectError};
+#[cfg(windows)]
+use crate::error_handling::hresult_to_string;
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
+#[cfg(windows)]
+use std::cell::RefCell;
+#[cfg(windows)]
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+#[cfg(windows)]
 use windows::{
     core::{GUID, BSTR, PCWSTR},
+    Win32::Foundation::{
+        CLASS_E_NOTLICENSED, CO_E_CLASSSTRING, CO_E_SERVER_EXEC_FAILURE, E_ACCESSDENIED, RPC_E_CALL_REJECTED, RPC_E_CHANGED_MODE,
+    },
     Win32::System::Com::{
-        CoCreateInstance, CoInitializeEx, CoUninitialize, IIDFromString,
-        CLSCTX_ALL, COINIT_MULTITHREADED,
-        IDispatch, ITypeInfo, ITypeLib, TYPEATTR, FUNCDESC, VARDESC,
+        CoCreateInstance, CoInitializeEx, CoUninitialize,
+        CLSCTX_ALL, COINIT, COINIT_APARTMENTTHREADED, COINIT_MULTITHREADED,
+        IDispatch, ITypeInfo, ITypeLib, TYPEATTR, FUNCDESC, VARDESC, VAR_CONST, INVOKEKIND,
+        IMPLTYPEFLAG_FDEFAULT, IMPLTYPEFLAG_FSOURCE,
+        FUNCFLAG_FHIDDEN, FUNCFLAG_FRESTRICTED, VARFLAG_FHIDDEN, VARFLAG_FRESTRICTED,
+        TYPEKIND, TKIND_ALIAS, TKIND_COCLASS, TKIND_DISPATCH, TKIND_ENUM, TKIND_INTERFACE,
+        TKIND_MODULE, TKIND_RECORD, TKIND_UNION, TYPEDESC,
         INVOKE_FUNC, INVOKE_PROPERTYGET, INVOKE_PROPERTYPUT, INVOKE_PROPERTYPUTREF,
+        DISPATCH_METHOD, DISPATCH_PROPERTYGET, DISPPARAMS, EXCEPINFO,
     },
     Win32::System::Ole::{
-        LoadRegTypeLib,
+        LoadRegTypeLib, TYPEFLAG_FDUAL,
     },
     Win32::System::Variant::{
-        VARENUM, VT_BSTR, VT_I4, VT_UI4, VT_DISPATCH, VT_BOOL, VT_VARIANT, VT_UNKNOWN, VT_VOID,
+        VariantChangeType, VariantClear, VARIANT_ALPHABOOL, VT_EMPTY, VT_NULL,
+        VARIANT, VARENUM, VT_BSTR, VT_I4, VT_UI4, VT_DISPATCH, VT_BOOL, VT_VARIANT, VT_UNKNOWN, VT_VOID,
         VT_I2, VT_R4, VT_R8, VT_CY, VT_DATE, VT_ERROR, VT_I1, VT_UI1, VT_UI2, VT_INT, VT_UINT,
         VT_HRESULT, VT_PTR, VT_SAFEARRAY, VT_USERDEFINED, VT_LPSTR, VT_LPWSTR,
+        VT_CARRAY, VT_DECIMAL, VT_I8, VT_UI8, VT_FILETIME, VT_RECORD, VT_CLSID, VT_STREAM, VT_STORAGE, VT_BLOB,
     },
 };
-use winreg::{RegKey, enums::HKEY_CLASSES_ROOT};
+#[cfg(windows)]
+use winreg::{RegKey, enums::{HKEY_CLASSES_ROOT, KEY_READ, KEY_WOW64_32KEY}};
+
+// `PARAMFLAG_*` bits stored in `Param::flags`, defined here so parameters format without the Win32 bindings
+pub const PARAMFLAG_FIN: u16 = 0x1;
+pub const PARAMFLAG_FOUT: u16 = 0x2;
+pub const PARAMFLAG_FRETVAL: u16 = 0x8;
+pub const PARAMFLAG_FOPT: u16 = 0x10;
+pub const PARAMFLAG_FHASDEFAULT: u16 = 0x20;
 
 /// RAII Guard for COM initialization
-pub struct ComGuard;
+pub struct ComGuard {
+    /// False when COM was skipped because mock data stands in for it
+    initialized: bool,
+}
 
 impl Drop for ComGuard {
     fn drop(&mut self) {
+        if !self.initialized {
+            return;
+        }
+        tracing::debug!("Uninitializing COM");
+        clear_type_lib_cache();
+        #[cfg(windows)]
         unsafe { CoUninitialize() };
     }
 }
 
-/// Initializes the COM library.
-pub fn initialize_com() -> Result<ComGuard> {
-    unsafe {
-        let _ = CoInitializeEx(None, COINIT_MULTITHREADED).ok();
+/// COM threading model a thread joins when initializing COM.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Apartment {
+    /// Multi-threaded apartment (`COINIT_MULTITHREADED`)
+    #[default]
+    Mta,
+    /// Single-threaded apartment (`COINIT_APARTMENTTHREADED`), required by many UI and Office components
+    Sta,
+}
+
+#[cfg(windows)]
+impl Apartment {
+    /// The `CoInitializeEx` flag for this apartment.
+    pub fn coinit(self) -> COINIT {
+        match self {
+            Apartment::Mta => COINIT_MULTITHREADED,
+            Apartment::Sta => COINIT_APARTMENTTHREADED,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Apartment::Mta => "multi-threaded apartment (MTA)",
+            Apartment::Sta => "single-threaded apartment (STA)",
+        }
     }
-    Ok(ComGuard)
+}
+
+/// Initializes the COM library in the given apartment.
+///
+/// This is a no-op while mock data is installed, and on platforms without COM, so the mock runs anywhere.
+/// Fails when the thread already joined a different apartment.
+#[tracing::instrument(level = "debug")]
+pub fn initialize_com(apartment: Apartment) -> Result<ComGuard> {
+    if crate::mock::active().is_some() {
+        return Ok(ComGuard { initialized: false });
+    }
+    #[cfg(windows)]
+    {
+        let hr = unsafe { CoInitializeEx(None, apartment.coinit()) };
+        tracing::debug!(hresult = format_args!("0x{:08X}", hr.0), "CoInitializeEx returned");
+        check_coinit(hr, apartment).map(|initialized| ComGuard { initialized })
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = apartment;
+        Ok(ComGuard { initialized: false })
+    }
+}
+
+/// Interprets a `CoInitializeEx` result as whether COM was initialized and must be balanced by `CoUninitialize`.
+///
+/// `S_FALSE` (already initialized in the same apartment) counts as success; `RPC_E_CHANGED_MODE`
+/// means the thread is in another apartment, where objects would silently run in the wrong model.
+#[cfg(windows)]
+fn check_coinit(hr: windows::core::HRESULT, apartment: Apartment) -> Result<bool> {
+    if hr == RPC_E_CHANGED_MODE {
+        return Err(InspectError::Generic(format!(
+            "Cannot join the {}: COM is already initialized on this thread in another apartment",
+            apartment.name()
+        ))
+        .into());
+    }
+    hr.ok()
+        .map(|()| true)
+        .map_err(|e| InspectError::Generic(format!("CoInitializeEx failed: {}", hresult_to_string(e.code().0))).into())
 }
 
 /// Details about a parsed COM Type.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct TypeDetails {
     pub name: String,
     pub description: String,
+    /// The type's own GUID (IID or CLSID), braced
+    #[serde(default)]
+    pub guid: String,
+    /// `(major, minor)` version from the type attributes
+    #[serde(default)]
+    pub version: (u16, u16),
+    /// Coclass, Interface, Dispatch, Enum, Record, Module, Alias or Union
+    #[serde(default)]
+    pub typekind: String,
+    /// How the interface is called; `None` for non-interface types
+    #[serde(default)]
+    pub interface_kind: Option<InterfaceKind>,
     pub members: Vec<Member>,
+    /// Interfaces listed by a coclass; empty for other kinds
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interfaces: Vec<ImplementedInterface>,
+    /// Help file the type library points to, usually a `.chm` or `.hlp`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_file: Option<String>,
+    /// Topic ID of the type inside `help_file`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_context: Option<u32>,
+}
+
+impl TypeDetails {
+    /// `file (context N)` for types that name a help file or topic.
+    pub fn help_label(&self) -> Option<String> {
+        match (&self.help_file, self.help_context) {
+            (Some(file), Some(context)) => Some(format!("{} (context {})", file, context)),
+            (Some(file), None) => Some(file.clone()),
+            (None, Some(context)) => Some(format!("context {}", context)),
+            (None, None) => None,
+        }
+    }
+
+    /// Renders the type and its members as a plain-text listing.
+    pub fn to_text(&self) -> String {
+        let mut buffer = String::new();
+        buffer.push_str(&format!("Type: {}\n", self.name));
+        buffer.push_str(&format!("Description: {}\n", self.description));
+        if !self.guid.is_empty() {
+            buffer.push_str(&format!("GUID: {}\n", self.guid));
+        }
+        if !self.typekind.is_empty() {
+            let badge = self.interface_kind.map(|kind| format!(" {}", kind.badge())).unwrap_or_default();
+            buffer.push_str(&format!(
+                "Kind: {}{} (version {}.{})\n",
+                self.typekind, badge, self.version.0, self.version.1
+            ));
+        }
+        for interface in &self.interfaces {
+            buffer.push_str(&format!("Implements: {}\n", interface.label()));
+        }
+        if let Some(help) = self.help_label() {
+            buffer.push_str(&format!("Help: {}\n", help));
+        }
+        buffer.push('\n');
+
+        for member in &self.members {
+            buffer.push_str(&member.summary());
+            buffer.push('\n');
+            if !member.doc().is_empty() {
+                buffer.push_str(&format!("    {}\n", member.doc()));
+            }
+        }
+
+        buffer
+    }
+}
+
+/// A method parameter as declared in the type library.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Param {
+    pub name: String,
+    /// Type name as produced by [`vartype_to_string`], e.g. `Long&` for a by-reference parameter
+    pub ty: String,
+    /// Raw `PARAMFLAG_*` bits
+    #[serde(default)]
+    pub flags: u16,
+    /// Display form of the value advertised by `PARAMFLAG_FHASDEFAULT`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Flagged optional, or among the trailing `cParamsOpt` parameters
+    #[serde(default)]
+    pub optional: bool,
+    /// The SAFEARRAY that collects the remaining arguments of a vararg method
+    #[serde(default)]
+    pub variadic: bool,
+}
+
+impl Param {
+    pub fn is_in(&self) -> bool {
+        self.flags & PARAMFLAG_FIN != 0
+    }
+
+    pub fn is_out(&self) -> bool {
+        self.flags & PARAMFLAG_FOUT != 0
+    }
+
+    /// Whether this is the `[out, retval]` parameter carrying the result.
+    pub fn is_retval(&self) -> bool {
+        self.flags & PARAMFLAG_FRETVAL != 0
+    }
+
+    /// Formats the parameter as `[attrs] name: Type = default`, marking optional (`name?`) and variadic (`...name`) ones.
+    pub fn display(&self) -> String {
+        let name = if self.variadic {
+            format!("...{}", self.name)
+        } else if self.optional {
+            format!("{}?", self.name)
+        } else {
+            self.name.clone()
+        };
+
+        let default = self.default.as_ref().map(|d| format!(" = {}", d)).unwrap_or_default();
+        let attributes = param_attributes(self.flags);
+        if attributes.is_empty() {
+            format!("{}: {}{}", name, self.ty, default)
+        } else {
+            format!("[{}] {}: {}{}", attributes.join(", "), name, self.ty, default)
+        }
+    }
+}
+
+/// Renders a method signature as `([in] name: Type, ...) -> Ret`.
+pub fn format_signature(params: &[Param], return_type: &str) -> String {
+    let params: Vec<String> = params.iter().map(Param::display).collect();
+    format!("({}) -> {}", params.join(", "), return_type)
+}
+
+/// Maps a [`vartype_to_string`] name back to its MIDL spelling.
+fn idl_type(type_name: &str) -> String {
+    if let Some(inner) = type_name.strip_suffix('&').or_else(|| type_name.strip_suffix('*')) {
+        return format!("{}*", idl_type(inner));
+    }
+    if let Some(inner) = type_name.strip_suffix("[]") {
+        return format!("SAFEARRAY({})", idl_type(inner));
+    }
+
+    match type_name {
+        "Void" => "void",
+        "Short" => "short",
+        "Long" => "long",
+        "Single" => "float",
+        "Double" => "double",
+        "Currency" => "CURRENCY",
+        "Date" => "DATE",
+        "String" => "BSTR",
+        "IDispatch" => "IDispatch*",
+        "Error" => "SCODE",
+        "Boolean" => "VARIANT_BOOL",
+        "Variant" => "VARIANT",
+        "IUnknown" => "IUnknown*",
+        "Byte" => "unsigned char",
+        "UShort" => "unsigned short",
+        "ULong" => "unsigned long",
+        "Int" => "int",
+        "UInt" => "unsigned int",
+        "HResult" => "HRESULT",
+        "Pointer" => "void*",
+        "SafeArray" => "SAFEARRAY(VARIANT)",
+        "String (LPSTR)" => "LPSTR",
+        "String (LPWSTR)" => "LPWSTR",
+        "Decimal" => "DECIMAL",
+        "Int64" => "hyper",
+        "UInt64" => "unsigned hyper",
+        "FileTime" => "FILETIME",
+        "GUID" => "GUID",
+        "IStream" => "IStream*",
+        "IStorage" => "IStorage*",
+        "Blob" => "BLOB",
+        "UserDefined" => "VARIANT /* user-defined */",
+        "Unknown" => "VARIANT /* unknown */",
+        // Resolved user-defined types (interfaces, enums, records) keep their own name
+        _ => type_name,
+    }
+    .to_string()
+}
+
+/// Returns `(interface, coclass)` names, following the `IFoo` / `Foo` convention.
+fn interface_names(type_name: &str) -> (String, String) {
+    let mut chars = type_name.chars();
+    let is_interface = chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase());
+    if is_interface {
+        (type_name.to_string(), type_name[1..].to_string())
+    } else {
+        (format!("I{}", type_name), type_name.to_string())
+    }
+}
+
+/// Renders one method parameter with its MIDL attributes, e.g. `[in, optional, defaultvalue(10)] long limit`.
+fn idl_param(param: &Param) -> String {
+    let mut attributes: Vec<String> = param_attributes(param.flags).into_iter().map(str::to_string).collect();
+    if attributes.is_empty() {
+        attributes.push("in".to_string());
+    }
+    if param.optional && param.flags & PARAMFLAG_FOPT == 0 {
+        attributes.push("optional".to_string());
+    }
+    if let Some(default) = &param.default {
+        attributes.push(format!("defaultvalue({})", default));
+    }
+    let ty = if param.variadic { "SAFEARRAY(VARIANT)".to_string() } else { idl_type(&param.ty) };
+    format!("[{}] {} {}", attributes.join(", "), ty, param.name)
+}
+
+/// Renders the members of a vtable or dual interface, where every method returns `HRESULT`.
+fn idl_interface_members(members: &[Member]) -> String {
+    let mut buffer = String::new();
+    for member in members {
+        match member {
+            Member::Method { name, params, return_type, .. } => {
+                let has_retval = params.iter().any(Param::is_retval);
+                let is_vararg = params.iter().any(|param| param.variadic);
+                let mut params: Vec<String> = params.iter().map(idl_param).collect();
+                if !has_retval && return_type != "Void" && return_type != "HResult" {
+                    params.push(format!("[out, retval] {}* retVal", idl_type(return_type)));
+                }
+                let method_attributes = if is_vararg { "[vararg] " } else { "" };
+                buffer.push_str(&format!("    {}HRESULT {}({});\n", method_attributes, name, params.join(", ")));
+            }
+            Member::Property { name, value_type, access, index, .. } => {
+                let ty = idl_type(value_type);
+                let index: String = index
+                    .iter()
+                    .filter(|param| !param.is_retval())
+                    .map(|param| format!("[in] {} {}, ", idl_type(&param.ty), param.name))
+                    .collect();
+                if matches!(access, AccessMode::Read | AccessMode::ReadWrite) {
+                    buffer.push_str(&format!("    [propget] HRESULT {}({}[out, retval] {}* pVal);\n", name, index, ty));
+                }
+                if matches!(access, AccessMode::Write | AccessMode::ReadWrite) {
+                    buffer.push_str(&format!("    [propput] HRESULT {}({}[in] {} newVal);\n", name, index, ty));
+                }
+            }
+            Member::Constant { .. } => {}
+        }
+    }
+    buffer
+}
+
+/// Renders a pure dispinterface, whose members are only reachable through `IDispatch::Invoke`.
+///
+/// Plain properties are listed under `properties:`; methods, and properties that take an index or
+/// lack a getter, are listed under `methods:` with their real return types rather than `HRESULT`.
+fn idl_dispinterface(interface_name: &str, members: &[Member]) -> String {
+    let attributes = |dispid: &Option<i32>, extra: &[&str]| {
+        let parts: Vec<String> = dispid
+            .map(|dispid| format!("id({})", dispid))
+            .into_iter()
+            .chain(extra.iter().map(|attribute| attribute.to_string()))
+            .collect();
+        if parts.is_empty() { String::new() } else { format!("[{}] ", parts.join(", ")) }
+    };
+
+    let mut properties = String::new();
+    let mut methods = String::new();
+    for member in members {
+        match member {
+            Member::Property { name, value_type, access, index, dispid, .. } if index.is_empty() && *access != AccessMode::Write => {
+                let extra: &[&str] = if *access == AccessMode::Read { &["readonly"] } else { &[] };
+                properties.push_str(&format!("    {}{} {};\n", attributes(dispid, extra), idl_type(value_type), name));
+            }
+            Member::Property { name, value_type, access, index, dispid, .. } => {
+                let ty = idl_type(value_type);
+                let mut params: Vec<String> = index.iter().filter(|param| !param.is_retval()).map(idl_param).collect();
+                if matches!(access, AccessMode::Read | AccessMode::ReadWrite) {
+                    methods.push_str(&format!("    {}{} {}({});\n", attributes(dispid, &["propget"]), ty, name, params.join(", ")));
+                }
+                if matches!(access, AccessMode::Write | AccessMode::ReadWrite) {
+                    params.push(format!("[in] {} newVal", ty));
+                    methods.push_str(&format!("    {}void {}({});\n", attributes(dispid, &["propput"]), name, params.join(", ")));
+                }
+            }
+            Member::Method { name, params, return_type, dispid, .. } => {
+                // The [out, retval] parameter becomes the return value of an Invoke-only method
+                let return_type = params
+                    .iter()
+                    .find(|param| param.is_retval())
+                    .map_or(return_type.as_str(), |param| param.ty.trim_end_matches(['&', '*']));
+                let extra: &[&str] = if params.iter().any(|param| param.variadic) { &["vararg"] } else { &[] };
+                let params: Vec<String> = params.iter().filter(|param| !param.is_retval()).map(idl_param).collect();
+                methods.push_str(&format!("    {}{} {}({});\n", attributes(dispid, extra), idl_type(return_type), name, params.join(", ")));
+            }
+            Member::Constant { .. } => {}
+        }
+    }
+    format!("dispinterface {} {{\nproperties:\n{}methods:\n{}}};\n\n", interface_name, properties, methods)
+}
+
+/// Renders a MIDL-like sketch of the coclass and its dispatch interface.
+///
+/// The output is structurally faithful but not guaranteed to compile: it has no `uuid`,
+/// `library` block or imports, and the interfaces, enums and records its members name are
+/// referenced but not declared.
+pub fn to_idl(details: &TypeDetails) -> String {
+    let (interface_name, coclass_name) = interface_names(&details.name);
+    let mut buffer = String::new();
+
+    buffer.push_str("// Reconstructed by rustcom_explorer; review before compiling.\n\n");
+    let (attributes, base) = match details.interface_kind {
+        Some(InterfaceKind::Vtable) => ("    object,\n", "IUnknown"),
+        // A dispinterface is not a vtable interface, so it takes neither `object` nor a base
+        Some(InterfaceKind::Dispatch) => ("", ""),
+        // Unknown kinds are assumed to be dual, the common case for automation servers
+        Some(InterfaceKind::Dual) | None => ("    object,\n    dual,\n", "IDispatch"),
+    };
+    buffer.push_str("[\n");
+    buffer.push_str(attributes);
+    if !details.description.is_empty() {
+        buffer.push_str(&format!("    helpstring(\"{}\"),\n", details.description.replace('"', "\\\"")));
+    }
+    buffer.push_str("]\n");
+    if details.interface_kind == Some(InterfaceKind::Dispatch) {
+        buffer.push_str(&idl_dispinterface(&interface_name, &details.members));
+    } else {
+        buffer.push_str(&format!("interface {} : {} {{\n", interface_name, base));
+        buffer.push_str(&idl_interface_members(&details.members));
+        buffer.push_str("};\n\n");
+    }
+
+    let constants: Vec<_> = details
+        .members
+        .iter()
+        .filter_map(|member| match member {
+            Member::Constant { name, value, value_type, .. } => Some((name, value, value_type)),
+            _ => None,
+        })
+        .collect();
+    if !constants.is_empty() {
+        buffer.push_str(&format!("module {}Constants {{\n", coclass_name));
+        for (name, value, value_type) in constants {
+            buffer.push_str(&format!("    const {} {} = {};\n", idl_type(value_type), name, value));
+        }
+        buffer.push_str("};\n\n");
+    }
+
+    let keyword = if details.interface_kind == Some(InterfaceKind::Dispatch) { "dispinterface" } else { "interface" };
+    buffer.push_str(&format!("coclass {} {{\n    [default] {} {};\n}};\n", coclass_name, keyword, interface_name));
+    buffer
+}
+
+/// Maps a [`vartype_to_string`] name to a windows-rs type, or `None` if it has no direct equivalent.
+fn rust_type(type_name: &str) -> Option<String> {
+    if let Some(inner) = type_name.strip_suffix('&').or_else(|| type_name.strip_suffix('*')) {
+        return rust_type(inner).map(|ty| format!("*mut {}", ty));
+    }
+    if type_name.ends_with("[]") {
+        return Some("*mut SAFEARRAY".to_string());
+    }
+
+    let ty = match type_name {
+        "Void" => "()",
+        "Short" => "i16",
+        "Long" | "Int" => "i32",
+        "Single" => "f32",
+        "Double" | "Date" => "f64",
+        "Currency" => "CY",
+        "String" => "BSTR",
+        "IDispatch" => "IDispatch",
+        "Error" | "HResult" => "HRESULT",
+        "Boolean" => "VARIANT_BOOL",
+        "Variant" => "VARIANT",
+        "IUnknown" => "IUnknown",
+        "Byte" => "u8",
+        "UShort" => "u16",
+        "ULong" | "UInt" => "u32",
+        "Int64" => "i64",
+        "UInt64" => "u64",
+        "Pointer" => "*mut core::ffi::c_void",
+        "SafeArray" => "*mut SAFEARRAY",
+        "String (LPSTR)" => "PSTR",
+        "String (LPWSTR)" => "PWSTR",
+        _ => return None,
+    };
+    Some(ty.to_string())
+}
+
+/// Like [`rust_type`], falling back to `VARIANT` with a marker comment for unmapped types.
+fn rust_type_or_comment(type_name: &str) -> String {
+    rust_type(type_name).unwrap_or_else(|| format!("VARIANT /* unmapped: {} */", type_name))
+}
+
+/// Escapes COM parameter names that collide with Rust keywords.
+fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+        "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while", "async",
+        "await", "dyn", "gen", "box", "yield", "abstract", "final", "macro", "override", "priv",
+        "typeof", "unsized", "virtual", "do", "become", "try",
+    ];
+    if KEYWORDS.contains(&name) { format!("r#{}", name) } else { name.to_string() }
+}
+
+/// Banner and imports that start every generated Rust file.
+const RUST_PRELUDE: &str = "// Reconstructed by rustcom_explorer; review before use.\n\
+    use windows::core::{Result, BSTR, HRESULT, IUnknown, PSTR, PWSTR};\n\
+    use windows::Win32::Foundation::VARIANT_BOOL;\n\
+    use windows::Win32::System::Com::{IDispatch, CY, SAFEARRAY};\n\
+    use windows::Win32::System::Variant::VARIANT;\n\n";
+
+/// Renders a windows-rs style trait skeleton for the type.
+///
+/// Methods become `fn Name(&self, ...) -> Result<T>` and properties become
+/// getter/setter pairs. Types without a windows-rs equivalent fall back to
+/// `VARIANT` and are flagged with an `unmapped` comment.
+pub fn to_rust_bindings(details: &TypeDetails) -> String {
+    format!("{}{}", RUST_PRELUDE, rust_trait(details))
+}
+
+/// Renders [`to_rust_bindings`] for several types as one file, with the imports emitted once.
+pub fn to_rust_bindings_all(types: &[TypeDetails]) -> String {
+    let traits: Vec<String> = types.iter().map(rust_trait).collect();
+    format!("{}{}", RUST_PRELUDE, traits.join("\n"))
+}
+
+/// The constants and trait of [`to_rust_bindings`], without the imports.
+fn rust_trait(details: &TypeDetails) -> String {
+    let (interface_name, _) = interface_names(&details.name);
+    let mut buffer = String::new();
+
+    for member in &details.members {
+        if let Member::Constant { name, value, value_type, .. } = member {
+            buffer.push_str("#[allow(non_upper_case_globals)]\n");
+            buffer.push_str(&format!("pub const {}: {} = {};\n", name, rust_type_or_comment(value_type), value));
+        }
+    }
+    if details.members.iter().any(|member| matches!(member, Member::Constant { .. })) {
+        buffer.push('\n');
+    }
+
+    if !details.description.is_empty() {
+        buffer.push_str(&format!("/// {}\n", details.description));
+    }
+    buffer.push_str("#[allow(non_snake_case)]\n");
+    buffer.push_str(&format!("pub trait {} {{\n", interface_name));
+
+    for member in &details.members {
+        match member {
+            Member::Method { name, params: method_params, return_type, .. } => {
+                let mut params = vec!["&self".to_string()];
+                let mut ret = if return_type == "HResult" { "()".to_string() } else { rust_type_or_comment(return_type) };
+                for param in method_params {
+                    if param.is_retval() {
+                        // windows-rs returns `[out, retval]` parameters through `Result`
+                        let pointee = param.ty.strip_suffix('*').or_else(|| param.ty.strip_suffix('&')).unwrap_or(&param.ty);
+                        ret = rust_type_or_comment(pointee);
+                    } else {
+                        let default = param.default.as_ref().map(|d| format!(" /* = {} */", d)).unwrap_or_default();
+                        params.push(format!("{}: {}{}", rust_ident(&param.name), rust_type_or_comment(&param.ty), default));
+                    }
+                }
+                buffer.push_str(&format!("    fn {}({}) -> Result<{}>;\n", name, params.join(", "), ret));
+            }
+            Member::Property { name, value_type, access, .. } => {
+                let ty = rust_type_or_comment(value_type);
+                if matches!(access, AccessMode::Read | AccessMode::ReadWrite) {
+                    buffer.push_str(&format!("    fn {}(&self) -> Result<{}>;\n", name, ty));
+                }
+                if matches!(access, AccessMode::Write | AccessMode::ReadWrite) {
+                    buffer.push_str(&format!("    fn Set{}(&self, value: {}) -> Result<()>;\n", name, ty));
+                }
+            }
+            Member::Constant { .. } => {}
+        }
+    }
+    buffer.push_str("}\n");
+    buffer
+}
+
+/// Maps a [`vartype_to_string`] name to a Python type hint; anything without a plain equivalent is `Any`.
+fn python_type(type_name: &str) -> &'static str {
+    // By-reference parameters carry the same values as their pointee
+    let type_name = type_name.strip_suffix('&').or_else(|| type_name.strip_suffix('*')).unwrap_or(type_name);
+    match type_name {
+        "Void" => "None",
+        "Short" | "Long" | "Int" | "Byte" | "UShort" | "ULong" | "UInt" | "Int64" | "UInt64" | "Error"
+        | "HResult" => "int",
+        "Single" | "Double" | "Currency" | "Decimal" => "float",
+        "String" | "String (LPSTR)" | "String (LPWSTR)" => "str",
+        "Boolean" => "bool",
+        _ => "Any",
+    }
+}
+
+/// Escapes COM names that collide with Python keywords by appending an underscore.
+fn python_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+        "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+        "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+    ];
+    if KEYWORDS.contains(&name) { format!("{}_", name) } else { name.to_string() }
+}
+
+/// Formats the DISPID comment that precedes a stub member.
+fn python_dispid_comment(member: &Member) -> String {
+    match member.dispid() {
+        Some(dispid) => format!("    # DISPID {} (0x{:08X})\n", dispid, dispid),
+        None => String::new(),
+    }
+}
+
+/// Banner and imports that start every generated Python stub.
+const PYTHON_PRELUDE: &str = "# Reconstructed by rustcom_explorer; review before use.\nfrom typing import Any\n\n";
+
+/// Renders a `.pyi`-style stub class for the type, for use with `win32com.client` or `comtypes`.
+///
+/// Methods keep their parameter order, with `[out, retval]` parameters turned into the return type.
+/// Readable properties become `@property` getters (plus a setter when writable), write-only
+/// properties become annotated attributes, and constants become class attributes.
+pub fn to_python_stub(details: &TypeDetails) -> String {
+    format!("{}{}", PYTHON_PRELUDE, python_class(details))
+}
+
+/// Renders [`to_python_stub`] for several types as one file, with the imports emitted once.
+pub fn to_python_stub_all(types: &[TypeDetails]) -> String {
+    let classes: Vec<String> = types.iter().map(python_class).collect();
+    format!("{}{}", PYTHON_PRELUDE, classes.join("\n"))
+}
+
+/// The class of [`to_python_stub`], without the imports.
+fn python_class(details: &TypeDetails) -> String {
+    let mut buffer = String::new();
+    buffer.push_str(&format!("class {}:\n", python_ident(&details.name)));
+    if !details.description.is_empty() {
+        buffer.push_str(&format!("    \"\"\"{}\"\"\"\n", details.description.replace('\\', "\\\\").replace('"', "\\\"")));
+    }
+    for interface in &details.interfaces {
+        buffer.push_str(&format!("    # Implements: {}\n", interface.label()));
+    }
+
+    let mut body = String::new();
+    for member in &details.members {
+        match member {
+            Member::Method { name, params: method_params, return_type, .. } => {
+                let mut params = vec!["self".to_string()];
+                let mut ret = python_type(return_type);
+                // Python forbids required parameters after defaulted ones
+                let mut defaulted = false;
+                for param in method_params {
+                    if param.is_retval() {
+                        ret = python_type(&param.ty);
+                    } else if param.variadic {
+                        params.push(format!("*{}: Any", python_ident(&param.name)));
+                    } else {
+                        defaulted |= param.optional || param.default.is_some();
+                        let default = if defaulted { " = ..." } else { "" };
+                        params.push(format!("{}: {}{}", python_ident(&param.name), python_type(&param.ty), default));
+                    }
+                }
+                body.push_str(&python_dispid_comment(member));
+                body.push_str(&format!("    def {}({}) -> {}: ...\n", python_ident(name), params.join(", "), ret));
+            }
+            Member::Property { name, value_type, access, .. } => {
+                let name = python_ident(name);
+                let ty = python_type(value_type);
+                body.push_str(&python_dispid_comment(member));
+                if *access == AccessMode::Write {
+                    body.push_str(&format!("    {}: {}  # write-only\n", name, ty));
+                    continue;
+                }
+                body.push_str(&format!("    @property\n    def {}(self) -> {}: ...\n", name, ty));
+                if *access == AccessMode::ReadWrite {
+                    body.push_str(&format!("    @{}.setter\n    def {}(self, value: {}) -> None: ...\n", name, name, ty));
+                }
+            }
+            Member::Constant { name, value, value_type, .. } => {
+                body.push_str(&format!("    {}: {} = {}\n", python_ident(name), python_type(value_type), value));
+            }
+        }
+    }
+    if body.is_empty() {
+        body.push_str("    ...\n");
+    }
+    buffer.push_str(&body);
+    buffer
+}
+
+/// Maps a [`vartype_to_string`] name to a .NET type, or `None` if it has no direct equivalent.
+fn csharp_type(type_name: &str) -> Option<String> {
+    if let Some(inner) = type_name.strip_suffix("[]") {
+        return csharp_type(inner).map(|ty| format!("{}[]", ty));
+    }
+
+    let ty = match type_name {
+        "Void" => "void",
+        "Short" => "short",
+        "Long" | "Int" | "Error" | "HResult" => "int",
+        "Single" => "float",
+        "Double" => "double",
+        "Currency" | "Decimal" => "decimal",
+        "Date" => "DateTime",
+        "String" | "String (LPSTR)" | "String (LPWSTR)" => "string",
+        "Boolean" => "bool",
+        "Variant" | "IDispatch" | "IUnknown" => "object",
+        "Byte" => "byte",
+        "UShort" => "ushort",
+        "ULong" | "UInt" => "uint",
+        "Int64" => "long",
+        "UInt64" => "ulong",
+        "Pointer" => "IntPtr",
+        "SafeArray" => "Array",
+        "GUID" => "Guid",
+        _ => return None,
+    };
+    Some(ty.to_string())
+}
+
+/// Like [`csharp_type`], falling back to `object` with a marker comment for unmapped types.
+fn csharp_type_or_comment(type_name: &str) -> String {
+    csharp_type(type_name).unwrap_or_else(|| format!("object /* unmapped: {} */", type_name))
+}
+
+/// Escapes COM names that collide with C# keywords with the `@` prefix.
+fn csharp_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class",
+        "const", "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event",
+        "explicit", "extern", "false", "finally", "fixed", "float", "for", "foreach", "goto", "if",
+        "implicit", "in", "int", "interface", "internal", "is", "lock", "long", "namespace", "new", "null",
+        "object", "operator", "out", "override", "params", "private", "protected", "public", "readonly",
+        "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc", "static", "string", "struct",
+        "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong", "unchecked", "unsafe",
+        "ushort", "using", "virtual", "void", "volatile", "while",
+    ];
+    if KEYWORDS.contains(&name) { format!("@{}", name) } else { name.to_string() }
+}
+
+/// Strips the braces off a registry-style GUID for use in a `[Guid("...")]` attribute.
+fn csharp_guid(guid: &str) -> &str {
+    guid.trim_start_matches('{').trim_end_matches('}')
+}
+
+/// Banner and `using` directives that start every generated C# file.
+const CSHARP_PRELUDE: &str = "// Reconstructed by rustcom_explorer; review before use.\nusing System;\nusing System.Runtime.InteropServices;\n\n";
+
+/// Renders a `[ComImport]` C# interface declaration for the type, followed by a coclass stub.
+///
+/// `clsid` is the coclass the type was inspected through; pass an empty string to omit the stub.
+/// `[out, retval]` parameters become return values, by-reference parameters become `ref`/`out`,
+/// and dispatch members carry their `[DispId]`. Types without a .NET equivalent fall back to
+/// `object` and are flagged with an `unmapped` comment.
+pub fn to_csharp_interop(details: &TypeDetails, clsid: &str) -> String {
+    format!("{}{}", CSHARP_PRELUDE, csharp_declarations(details, clsid))
+}
+
+/// Renders [`to_csharp_interop`] for several types as one file, with the `using` directives emitted once
+/// and no coclass stubs, since a type library lists its coclasses among the types.
+pub fn to_csharp_interop_all(types: &[TypeDetails]) -> String {
+    let declarations: Vec<String> = types.iter().map(|details| csharp_declarations(details, "")).collect();
+    format!("{}{}", CSHARP_PRELUDE, declarations.join("\n"))
+}
+
+/// The declarations of [`to_csharp_interop`], without the `using` directives.
+fn csharp_declarations(details: &TypeDetails, clsid: &str) -> String {
+    let (interface_name, coclass_name) = interface_names(&details.name);
+    let mut buffer = String::new();
+
+    if details.typekind == "Coclass" {
+        for interface in &details.interfaces {
+            buffer.push_str(&format!("// Implements: {}\n", interface.label()));
+        }
+        buffer.push_str(&format!(
+            "[ComImport, Guid(\"{}\")]\npublic class {}Class {{ }}\n",
+            csharp_guid(&details.guid),
+            coclass_name
+        ));
+        return buffer;
+    }
+
+    let interface_type = match details.interface_kind {
+        Some(InterfaceKind::Vtable) => "InterfaceIsIUnknown",
+        Some(InterfaceKind::Dispatch) => "InterfaceIsIDispatch",
+        // Unknown kinds are assumed to be dual, the common case for automation servers
+        Some(InterfaceKind::Dual) | None => "InterfaceIsDual",
+    };
+    let with_dispids = details.interface_kind != Some(InterfaceKind::Vtable);
+    let dispid_attribute = |member: &Member| match member.dispid() {
+        Some(dispid) if with_dispids => format!("    [DispId({})]\n", dispid),
+        _ => String::new(),
+    };
+
+    if !details.description.is_empty() {
+        buffer.push_str(&format!("/// <summary>{}</summary>\n", xml_text(&details.description)));
+    }
+    buffer.push_str(&format!(
+        "[ComImport, Guid(\"{}\"), InterfaceType(ComInterfaceType.{})]\n",
+        csharp_guid(&details.guid),
+        interface_type
+    ));
+    buffer.push_str(&format!("public interface {}\n{{\n", interface_name));
+
+    for member in &details.members {
+        match member {
+            Member::Method { name, params: method_params, return_type, .. } => {
+                let mut params = Vec::new();
+                let mut ret = if return_type == "HResult" { "void".to_string() } else { csharp_type_or_comment(return_type) };
+                for param in method_params {
+                    let by_ref = param.ty.strip_suffix('&').or_else(|| param.ty.strip_suffix('*'));
+                    if param.is_retval() {
+                        ret = csharp_type_or_comment(by_ref.unwrap_or(&param.ty));
+                        continue;
+                    }
+                    let ident = csharp_ident(&param.name);
+                    let declaration = if param.variadic {
+                        format!("[In] params object[] {}", ident)
+                    } else if let Some(pointee) = by_ref {
+                        let modifier = if param.is_out() && !param.is_in() { "out" } else { "ref" };
+                        format!("{} {} {}", modifier, csharp_type_or_comment(pointee), ident)
+                    } else {
+                        format!("{} {}", csharp_type_or_comment(&param.ty), ident)
+                    };
+                    let optional = if param.optional { "[Optional] " } else { "" };
+                    let default = param.default.as_ref().map(|d| format!(" /* = {} */", d)).unwrap_or_default();
+                    params.push(format!("{}{}{}", optional, declaration, default));
+                }
+                buffer.push_str(&dispid_attribute(member));
+                buffer.push_str(&format!("    {} {}({});\n", ret, csharp_ident(name), params.join(", ")));
+            }
+            Member::Property { name, value_type, access, .. } => {
+                let accessors = match access {
+                    AccessMode::Read => "get; ",
+                    AccessMode::Write => "set; ",
+                    AccessMode::ReadWrite => "get; set; ",
+                };
+                buffer.push_str(&dispid_attribute(member));
+                buffer.push_str(&format!(
+                    "    {} {} {{ {}}}\n",
+                    csharp_type_or_comment(value_type),
+                    csharp_ident(name),
+                    accessors
+                ));
+            }
+            Member::Constant { .. } => {}
+        }
+    }
+    buffer.push_str("}\n");
+
+    let constants: Vec<_> = details
+        .members
+        .iter()
+        .filter_map(|member| match member {
+            Member::Constant { name, value, value_type, .. } => Some((name, value, value_type)),
+            _ => None,
+        })
+        .collect();
+    if !constants.is_empty() {
+        buffer.push_str(&format!("\npublic static class {}Constants\n{{\n", coclass_name));
+        for (name, value, value_type) in constants {
+            buffer.push_str(&format!(
+                "    public const {} {} = {};\n",
+                csharp_type_or_comment(value_type),
+                csharp_ident(name),
+                value
+            ));
+        }
+        buffer.push_str("}\n");
+    }
+
+    if !clsid.is_empty() {
+        buffer.push_str(&format!(
+            "\n// Create with `({})new {}Class()`\n[ComImport, Guid(\"{}\")]\npublic class {}Class {{ }}\n",
+            interface_name,
+            coclass_name,
+            csharp_guid(clsid),
+            coclass_name
+        ));
+    }
+    buffer
+}
+
+/// Escapes text for an XML doc comment.
+fn xml_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Maps a [`vartype_to_string`] name to a TypeScript type; anything without a plain equivalent is `any`.
+fn typescript_type(type_name: &str) -> &'static str {
+    let type_name = type_name.strip_suffix('&').or_else(|| type_name.strip_suffix('*')).unwrap_or(type_name);
+    match type_name {
+        "Void" => "void",
+        "Short" | "Long" | "Int" | "Byte" | "UShort" | "ULong" | "UInt" | "Int64" | "UInt64" | "Single"
+        | "Double" | "Currency" | "Decimal" | "Error" | "HResult" => "number",
+        "String" | "String (LPSTR)" | "String (LPWSTR)" => "string",
+        "Boolean" => "boolean",
+        _ => "any",
+    }
+}
+
+/// Escapes parameter names that are reserved words in TypeScript by appending an underscore.
+fn typescript_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do",
+        "else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import", "in",
+        "instanceof", "new", "null", "return", "super", "switch", "this", "throw", "true", "try", "typeof",
+        "var", "void", "while", "with", "implements", "interface", "let", "package", "private",
+        "protected", "public", "static", "yield",
+    ];
+    if KEYWORDS.contains(&name) { format!("{}_", name) } else { name.to_string() }
+}
+
+/// Renders `.d.ts` declarations for the type inside a namespace named after its coclass.
+///
+/// Methods keep their parameter order, with `[out, retval]` parameters turned into the return type.
+/// Properties become `get`/`set` accessors and constants become `const` declarations.
+pub fn to_typescript_declarations(details: &TypeDetails) -> String {
+    let (interface_name, coclass_name) = interface_names(&details.name);
+    let mut buffer = String::new();
+
+    buffer.push_str("// Reconstructed by rustcom_explorer; review before use.\n");
+    if details.typekind == "Coclass" {
+        buffer.push_str(&format!("declare namespace {} {{\n", details.name));
+        for interface in &details.interfaces {
+            buffer.push_str(&format!("    // Implements: {}\n", interface.label()));
+        }
+        buffer.push_str(&format!("    const CLSID: \"{}\";\n}}\n", details.guid));
+        return buffer;
+    }
+
+    buffer.push_str(&format!("declare namespace {} {{\n", coclass_name));
+    for member in &details.members {
+        if let Member::Constant { name, value, .. } = member {
+            buffer.push_str(&format!("    const {} = {};\n", name, value));
+        }
+    }
+    if details.members.iter().any(|member| matches!(member, Member::Constant { .. })) {
+        buffer.push('\n');
+    }
+
+    if !details.description.is_empty() {
+        buffer.push_str(&format!("    /** {} */\n", details.description.replace("*/", "*\\/")));
+    }
+    buffer.push_str(&format!("    interface {} {{\n", interface_name));
+    for member in &details.members {
+        match member {
+            Member::Method { name, params: method_params, return_type, .. } => {
+                let mut params = Vec::new();
+                let mut ret = typescript_type(return_type);
+                // TypeScript forbids required parameters after optional ones
+                let mut optional = false;
+                for param in method_params {
+                    if param.is_retval() {
+                        ret = typescript_type(&param.ty);
+                    } else if param.variadic {
+                        params.push(format!("...{}: any[]", typescript_ident(&param.name)));
+                    } else {
+                        optional |= param.optional || param.default.is_some();
+                        let marker = if optional { "?" } else { "" };
+                        params.push(format!("{}{}: {}", typescript_ident(&param.name), marker, typescript_type(&param.ty)));
+                    }
+                }
+                buffer.push_str(&format!("        {}({}): {};\n", name, params.join(", "), ret));
+            }
+            Member::Property { name, value_type, access, .. } => {
+                let ty = typescript_type(value_type);
+                if matches!(access, AccessMode::Read | AccessMode::ReadWrite) {
+                    buffer.push_str(&format!("        get {}(): {};\n", name, ty));
+                }
+                if matches!(access, AccessMode::Write | AccessMode::ReadWrite) {
+                    buffer.push_str(&format!("        set {}(value: {});\n", name, ty));
+                }
+            }
+            Member::Constant { .. } => {}
+        }
+    }
+    buffer.push_str("    }\n}\n");
+    buffer
+}
+
+/// Renders a PowerShell snippet that creates the object and sketches calls to its members.
+///
+/// The object is created by `progid` when known, falling back to `[Type]::GetTypeFromCLSID`;
+/// pass `None` and an empty `clsid` to omit the creation line. Member examples are commented
+/// out so the snippet can be pasted without invoking anything.
+pub fn to_powershell_snippet(details: &TypeDetails, progid: Option<&str>, clsid: &str) -> String {
+    let mut buffer = String::new();
+    buffer.push_str(&format!("# {}", details.name));
+    if !details.description.is_empty() {
+        buffer.push_str(&format!(" - {}", details.description));
+    }
+    buffer.push('\n');
+    match progid {
+        Some(progid) => buffer.push_str(&format!("$obj = New-Object -ComObject {}\n", progid)),
+        None if !clsid.is_empty() => buffer.push_str(&format!(
+            "$obj = [Activator]::CreateInstance([Type]::GetTypeFromCLSID('{}'))\n",
+            clsid
+        )),
+        None => {}
+    }
+
+    for member in &details.members {
+        match member {
+            Member::Method { name, params, return_type, .. } => {
+                let mut args = Vec::new();
+                // A plain HRESULT is turned into an exception, not a result
+                let mut returns = !matches!(return_type.to_ascii_lowercase().as_str(), "void" | "hresult");
+                for param in params {
+                    if param.is_retval() {
+                        returns = true;
+                    } else {
+                        args.push(format!("${}", param.name));
+                    }
+                }
+                let assign = if returns { "$result = " } else { "" };
+                buffer.push_str(&format!("# {}$obj.{}({})\n", assign, name, args.join(", ")));
+            }
+            Member::Property { name, access, .. } => {
+                if *access != AccessMode::Write {
+                    buffer.push_str(&format!("# $obj.{}\n", name));
+                }
+                if *access != AccessMode::Read {
+                    buffer.push_str(&format!("# $obj.{} = $value\n", name));
+                }
+            }
+            Member::Constant { name, value, .. } => {
+                buffer.push_str(&format!("# ${} = {}\n", name, value));
+            }
+        }
+    }
+    buffer
+}
+
+/// Renders a GraphViz digraph linking each coclass to the interfaces it lists.
+///
+/// Coclasses are boxes and interfaces ellipses, labelled with their names; GUIDs go in
+/// tooltips. Source (event) interfaces get dashed edges. Interfaces shared by several
+/// coclasses are drawn once.
+pub fn to_dot(types: &[TypeDetails]) -> String {
+    let mut buffer = String::from("digraph com {\n    rankdir=LR;\n");
+    let mut drawn = std::collections::HashSet::new();
+    let mut edges = String::new();
+
+    for details in types {
+        let id = dot_node_id(&details.name, &details.guid);
+        let shape = if details.typekind == "Coclass" { "box" } else { "ellipse" };
+        if drawn.insert(id.clone()) {
+            buffer.push_str(&format!(
+                "    {} [label={}, shape={}, tooltip={}];\n",
+                dot_quote(&id), dot_quote(&details.name), shape, dot_quote(&details.guid)
+            ));
+        }
+        for interface in &details.interfaces {
+            let target = dot_node_id(&interface.name, &interface.guid);
+            if drawn.insert(target.clone()) {
+                buffer.push_str(&format!(
+                    "    {} [label={}, shape=ellipse, tooltip={}];\n",
+                    dot_quote(&target), dot_quote(&interface.name), dot_quote(&interface.guid)
+                ));
+            }
+            let style = if interface.source { " [style=dashed]" } else { "" };
+            edges.push_str(&format!("    {} -> {}{};\n", dot_quote(&id), dot_quote(&target), style));
+        }
+    }
+
+    buffer.push_str(&edges);
+    buffer.push_str("}\n");
+    buffer
+}
+
+/// Identifies a node by GUID, falling back to the name for types without one.
+fn dot_node_id(name: &str, guid: &str) -> String {
+    if guid.is_empty() { name.to_string() } else { guid.to_ascii_uppercase() }
+}
+
+/// Quotes a DOT identifier, escaping backslashes and double quotes.
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
 }
 
 /// Represents a member (Method or Property) of a COM object.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(tag = "kind", content = "details")]
 pub enum Member {
     Method {
         name: String,
-        signature: String,
+        #[serde(default)]
+        params: Vec<Param>,
         return_type: String,
+        /// DISPID (`MEMBERID`) used to invoke the member; absent in exports that predate it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispid: Option<i32>,
+        /// Flagged hidden or restricted in the type library
+        #[serde(default)]
+        hidden: bool,
+        /// One-line help string from the type library
+        #[serde(default)]
+        doc: String,
     },
     Property {
         name: String,
         value_type: String,
         access: AccessMode,
+        /// Arguments of a parameterized property such as `Item(index)`; empty for plain properties
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        index: Vec<Param>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispid: Option<i32>,
+        #[serde(default)]
+        hidden: bool,
+        #[serde(default)]
+        doc: String,
+    },
+    Constant {
+        name: String,
+        value: i64,
+        value_type: String,
+        #[serde(default)]
+        hidden: bool,
+        #[serde(default)]
+        doc: String,
     },
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+impl Member {
+    /// The member's help string, empty when the type library ships none.
+    pub fn doc(&self) -> &str {
+        match self {
+            Member::Method { doc, .. } | Member::Property { doc, .. } | Member::Constant { doc, .. } => doc,
+        }
+    }
+
+    /// Whether the type library marks this member hidden or restricted (plumbing such as `QueryInterface`).
+    pub fn is_hidden(&self) -> bool {
+        match self {
+            Member::Method { hidden, .. } | Member::Property { hidden, .. } | Member::Constant { hidden, .. } => *hidden,
+        }
+    }
+
+    /// Whether [`invoke_member`] can call this member without arguments: a readable, unindexed property
+    /// or a method whose only parameter is its return value.
+    pub fn is_invocable(&self) -> bool {
+        match self {
+            Member::Method { params, .. } => params.iter().all(Param::is_retval),
+            Member::Property { access, index, .. } => {
+                matches!(access, AccessMode::Read | AccessMode::ReadWrite) && index.iter().all(Param::is_retval)
+            }
+            Member::Constant { .. } => false,
+        }
+    }
+
+    /// The DISPID of a method or property, if the type library supplied one.
+    pub fn dispid(&self) -> Option<i32> {
+        match self {
+            Member::Method { dispid, .. } | Member::Property { dispid, .. } => *dispid,
+            Member::Constant { .. } => None,
+        }
+    }
+
+    /// The member's name.
+    pub fn name(&self) -> &str {
+        match self {
+            Member::Method { name, .. } | Member::Property { name, .. } | Member::Constant { name, .. } => name,
+        }
+    }
+
+    /// One-line description of the member, e.g. `Property Visible: BOOL [Read/Write]`.
+    pub fn summary(&self) -> String {
+        match self {
+            Member::Method { name, params, return_type, .. } => format!("Method {}{}", name, format_signature(params, return_type)),
+            Member::Property { name, value_type, access, index, .. } => {
+                let access_str = match access {
+                    AccessMode::Read => "Read",
+                    AccessMode::Write => "Write",
+                    AccessMode::ReadWrite => "Read/Write",
+                };
+                let index = if index.is_empty() {
+                    String::new()
+                } else {
+                    let params: Vec<String> = index.iter().map(Param::display).collect();
+                    format!("({})", params.join(", "))
+                };
+                format!("Property {}{}: {} [{}]", name, index, value_type, access_str)
+            }
+            Member::Constant { name, value, value_type, .. } => format!("Const {}: {} = {}", name, value_type, value),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Param {
+    /// A required parameter without a default.
+    pub(crate) fn new(name: &str, ty: &str, flags: u16) -> Self {
+        Param { name: name.to_string(), ty: ty.to_string(), flags, default: None, optional: false, variadic: false }
+    }
+
+    pub(crate) fn optional(mut self, default: Option<&str>) -> Self {
+        self.optional = true;
+        self.default = default.map(str::to_string);
+        self
+    }
+
+    pub(crate) fn variadic(mut self) -> Self {
+        self.variadic = true;
+        self
+    }
+}
+
+/// Shorthands for building members in tests; new fields only need a default here.
+#[cfg(test)]
+impl Member {
+    /// A visible, undocumented method without a DISPID.
+    pub(crate) fn method(name: &str, params: Vec<Param>, return_type: &str) -> Self {
+        Member::Method {
+            name: name.to_string(),
+            params,
+            return_type: return_type.to_string(),
+            dispid: None,
+            hidden: false,
+            doc: String::new(),
+        }
+    }
+
+    /// A visible, undocumented property without a DISPID.
+    pub(crate) fn property(name: &str, value_type: &str, access: AccessMode) -> Self {
+        Member::Property {
+            name: name.to_string(),
+            value_type: value_type.to_string(),
+            access,
+            index: Vec::new(),
+            dispid: None,
+            hidden: false,
+            doc: String::new(),
+        }
+    }
+
+    pub(crate) fn with_index(mut self, params: Vec<Param>) -> Self {
+        if let Member::Property { index, .. } = &mut self {
+            *index = params;
+        }
+        self
+    }
+
+    pub(crate) fn with_dispid(mut self, id: i32) -> Self {
+        if let Member::Method { dispid, .. } | Member::Property { dispid, .. } = &mut self {
+            *dispid = Some(id);
+        }
+        self
+    }
+
+    pub(crate) fn with_hidden(mut self, is_hidden: bool) -> Self {
+        match &mut self {
+            Member::Method { hidden, .. } | Member::Property { hidden, .. } | Member::Constant { hidden, .. } => *hidden = is_hidden,
+        }
+        self
+    }
+
+    pub(crate) fn with_doc(mut self, text: &str) -> Self {
+        match &mut self {
+            Member::Method { doc, .. } | Member::Property { doc, .. } | Member::Constant { doc, .. } => *doc = text.to_string(),
+        }
+        self
+    }
+}
+
+/// Calling convention of an interface type.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum InterfaceKind {
+    /// Callable through both `IDispatch::Invoke` and the vtable (`TYPEFLAG_FDUAL`)
+    Dual,
+    /// A pure dispinterface, callable only through `IDispatch::Invoke`
+    Dispatch,
+    /// A custom `IUnknown`-derived interface, callable only through the vtable
+    Vtable,
+}
+
+impl InterfaceKind {
+    /// Classifies an interface from its `TYPEKIND` and `TYPEFLAGS`.
+    #[cfg(windows)]
+    fn from_type_attr(kind: TYPEKIND, type_flags: u16) -> Option<Self> {
+        let dual = type_flags as i32 & TYPEFLAG_FDUAL.0 != 0;
+        match kind {
+            TKIND_DISPATCH | TKIND_INTERFACE if dual => Some(InterfaceKind::Dual),
+            TKIND_DISPATCH => Some(InterfaceKind::Dispatch),
+            TKIND_INTERFACE => Some(InterfaceKind::Vtable),
+            _ => None,
+        }
+    }
+
+    /// Short badge for the inspection view, e.g. `[dual]`.
+    pub fn badge(&self) -> &'static str {
+        match self {
+            InterfaceKind::Dual => "[dual]",
+            InterfaceKind::Dispatch => "[dispatch]",
+            InterfaceKind::Vtable => "[vtable]",
+        }
+    }
+}
+
+/// An interface a coclass lists in its type library.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ImplementedInterface {
+    pub name: String,
+    /// The interface's IID, braced
+    pub guid: String,
+    /// An outgoing event interface the coclass fires rather than implements (`IMPLTYPEFLAG_FSOURCE`)
+    #[serde(default)]
+    pub source: bool,
+    /// The default interface for its direction (`IMPLTYPEFLAG_FDEFAULT`)
+    #[serde(default)]
+    pub default: bool,
+}
+
+impl ImplementedInterface {
+    /// The name with IDL-style attributes, e.g. `[default, source] DWidgetEvents`.
+    pub fn label(&self) -> String {
+        let attributes: Vec<&str> = [(self.default, "default"), (self.source, "source")]
+            .into_iter()
+            .filter_map(|(set, attribute)| set.then_some(attribute))
+            .collect();
+        if attributes.is_empty() {
+            self.name.clone()
+        } else {
+            format!("[{}] {}", attributes.join(", "), self.name)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum AccessMode {
     Read,
     Write,
     ReadWrite,
 }
 
+/// Settings that govern how an object is inspected.
+#[derive(Debug, Clone, Copy)]
+pub struct InspectOptions {
+    /// Allow falling back to `CoCreateInstance` when no registered type library is found
+    pub allow_unsafe: bool,
+    /// How long dynamic instantiation may take before it is abandoned
+    pub instantiate_timeout: Duration,
+    /// Apartment joined by the instantiation worker thread
+    pub apartment: Apartment,
+    /// How often a server launch that failed transiently is retried before giving up
+    pub instantiate_retries: u32,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self {
+            allow_unsafe: false,
+            instantiate_timeout: DEFAULT_INSTANTIATE_TIMEOUT,
+            apartment: Apartment::default(),
+            instantiate_retries: DEFAULT_INSTANTIATE_RETRIES,
+        }
+    }
+}
+
+/// Opens a type's help file, jumping to `context` in compiled HTML Help (`.chm`) files.
+///
+/// Fails with a descriptive error when the file does not exist.
+pub fn open_help_file(help_file: &str, context: Option<u32>) -> Result<()> {
+    if !std::path::Path::new(help_file).exists() {
+        return Err(InspectError::Generic(format!("'{}' does not exist", help_file)).into());
+    }
+
+    #[cfg(windows)]
+    {
+        use windows::Win32::UI::Shell::ShellExecuteW;
+        use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+        use windows::core::{HSTRING, w};
+
+        let is_chm = help_file.to_ascii_lowercase().ends_with(".chm");
+        let result = match context {
+            // hh.exe maps the topic ID through the file's [MAP] section
+            Some(context) if is_chm => {
+                let parameters = HSTRING::from(format!("-mapid {} \"{}\"", context, help_file));
+                unsafe { ShellExecuteW(None, w!("open"), w!("hh.exe"), &parameters, None, SW_SHOWNORMAL) }
+            }
+            _ => unsafe { ShellExecuteW(None, w!("open"), &HSTRING::from(help_file), None, None, SW_SHOWNORMAL) },
+        };
+        // Values above 32 indicate success
+        if result.0 as isize <= 32 {
+            return Err(InspectError::Generic(format!("Failed to open '{}' (code {})", help_file, result.0 as isize)).into());
+        }
+        Ok(())
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = context;
+        Err(InspectError::Generic("Opening help files is not supported on this platform".to_string()).into())
+    }
+}
+
+/// Default budget for `CoCreateInstance` and the type info queries that follow it.
+pub const DEFAULT_INSTANTIATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default number of retries after a transient server launch failure.
+pub const DEFAULT_INSTANTIATE_RETRIES: u32 = 2;
+
+/// Delay before the first launch retry; it doubles with every further attempt.
+#[cfg(windows)]
+const LAUNCH_RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
 /// Retrieves TypeInfo for a CLSID. 
 /// 
 /// # Safety
 /// If `allow_unsafe` is `false`, this function will ONLY attempt to read from the Registry.
 /// If `allow_unsafe` is `true`, it may fallback to `CoCreateInstance`, which can execute arbitrary code.
 pub fn get_type_info(clsid_str: &str, allow_unsafe: bool) -> Result<TypeDetails> {
-    let clsid = guid_from_str(clsid_str).unwrap_or(GUID::zeroed());
-    
-    // 1. Try Registry Strategy (Safe)
-    if let Ok(type_info) = load_type_info_from_registry(clsid_str) {
-        return parse_type_info(&type_info, clsid_str)
-            .map_err(|e| InspectError::Parsing(format!("Registry TypeInfo parsing failed: {}", e)).into());
+    get_type_info_with(clsid_str, &InspectOptions { allow_unsafe, ..Default::default() })
+}
+
+/// Like [`get_type_info`], with explicit control over the instantiation timeout and apartment.
+#[tracing::instrument(level = "debug", skip(options), fields(clsid = %clsid_str))]
+pub fn get_type_info_with(clsid_str: &str, options: &InspectOptions) -> Result<TypeDetails> {
+    if let Some(mock) = crate::mock::active() {
+        return mock.details_for(clsid_str);
     }
+    #[cfg(windows)]
+    {
+        let clsid = guid_from_str(clsid_str)?;
 
-    // 2. Fallback: Dynamic Instantiation (Potentially Unsafe)
-    if allow_unsafe {
-        load_type_info_dynamic(&clsid)
-    } else {
-        Err(InspectError::Safety(
-            "Type Library not found in registry. Unsafe instantiation is disabled.".to_string()
-        ).into())
+        // 1. Try Registry Strategy (Safe)
+        match load_type_info_from_registry(clsid_str) {
+            Ok(type_info) => {
+                tracing::debug!("Using registered type library");
+                return parse_type_info(&type_info, clsid_str)
+                    .map_err(|e| InspectError::Parsing(format!("Registry TypeInfo parsing failed: {}", e)).into());
+            }
+            Err(e) => tracing::debug!("No registered type library: {:#}", e),
+        }
+
+        // 2. Fallback: Dynamic Instantiation (Potentially Unsafe)
+        if options.allow_unsafe {
+            tracing::debug!("Instantiating the object to query its type info");
+            let retries = options.instantiate_retries;
+            run_with_timeout(options, move || load_type_info_dynamic(&clsid, retries))
+        } else {
+            Err(InspectError::Safety(
+                "Type Library not found in registry. Unsafe instantiation is disabled.".to_string()
+            ).into())
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = options;
+        Err(InspectError::Generic("Inspecting COM objects is not supported on this platform".to_string()).into())
     }
 }
 
-fn guid_from_str(s: &str) -> Result<GUID> {
-    // Ensure braces for IIDFromString
-    let s_braced = if s.trim().starts_with('{') { s.to_string() } else { format!("{{{}}}", s) };
-    let wide: Vec<u16> = s_braced.encode_utf16().chain(std::iter::once(0)).collect();
-    
+/// Retrieves every type in the type library that describes a CLSID, one [`TypeDetails`] per type.
+///
+/// Uses the same registry-first strategy, safety rules and timeout as [`get_type_info_with`].
+#[tracing::instrument(level = "debug", skip(options), fields(clsid = %clsid_str))]
+pub fn get_type_library(clsid_str: &str, options: &InspectOptions) -> Result<Vec<TypeDetails>> {
+    if let Some(mock) = crate::mock::active() {
+        return mock.details_for(clsid_str).map(|details| vec![details]);
+    }
+    #[cfg(windows)]
+    {
+        let clsid = guid_from_str(clsid_str)?;
+        if let Ok(type_lib) = load_type_lib_from_registry(clsid_str) {
+            return Ok(parse_type_lib(&type_lib));
+        }
+
+        if options.allow_unsafe {
+            let retries = options.instantiate_retries;
+            run_with_timeout(options, move || load_type_lib_dynamic(&clsid, retries).map(|type_lib| parse_type_lib(&type_lib)))
+        } else {
+            Err(InspectError::Safety(
+                "Type Library not found in registry. Unsafe instantiation is disabled.".to_string()
+            ).into())
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = options;
+        Err(InspectError::Generic("Inspecting COM objects is not supported on this platform".to_string()).into())
+    }
+}
+
+/// Parses every type in a library, skipping individual types that fail to load.
+#[cfg(windows)]
+fn parse_type_lib(type_lib: &ITypeLib) -> Vec<TypeDetails> {
+    let mut types = Vec::new();
     unsafe {
-        IIDFromString(PCWSTR::from_raw(wide.as_ptr()))
-            .map_err(|e| InspectError::Generic(format!("Invalid GUID string: {}", e)).into())
+        for index in 0..type_lib.GetTypeInfoCount() {
+            if let Ok(type_info) = type_lib.GetTypeInfo(index)
+                && let Ok(details) = parse_type_info(&type_info, &format!("Type{}", index))
+            {
+                types.push(details);
+            }
+        }
+    }
+    types
+}
+
+/// Runs a dynamic-instantiation job on a COM-initialized worker thread, giving up after the configured timeout.
+///
+/// A hung server cannot be interrupted, so on timeout the worker is left to finish (or hang) on its own.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn run_with_timeout<T, F>(options: &InspectOptions, job: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let apartment = options.apartment;
+    thread::spawn(move || {
+        let result = initialize_com(apartment).and_then(|_com_guard| job());
+        let _ = tx.send(result);
+    });
+
+    match rx.recv_timeout(options.instantiate_timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(InspectError::Instantiation {
+            message: format!(
+                "Instantiation timed out after {} ms; the server may be hung or waiting for input",
+                options.instantiate_timeout.as_millis()
+            ),
+            hresult: None,
+        }
+        .into()),
+        Err(RecvTimeoutError::Disconnected) => Err(InspectError::Instantiation {
+            message: "Instantiation worker exited unexpectedly".to_string(),
+            hresult: None,
+        }
+        .into()),
     }
 }
 
+/// Parses a GUID in the 8-4-4-4-12 hex layout, with or without braces and in any case.
+///
+/// Anything else is rejected up front rather than reaching the registry or COM as a zeroed GUID.
+#[cfg(windows)]
+fn guid_from_str(s: &str) -> Result<GUID> {
+    let input = s.trim();
+    let bare = match input.strip_prefix('{') {
+        Some(rest) => rest.strip_suffix('}'),
+        None => Some(input),
+    };
+    bare.filter(|bare| crate::scanner::is_bare_guid(bare))
+        .and_then(|bare| u128::from_str_radix(&bare.replace('-', ""), 16).ok())
+        .map(GUID::from_u128)
+        .ok_or_else(|| InspectError::Generic(format!("invalid CLSID: '{}'", input)).into())
+}
+
 // --- Strategy 1: Registry Loading ---
 
-fn load_type_info_from_registry(clsid_str: &str) -> Result<ITypeInfo> {
+/// Loads the type library registered for a CLSID via `CLSID\{clsid}\TypeLib` and `Version`.
+#[cfg(windows)]
+fn load_type_lib_from_registry(clsid_str: &str) -> Result<ITypeLib> {
     let hkcr = RegKey::predef(HKEY_CLASSES_ROOT);
-    let clsid_key = hkcr.open_subkey(format!("CLSID\\{}", clsid_str))
-        .map_err(|e| InspectError::Registry(format!("CLSID key not found: {}", e)))?;
+    let clsid_path = format!("CLSID\\{}", clsid_str);
+    // Fall back to the 32-bit view for classes registered only under Wow6432Node
+    let clsid_key = hkcr.open_subkey(&clsid_path)
+        .or_else(|_| hkcr.open_subkey_with_flags(&clsid_path, KEY_READ | KEY_WOW64_32KEY))
+        .map_err(|e| InspectError::from_io("CLSID key not found", e))?;
     
     let typelib_guid_str: String = clsid_key.open_subkey("TypeLib")
         .and_then(|k| k.get_value(""))
-        .map_err(|_| InspectError::Registry("TypeLib subkey or value missing".into()))?;
+        .map_err(|e| InspectError::from_io("TypeLib subkey or value missing", e))?;
         
     let typelib_guid = guid_from_str(&typelib_guid_str)?;
 
@@ -122,16 +1605,79 @@ fn load_type_info_from_registry(clsid_str: &str) -> Result<ITypeInfo> {
         
     let (major, minor) = parse_version(&version_str).unwrap_or((1, 0));
 
+    cached_type_lib((typelib_guid, major, minor), || unsafe {
+        LoadRegTypeLib(&typelib_guid, major, minor, 0)
+            .map_err(|e| {
+                if e.code() == E_ACCESSDENIED {
+                    InspectError::Permission(format!("LoadRegTypeLib failed: {}", hresult_to_string(e.code().0)))
+                } else {
+                    InspectError::Registry(format!("LoadRegTypeLib failed: {}", e.message()))
+                }
+            })
+            .map_err(Into::into)
+    })
+}
+
+#[cfg(windows)]
+thread_local! {
+    /// Registered type libraries loaded on this thread, keyed by `(GUID, major, minor)`.
+    ///
+    /// Thousands of classes share a handful of libraries (e.g. every Office class), so a
+    /// full-registry export would otherwise call `LoadRegTypeLib` once per object. Interface
+    /// pointers belong to the apartment of the thread that obtained them, hence one cache per thread.
+    static TYPE_LIB_CACHE: RefCell<HashMap<(GUID, u16, u16), ITypeLib>> = RefCell::new(HashMap::new());
+}
+
+static TYPE_LIB_LOADS: AtomicUsize = AtomicUsize::new(0);
+static TYPE_LIB_HITS: AtomicUsize = AtomicUsize::new(0);
+
+/// How often registry type libraries were loaded versus served from the per-thread cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeLibCacheStats {
+    /// `LoadRegTypeLib` calls made
+    pub loads: usize,
+    /// Lookups answered from the cache instead
+    pub hits: usize,
+}
+
+/// Process-wide counters of the type library cache, summed over all threads.
+pub fn type_lib_cache_stats() -> TypeLibCacheStats {
+    TypeLibCacheStats { loads: TYPE_LIB_LOADS.load(Ordering::Relaxed), hits: TYPE_LIB_HITS.load(Ordering::Relaxed) }
+}
+
+/// Returns the cached library for `key`, or loads and caches it. Failures are not cached.
+#[cfg(windows)]
+fn cached_type_lib(key: (GUID, u16, u16), load: impl FnOnce() -> Result<ITypeLib>) -> Result<ITypeLib> {
+    TYPE_LIB_CACHE.with_borrow_mut(|cache| {
+        if let Some(type_lib) = cache.get(&key) {
+            TYPE_LIB_HITS.fetch_add(1, Ordering::Relaxed);
+            return Ok(type_lib.clone());
+        }
+        let type_lib = load()?;
+        TYPE_LIB_LOADS.fetch_add(1, Ordering::Relaxed);
+        cache.insert(key, type_lib.clone());
+        Ok(type_lib)
+    })
+}
+
+/// Releases this thread's cached type libraries. Must run before the thread calls `CoUninitialize`.
+pub fn clear_type_lib_cache() {
+    #[cfg(windows)]
+    TYPE_LIB_CACHE.with_borrow_mut(HashMap::clear);
+}
+
+#[cfg(windows)]
+fn load_type_info_from_registry(clsid_str: &str) -> Result<ITypeInfo> {
+    let type_lib = load_type_lib_from_registry(clsid_str)?;
+
     unsafe {
-        let type_lib: ITypeLib = LoadRegTypeLib(&typelib_guid, major, minor, 0)
-            .map_err(|e| InspectError::Registry(format!("LoadRegTypeLib failed: {}", e.message())))?;
-            
-        type_lib.GetTypeInfoOfGuid(&guid_from_str(clsid_str).unwrap_or_default())
+        type_lib.GetTypeInfoOfGuid(&guid_from_str(clsid_str)?)
             .or_else(|_| type_lib.GetTypeInfo(0))
             .map_err(|e| InspectError::Registry(format!("GetTypeInfo from TypeLib failed: {}", e.message())).into())
     }
 }
 
+#[cfg(windows)]
 fn parse_version(ver: &str) -> Option<(u16, u16)> {
     let parts: Vec<&str> = ver.split('.').collect();
     if parts.len() >= 2 {
@@ -148,23 +1694,63 @@ fn parse_version(ver: &str) -> Option<(u16, u16)> {
 
 // --- Strategy 2: Dynamic Instantiation ---
 
-fn load_type_info_dynamic(clsid: &GUID) -> Result<TypeDetails> {
+/// Whether a launch failure may go away on its own: the server process started but had not
+/// registered its class objects yet, or was too busy to accept the activation call.
+#[cfg(windows)]
+fn is_transient_launch_error(code: windows::core::HRESULT) -> bool {
+    code == CO_E_SERVER_EXEC_FAILURE || code == RPC_E_CALL_REJECTED
+}
+
+/// Runs `attempt`, retrying up to `retries` more times with exponential backoff while it fails transiently.
+#[cfg(windows)]
+fn retry_transient<T>(
+    retries: u32,
+    backoff: Duration,
+    mut attempt: impl FnMut() -> windows::core::Result<T>,
+) -> windows::core::Result<T> {
+    let mut delay = backoff;
+    for retry in 1..=retries {
+        match attempt() {
+            Err(e) if is_transient_launch_error(e.code()) => {
+                tracing::debug!(retry, hresult = format_args!("0x{:08X}", e.code().0), "Server launch failed transiently; retrying");
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    attempt()
+}
+
+/// Instantiates the class to reach its `IDispatch` (potentially unsafe).
+///
+/// Transient launch failures are retried `retries` times; the retries count against the instantiation timeout.
+#[cfg(windows)]
+fn create_dispatch(clsid: &GUID, retries: u32) -> Result<IDispatch> {
     unsafe {
-        let unknown: IDispatch = CoCreateInstance(clsid, None, CLSCTX_ALL)
+        retry_transient(retries, LAUNCH_RETRY_BACKOFF, || CoCreateInstance(clsid, None, CLSCTX_ALL))
             .map_err(|e| {
-                let code = e.code().0;
+                let code = e.code();
                 match code {
-                    -2147024891 => InspectError::Permission("Access Denied (E_ACCESSDENIED)".to_string()), // 0x80070005
-                    -2147221230 => InspectError::Permission("License Missing (CLASS_E_NOTLICENSED)".to_string()), // 0x80040112
-                    -2147221164 => InspectError::Registry("Class not registered (REGDB_E_CLASSNOTREG)".to_string()), // 0x80040154
-                    -2147221005 => InspectError::Registry("Invalid Class String (CO_E_CLASSSTRING)".to_string()), // 0x800401F3
-                    _ => InspectError::Instantiation { 
-                        message: e.message().to_string(), 
-                        hresult: Some(code) 
+                    E_ACCESSDENIED | CLASS_E_NOTLICENSED => {
+                        InspectError::Permission(hresult_to_string(code.0))
                     }
+                    CO_E_CLASSSTRING => InspectError::Registry(hresult_to_string(code.0)),
+                    _ => InspectError::Instantiation {
+                        message: e.message().to_string(),
+                        hresult: Some(code.0),
+                    },
                 }
-            })?;
-        
+                .into()
+            })
+    }
+}
+
+#[cfg(windows)]
+fn load_type_info_dynamic(clsid: &GUID, retries: u32) -> Result<TypeDetails> {
+    let unknown = create_dispatch(clsid, retries)?;
+
+    unsafe {
         let type_info = unknown.GetTypeInfo(0, 0)
             .map_err(|e| InspectError::Parsing(format!("GetTypeInfo(0) failed: {}", e.message())))?;
         
@@ -173,114 +1759,574 @@ fn load_type_info_dynamic(clsid: &GUID) -> Result<TypeDetails> {
     }
 }
 
-// --- Parsing Logic ---
+/// Finds the type library an instantiated object's type information lives in.
+#[cfg(windows)]
+fn load_type_lib_dynamic(clsid: &GUID, retries: u32) -> Result<ITypeLib> {
+    let dispatch = create_dispatch(clsid, retries)?;
 
-fn parse_type_info(type_info: &ITypeInfo, default_name: &str) -> Result<TypeDetails> {
-    let mut members = Vec::new();
-    let attr = ScopedTypeAttr::new(type_info).map_err(|e| InspectError::Parsing(e.to_string()))?;
-    let (name, doc) = get_documentation(type_info, -1).unwrap_or((default_name.to_string(), String::new()));
+    unsafe {
+        let type_info = dispatch.GetTypeInfo(0, 0)
+            .map_err(|e| InspectError::Parsing(format!("GetTypeInfo(0) failed: {}", e.message())))?;
+        let mut type_lib = None;
+        let mut index = 0;
+        type_info.GetContainingTypeLib(&mut type_lib, &mut index)
+            .map_err(|e| InspectError::Parsing(format!("GetContainingTypeLib failed: {}", e.message())))?;
+        type_lib.ok_or_else(|| InspectError::Parsing("Object has no containing type library".to_string()).into())
+    }
+}
+
+// --- Invocation ---
+
+/// Owns a `VARIANT` and releases its contents with `VariantClear` on drop.
+#[cfg(windows)]
+struct OwnedVariant(VARIANT);
+
+#[cfg(windows)]
+impl Drop for OwnedVariant {
+    fn drop(&mut self) {
+        let _ = unsafe { VariantClear(&mut self.0) };
+    }
+}
+
+/// Instantiates the object and calls a zero-argument method or property-get by name, returning the result as text.
+///
+/// # Safety
+/// This always runs `CoCreateInstance` and executes the member's implementation, so it requires
+/// `allow_unsafe` and is subject to the same timeout as dynamic inspection.
+#[tracing::instrument(level = "debug", skip(options), fields(clsid = %clsid_str))]
+pub fn invoke_member(clsid_str: &str, member_name: &str, options: &InspectOptions) -> Result<String> {
+    if !options.allow_unsafe {
+        return Err(InspectError::Safety("Invoking members requires unsafe mode.".to_string()).into());
+    }
+    if crate::mock::active().is_some() {
+        return Err(InspectError::Generic("Invoking members is not available with mock data.".to_string()).into());
+    }
+    #[cfg(windows)]
+    {
+        let clsid = guid_from_str(clsid_str)?;
+        let member_name = member_name.to_string();
+        let retries = options.instantiate_retries;
+        run_with_timeout(options, move || invoke_dispatch(&clsid, &member_name, retries))
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = member_name;
+        Err(InspectError::Generic("Invoking members is not supported on this platform".to_string()).into())
+    }
+}
+
+#[cfg(windows)]
+fn invoke_dispatch(clsid: &GUID, member_name: &str, retries: u32) -> Result<String> {
+    let dispatch = create_dispatch(clsid, retries)?;
+    let wide: Vec<u16> = member_name.encode_utf16().chain(std::iter::once(0)).collect();
+    let names = [PCWSTR::from_raw(wide.as_ptr())];
 
     unsafe {
-        // Iterate Functions
-        for i in 0..attr.0.cFuncs {
-            if let Ok(func_desc) = ScopedFuncDesc::new(type_info, i as u32) {
-                let desc = *func_desc.0;
-                let (func_name, _) = get_documentation(type_info, desc.memid).unwrap_or(("Unknown".to_string(), String::new()));
-                
-                let mut names = vec![BSTR::new(); 10]; 
-                let mut c_names = 0;
-                
-                let _ = type_info.GetNames(
-                    desc.memid, 
-                    &mut names, 
-                    &mut c_names
-                );
-                
-                let mut args = Vec::new();
-                let param_count = desc.cParams as usize;
-                let params_ptr = desc.lprgelemdescParam; 
+        let mut dispid = 0;
+        dispatch.GetIDsOfNames(&GUID::zeroed(), names.as_ptr(), 1, 0, &mut dispid)
+            .map_err(|e| InspectError::Generic(format!("'{}' not found via GetIDsOfNames: {}", member_name, e.message())))?;
 
-                for p in 0..param_count {
-                    let arg_name = if (p + 1) < c_names as usize {
-                        names[p + 1].to_string()
-                    } else {
-                        format!("arg{}", p)
-                    };
-                    
-                    let elem = *params_ptr.add(p);
-                    let arg_type = vartype_to_string(elem.tdesc.vt.0);
-                    args.push(format!("{}: {}", arg_name, arg_type));
-                }
+        let params = DISPPARAMS::default();
+        let mut result = OwnedVariant(VARIANT::default());
+        let mut excep_info = EXCEPINFO::default();
+        let invoked = dispatch.Invoke(
+            dispid,
+            &GUID::zeroed(),
+            0,
+            DISPATCH_METHOD | DISPATCH_PROPERTYGET,
+            &params,
+            Some(&mut result.0),
+            Some(&mut excep_info),
+            None,
+        );
+        let description = excep_info.bstrDescription.to_string();
+        std::mem::ManuallyDrop::drop(&mut excep_info.bstrSource);
+        std::mem::ManuallyDrop::drop(&mut excep_info.bstrDescription);
+        std::mem::ManuallyDrop::drop(&mut excep_info.bstrHelpFile);
 
-                let return_type = vartype_to_string(desc.elemdescFunc.tdesc.vt.0);
+        invoked.map_err(|e| InspectError::Instantiation {
+            message: if description.is_empty() { e.message().to_string() } else { description },
+            hresult: Some(e.code().0),
+        })?;
 
-                match desc.invkind {
-                    INVOKE_FUNC => {
-                        members.push(Member::Method {
-                            name: func_name,
-                            signature: format!("({}) -> {}", args.join(", "), return_type),
-                            return_type,
-                        });
-                    },
-                    INVOKE_PROPERTYGET | INVOKE_PROPERTYPUT | INVOKE_PROPERTYPUTREF => {
-                        let access = if desc.invkind == INVOKE_PROPERTYGET { AccessMode::Read } else { AccessMode::Write };
-                        let prop_type = if desc.invkind == INVOKE_PROPERTYGET {
-                            return_type
-                        } else if !args.is_empty() {
-                            args.last().unwrap().split(": ").nth(1).unwrap_or("Variant").to_string()
-                        } else {
-                            "Variant".to_string()
-                        };
-
-                        members.push(Member::Property {
-                            name: func_name,
-                            value_type: prop_type,
-                            access,
-                        });
-                    },
-                    _ => {}
-                }
-            }
+        Ok(variant_result_to_string(&result.0))
+    }
+}
+
+/// Renders an `Invoke` result, letting OLE Automation coerce it to a string where it can.
+///
+/// # Safety
+/// `value` must be an initialized `VARIANT`.
+#[cfg(windows)]
+unsafe fn variant_result_to_string(value: &VARIANT) -> String {
+    unsafe {
+        let vt = value.Anonymous.Anonymous.vt;
+        match vt {
+            VT_EMPTY => return "(empty)".to_string(),
+            VT_NULL => return "(null)".to_string(),
+            _ => {}
+        }
+        let mut text = OwnedVariant(VARIANT::default());
+        if VariantChangeType(&mut text.0, value, VARIANT_ALPHABOOL, VT_BSTR).is_ok() {
+            text.0.Anonymous.Anonymous.Anonymous.bstrVal.to_string()
+        } else {
+            format!("<{}>", vartype_to_string(vt.0))
+        }
+    }
+}
+
+// --- Parsing Logic ---
+
+/// The parts of a `TYPEATTR` the parser reads.
+#[cfg(windows)]
+#[derive(Debug, Clone)]
+pub struct TypeAttrInfo {
+    pub guid: GUID,
+    pub version: (u16, u16),
+    pub typekind: TYPEKIND,
+    /// Raw `TYPEFLAGS` bits
+    pub type_flags: u16,
+    pub func_count: u16,
+    pub var_count: u16,
+}
+
+/// A decoded `FUNCDESC`, with parameter and return types already rendered as text.
+#[cfg(windows)]
+#[derive(Debug, Clone)]
+pub struct FuncInfo {
+    pub memid: i32,
+    pub invkind: INVOKEKIND,
+    /// Raw `FUNCFLAGS` bits
+    pub flags: u16,
+    pub params: Vec<ParamInfo>,
+    pub return_type: String,
+    /// `cParamsOpt`: trailing optional VARIANT parameters, or -1 for a vararg method
+    pub optional_count: i16,
+}
+
+/// One parameter of a [`FuncInfo`].
+#[cfg(windows)]
+#[derive(Debug, Clone)]
+pub struct ParamInfo {
+    pub type_name: String,
+    /// Raw `PARAMFLAG_*` bits
+    pub flags: u16,
+    /// The default value rendered for display, when the parameter has one
+    pub default: Option<String>,
+}
+
+/// A decoded `VARDESC`.
+#[cfg(windows)]
+#[derive(Debug, Clone)]
+pub struct VarInfo {
+    pub memid: i32,
+    pub type_name: String,
+    /// Raw `VARFLAGS` bits
+    pub flags: u16,
+    /// The inline value of an enum member or module constant
+    pub constant: Option<i64>,
+}
+
+/// The operations [`parse_type_info`] needs from an `ITypeInfo`.
+///
+/// Implemented for the real `ITypeInfo`; tests supply a mock so member parsing can run without COM.
+#[cfg(windows)]
+pub trait TypeInfoSource {
+    fn type_attr(&self) -> Result<TypeAttrInfo>;
+    /// Describes the function at `index`, `0..func_count`.
+    fn func_desc(&self, index: u32) -> Result<FuncInfo>;
+    /// Describes the variable at `index`, `0..var_count`.
+    fn var_desc(&self, index: u32) -> Result<VarInfo>;
+    /// The member name followed by its parameter names, as far as the library records them,
+    /// returning at most `max_names` names.
+    fn names(&self, memid: i32, max_names: usize) -> Vec<String>;
+    /// `(name, doc string)` of a member, or of the type itself for `memid` -1.
+    fn documentation(&self, memid: i32) -> Result<(String, String)>;
+    /// `(help file, help context)` of the type itself; a zero context counts as none.
+    fn help(&self) -> (Option<String>, Option<u32>);
+    /// The interfaces a coclass lists, in declaration order; empty for other kinds.
+    fn implemented_interfaces(&self) -> Vec<ImplementedInterface>;
+}
+
+#[cfg(windows)]
+impl TypeInfoSource for ITypeInfo {
+    fn type_attr(&self) -> Result<TypeAttrInfo> {
+        let attr = ScopedTypeAttr::new(self)?;
+        Ok(TypeAttrInfo {
+            guid: attr.0.guid,
+            version: (attr.0.wMajorVerNum, attr.0.wMinorVerNum),
+            typekind: attr.0.typekind,
+            type_flags: attr.0.wTypeFlags,
+            func_count: attr.0.cFuncs,
+            var_count: attr.0.cVars,
+        })
+    }
+
+    fn func_desc(&self, index: u32) -> Result<FuncInfo> {
+        let func_desc = ScopedFuncDesc::new(self, index)?;
+        let desc = func_desc.0;
+        let params = (0..desc.cParams as usize)
+            .map(|p| unsafe {
+                let elem = *desc.lprgelemdescParam.add(p);
+                let flags = elem.Anonymous.paramdesc.wParamFlags.0;
+                let default = if flags & PARAMFLAG_FHASDEFAULT != 0 {
+                    let ex = elem.Anonymous.paramdesc.pparamdescex;
+                    if ex.is_null() { None } else { variant_to_display(&(*ex).varDefaultValue) }
+                } else {
+                    None
+                };
+                ParamInfo { type_name: typedesc_to_string(&elem.tdesc, self), flags, default }
+            })
+            .collect();
+
+        Ok(FuncInfo {
+            memid: desc.memid,
+            invkind: desc.invkind,
+            flags: desc.wFuncFlags.0,
+            params,
+            return_type: typedesc_to_string(&desc.elemdescFunc.tdesc, self),
+            optional_count: desc.cParamsOpt,
+        })
+    }
+
+    fn var_desc(&self, index: u32) -> Result<VarInfo> {
+        let var_desc = ScopedVarDesc::new(self, index)?;
+        let desc = var_desc.0;
+        // Enum members and module constants carry their value inline
+        let constant = if desc.varkind == VAR_CONST {
+            unsafe { variant_to_i64(desc.Anonymous.lpvarValue) }
+        } else {
+            None
+        };
+        Ok(VarInfo {
+            memid: desc.memid,
+            type_name: typedesc_to_string(&desc.elemdescVar.tdesc, self),
+            flags: desc.wVarFlags.0,
+            constant,
+        })
+    }
+
+    fn names(&self, memid: i32, max_names: usize) -> Vec<String> {
+        let mut names = vec![BSTR::new(); max_names];
+        let mut c_names = 0;
+        let _ = unsafe { self.GetNames(memid, &mut names, &mut c_names) };
+        names.truncate(c_names as usize);
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn documentation(&self, memid: i32) -> Result<(String, String)> {
+        let mut name = BSTR::new();
+        let mut doc_string = BSTR::new();
+        unsafe {
+            self.GetDocumentation(
+                memid, 
+                Some(&mut name as *mut _), 
+                Some(&mut doc_string as *mut _), 
+                std::ptr::null_mut(), 
+                None
+            ).map_err(|e| InspectError::Parsing(format!("GetDocumentation failed: {}", e.message())))?;
+        }
+        Ok((name.to_string(), doc_string.to_string()))
+    }
+
+    fn help(&self) -> (Option<String>, Option<u32>) {
+        let mut context = 0u32;
+        let mut help_file = BSTR::new();
+        let result = unsafe { self.GetDocumentation(-1, None, None, &mut context, Some(&mut help_file as *mut _)) };
+        if result.is_err() {
+            return (None, None);
         }
+        let help_file = Some(help_file.to_string()).filter(|file| !file.is_empty());
+        (help_file, Some(context).filter(|&context| context != 0))
+    }
+
+    fn implemented_interfaces(&self) -> Vec<ImplementedInterface> {
+        let Ok(attr) = ScopedTypeAttr::new(self) else {
+            return Vec::new();
+        };
+        if attr.0.typekind != TKIND_COCLASS {
+            return Vec::new();
+        }
+        (0..attr.0.cImplTypes as u32)
+            .filter_map(|index| unsafe {
+                let interface = self.GetRefTypeInfo(self.GetRefTypeOfImplType(index).ok()?).ok()?;
+                let flags = self.GetImplTypeFlags(index).unwrap_or_default();
+                let (name, _) = interface.documentation(-1).ok()?;
+                let guid = interface.type_attr().map(|attr| format!("{{{:?}}}", attr.guid)).unwrap_or_default();
+                Some(ImplementedInterface {
+                    name,
+                    guid,
+                    source: flags.0 & IMPLTYPEFLAG_FSOURCE.0 != 0,
+                    default: flags.0 & IMPLTYPEFLAG_FDEFAULT.0 != 0,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(windows)]
+fn parse_type_info(type_info: &impl TypeInfoSource, default_name: &str) -> Result<TypeDetails> {
+    let mut members = Vec::new();
+    let attr = type_info.type_attr()?;
+    let (name, doc) = type_info.documentation(-1).unwrap_or((default_name.to_string(), String::new()));
+    let (help_file, help_context) = type_info.help();
+
+    // Iterate Functions
+    for i in 0..attr.func_count {
+        let Ok(func) = type_info.func_desc(i as u32) else {
+            continue;
+        };
+        let (func_name, func_doc) = type_info.documentation(func.memid).unwrap_or(("Unknown".to_string(), String::new()));
+        // The first name is the member itself; parameter names follow
+        let param_count = func.params.len();
+        let names = type_info.names(func.memid, param_count + 1);
 
-        // Iterate Variables
-        for i in 0..attr.0.cVars {
-            if let Ok(var_desc) = ScopedVarDesc::new(type_info, i as u32) {
-                let desc = *var_desc.0;
-                let (var_name, _) = get_documentation(type_info, desc.memid).unwrap_or(("Unknown".to_string(), String::new()));
-                let var_type = vartype_to_string(desc.elemdescVar.tdesc.vt.0);
-                
-                members.push(Member::Property {
-                    name: var_name,
-                    value_type: var_type,
-                    access: AccessMode::ReadWrite,
+        // cParamsOpt counts trailing optional VARIANTs; -1 marks a vararg method
+        // whose last parameter is a SAFEARRAY of the remaining arguments
+        let is_vararg = func.optional_count == -1;
+        let first_optional = param_count.saturating_sub(func.optional_count.max(0) as usize);
+
+        let params: Vec<Param> = func.params.iter().enumerate().map(|(p, param)| Param {
+            name: names.get(p + 1).cloned().unwrap_or_else(|| format!("arg{}", p)),
+            ty: param.type_name.clone(),
+            flags: param.flags,
+            default: param.default.clone(),
+            optional: param.flags & PARAMFLAG_FOPT != 0 || p >= first_optional,
+            variadic: is_vararg && p + 1 == param_count,
+        }).collect();
+
+        let return_type = func.return_type;
+        let hidden = func.flags & (FUNCFLAG_FHIDDEN.0 | FUNCFLAG_FRESTRICTED.0) != 0;
+
+        match func.invkind {
+            INVOKE_FUNC => {
+                members.push(Member::Method {
+                    name: func_name,
+                    params,
+                    return_type,
+                    dispid: Some(func.memid),
+                    hidden,
+                    doc: func_doc,
                 });
-            }
+            },
+            INVOKE_PROPERTYGET | INVOKE_PROPERTYPUT | INVOKE_PROPERTYPUTREF => {
+                members.push(property_accessor(func.invkind, func_name, params, return_type, func.memid, hidden, func_doc));
+            },
+            _ => {}
+        }
+    }
+
+    // Iterate Variables
+    for i in 0..attr.var_count {
+        let Ok(var) = type_info.var_desc(i as u32) else {
+            continue;
+        };
+        let (var_name, var_doc) = type_info.documentation(var.memid).unwrap_or(("Unknown".to_string(), String::new()));
+        let hidden = var.flags & (VARFLAG_FHIDDEN.0 | VARFLAG_FRESTRICTED.0) != 0;
+
+        if let Some(value) = var.constant {
+            members.push(Member::Constant {
+                name: var_name,
+                value,
+                value_type: var.type_name,
+                hidden,
+                doc: var_doc,
+            });
+        } else {
+            members.push(Member::Property {
+                name: var_name,
+                value_type: var.type_name,
+                access: AccessMode::ReadWrite,
+                index: Vec::new(),
+                dispid: Some(var.memid),
+                hidden,
+                doc: var_doc,
+            });
         }
     }
 
     Ok(TypeDetails {
         name,
         description: doc,
-        members,
+        guid: format!("{{{:?}}}", attr.guid),
+        version: attr.version,
+        typekind: typekind_to_string(attr.typekind).to_string(),
+        interface_kind: InterfaceKind::from_type_attr(attr.typekind, attr.type_flags),
+        members: coalesce_properties(members),
+        interfaces: type_info.implemented_interfaces(),
+        help_file,
+        help_context,
     })
 }
 
-fn get_documentation(type_info: &ITypeInfo, memid: i32) -> Result<(String, String)> {
-    let mut name = BSTR::new();
-    let mut doc_string = BSTR::new();
+/// Names the `PARAMFLAG_*` bits, e.g. `["out", "retval"]`.
+fn param_attributes(flags: u16) -> Vec<&'static str> {
+    let mut attributes = Vec::new();
+    if flags & PARAMFLAG_FIN != 0 { attributes.push("in"); }
+    if flags & PARAMFLAG_FOUT != 0 { attributes.push("out"); }
+    if flags & PARAMFLAG_FRETVAL != 0 { attributes.push("retval"); }
+    if flags & PARAMFLAG_FOPT != 0 { attributes.push("optional"); }
+    attributes
+}
+
+/// Names a `TYPEKIND` the way the type library browser in Visual Studio does.
+#[cfg(windows)]
+fn typekind_to_string(kind: TYPEKIND) -> &'static str {
+    match kind {
+        TKIND_COCLASS => "Coclass",
+        TKIND_INTERFACE => "Interface",
+        TKIND_DISPATCH => "Dispatch",
+        TKIND_ENUM => "Enum",
+        TKIND_RECORD => "Record",
+        TKIND_MODULE => "Module",
+        TKIND_ALIAS => "Alias",
+        TKIND_UNION => "Union",
+        _ => "Unknown",
+    }
+}
+
+/// Reads an integral constant out of a `VARIANT`.
+///
+/// # Safety
+/// `ptr` must be null or point to a `VARIANT` owned by a live `VARDESC`.
+/// Only the union field matching the variant's `vt` tag is read.
+#[cfg(windows)]
+unsafe fn variant_to_i64(ptr: *const VARIANT) -> Option<i64> {
+    if ptr.is_null() {
+        return None;
+    }
+
     unsafe {
-        type_info.GetDocumentation(
-            memid, 
-            Some(&mut name as *mut _), 
-            Some(&mut doc_string as *mut _), 
-            std::ptr::null_mut(), 
-            None
-        ).map_err(|e| InspectError::Parsing(format!("GetDocumentation failed: {}", e.message())))?;
+        let inner = &(*ptr).Anonymous.Anonymous;
+        let val = &inner.Anonymous;
+        match inner.vt {
+            VT_I1 => Some(val.cVal as i64),
+            VT_UI1 => Some(val.bVal as i64),
+            VT_I2 => Some(val.iVal as i64),
+            VT_UI2 => Some(val.uiVal as i64),
+            VT_I4 | VT_INT | VT_ERROR | VT_HRESULT => Some(val.lVal as i64),
+            VT_UI4 | VT_UINT => Some(val.ulVal as i64),
+            VT_BOOL => Some(val.boolVal.0 as i64),
+            _ => None,
+        }
+    }
+}
+
+/// Renders a parameter default `VARIANT` for display: integers, floats, booleans and quoted strings.
+///
+/// # Safety
+/// `ptr` must point to a `VARIANT` owned by a live `PARAMDESCEX`.
+#[cfg(windows)]
+unsafe fn variant_to_display(ptr: *const VARIANT) -> Option<String> {
+    unsafe {
+        let inner = &(*ptr).Anonymous.Anonymous;
+        let val = &inner.Anonymous;
+        match inner.vt {
+            VT_BOOL => Some(if val.boolVal.0 != 0 { "True" } else { "False" }.to_string()),
+            VT_BSTR => Some(format!("{:?}", val.bstrVal.to_string())),
+            VT_R4 => Some(val.fltVal.to_string()),
+            VT_R8 => Some(val.dblVal.to_string()),
+            _ => variant_to_i64(ptr).map(|v| v.to_string()),
+        }
+    }
+}
+
+/// Builds the property member for one accessor function; [`coalesce_properties`] later merges accessors of the same property.
+///
+/// A getter's type is its return type. A setter assigns its last parameter, so that parameter's type is
+/// the property's, and any parameters before it index the property.
+#[cfg(windows)]
+fn property_accessor(
+    invkind: INVOKEKIND,
+    name: String,
+    mut params: Vec<Param>,
+    return_type: String,
+    memid: i32,
+    hidden: bool,
+    doc: String,
+) -> Member {
+    let (access, value_type) = if invkind == INVOKE_PROPERTYGET {
+        (AccessMode::Read, return_type)
+    } else {
+        let value_type = params.pop().map_or_else(|| "Variant".to_string(), |value| value.ty);
+        (AccessMode::Write, value_type)
+    };
+    Member::Property { name, value_type, access, index: params, dispid: Some(memid), hidden, doc }
+}
+
+/// Coalesces property accessors (get/put/putref) into a single member.
+///
+/// Accessors belong together when they share a DISPID, or a name when either lacks one. Access modes are
+/// combined, so a getter plus a setter becomes `ReadWrite`, and the getter's type and index win over a
+/// setter's. Each property keeps the position of its first accessor, and the rest of the member order is unchanged.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn coalesce_properties(members: Vec<Member>) -> Vec<Member> {
+    let same_property = |a: &Member, b: &Member| match (a.dispid(), b.dispid()) {
+        (Some(x), Some(y)) => x == y,
+        _ => a.name() == b.name(),
+    };
+
+    let mut merged: Vec<Member> = Vec::with_capacity(members.len());
+    for member in members {
+        if let Member::Property { value_type, access, index, hidden, doc, .. } = &member
+            && let Some(Member::Property {
+                value_type: existing_type,
+                access: existing_access,
+                index: existing_index,
+                hidden: existing_hidden,
+                doc: existing_doc,
+                ..
+            }) = merged.iter_mut().find(|m| matches!(m, Member::Property { .. }) && same_property(m, &member))
+        {
+            if *access == AccessMode::Read {
+                existing_type.clone_from(value_type);
+                existing_index.clone_from(index);
+            } else if existing_index.is_empty() {
+                existing_index.clone_from(index);
+            }
+            if *existing_access != *access {
+                *existing_access = AccessMode::ReadWrite;
+            }
+            // Only hide the merged property when every accessor is hidden
+            *existing_hidden &= *hidden;
+            if existing_doc.is_empty() {
+                *existing_doc = doc.clone();
+            }
+            continue;
+        }
+        merged.push(member);
+    }
+    merged
+}
+
+/// Renders a `TYPEDESC`, following pointer and array element chains and naming user-defined types.
+///
+/// Each `VT_PTR` level adds a `*` (`Long**`); `VT_SAFEARRAY` and `VT_CARRAY` append `[]` to their element type.
+#[cfg(windows)]
+fn typedesc_to_string(desc: &TYPEDESC, type_info: &ITypeInfo) -> String {
+    unsafe {
+        match desc.vt {
+            VT_PTR if !desc.Anonymous.lptdesc.is_null() => {
+                format!("{}*", typedesc_to_string(&*desc.Anonymous.lptdesc, type_info))
+            }
+            VT_SAFEARRAY if !desc.Anonymous.lptdesc.is_null() => {
+                format!("{}[]", typedesc_to_string(&*desc.Anonymous.lptdesc, type_info))
+            }
+            VT_CARRAY if !desc.Anonymous.lpadesc.is_null() => {
+                format!("{}[]", typedesc_to_string(&(*desc.Anonymous.lpadesc).tdescElem, type_info))
+            }
+            VT_USERDEFINED => type_info
+                .GetRefTypeInfo(desc.Anonymous.hreftype)
+                .ok()
+                .and_then(|ref_info| ref_info.documentation(-1).ok())
+                .map(|(name, _)| name)
+                .unwrap_or_else(|| "UserDefined".to_string()),
+            vt => vartype_to_string(vt.0),
+        }
     }
-    Ok((name.to_string(), doc_string.to_string()))
 }
 
+#[cfg(windows)]
 pub fn vartype_to_string(vt: u16) -> String {
     let base_type = vt & 0x0FFF; 
     let is_array = (vt & 0x2000) != 0;
@@ -312,6 +2358,15 @@ pub fn vartype_to_string(vt: u16) -> String {
         VT_USERDEFINED => "UserDefined",
         VT_LPSTR => "String (LPSTR)",
         VT_LPWSTR => "String (LPWSTR)",
+        VT_DECIMAL => "Decimal",
+        VT_I8 => "Int64",
+        VT_UI8 => "UInt64",
+        VT_FILETIME => "FileTime",
+        VT_RECORD => "Record",
+        VT_CLSID => "GUID",
+        VT_STREAM => "IStream",
+        VT_STORAGE => "IStorage",
+        VT_BLOB => "Blob",
         _ => "Unknown",
     };
 
@@ -323,7 +2378,9 @@ pub fn vartype_to_string(vt: u16) -> String {
 
 // --- RAII Wrappers ---
 
+#[cfg(windows)]
 struct ScopedTypeAttr<'a>(&'a TYPEATTR, &'a ITypeInfo);
+#[cfg(windows)]
 impl<'a> ScopedTypeAttr<'a> {
     fn new(info: &'a ITypeInfo) -> Result<Self> {
         unsafe {
@@ -332,13 +2389,16 @@ impl<'a> ScopedTypeAttr<'a> {
         }
     }
 }
+#[cfg(windows)]
 impl<'a> Drop for ScopedTypeAttr<'a> {
     fn drop(&mut self) {
         unsafe { self.1.ReleaseTypeAttr(self.0 as *const _ as *mut _) };
     }
 }
 
+#[cfg(windows)]
 struct ScopedFuncDesc<'a>(&'a FUNCDESC, &'a ITypeInfo);
+#[cfg(windows)]
 impl<'a> ScopedFuncDesc<'a> {
     fn new(info: &'a ITypeInfo, index: u32) -> Result<Self> {
         unsafe {
@@ -347,13 +2407,16 @@ impl<'a> ScopedFuncDesc<'a> {
         }
     }
 }
+#[cfg(windows)]
 impl<'a> Drop for ScopedFuncDesc<'a> {
     fn drop(&mut self) {
         unsafe { self.1.ReleaseFuncDesc(self.0 as *const _ as *mut _) };
     }
 }
 
+#[cfg(windows)]
 struct ScopedVarDesc<'a>(&'a VARDESC, &'a ITypeInfo);
+#[cfg(windows)]
 impl<'a> ScopedVarDesc<'a> {
     fn new(info: &'a ITypeInfo, index: u32) -> Result<Self> {
         unsafe {
@@ -362,8 +2425,716 @@ impl<'a> ScopedVarDesc<'a> {
         }
     }
 }
+#[cfg(windows)]
 impl<'a> Drop for ScopedVarDesc<'a> {
     fn drop(&mut self) {
         unsafe { self.1.ReleaseVarDesc(self.0 as *const _ as *mut _) };
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(members: Vec<Member>) -> TypeDetails {
+        TypeDetails {
+            name: "Widget".to_string(),
+            description: "A widget".to_string(),
+            guid: "{00000000-0000-0000-0000-000000000001}".to_string(),
+            version: (1, 2),
+            typekind: "Dispatch".to_string(),
+            interface_kind: Some(InterfaceKind::Dual),
+            members,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_text_includes_member_docs() {
+        let text = details(vec![Member::method("Open", vec![Param::new("path", "String", PARAMFLAG_FIN)], "Void").with_doc("Opens a workbook.")])
+        .to_text();
+
+        assert!(text.contains("GUID: {00000000-0000-0000-0000-000000000001}\nKind: Dispatch [dual] (version 1.2)\n"));
+        assert!(text.contains("Method Open([in] path: String) -> Void\n    Opens a workbook.\n"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_interface_kind_from_type_attr() {
+        let dual = TYPEFLAG_FDUAL.0 as u16;
+        assert_eq!(InterfaceKind::from_type_attr(TKIND_DISPATCH, dual), Some(InterfaceKind::Dual));
+        assert_eq!(InterfaceKind::from_type_attr(TKIND_INTERFACE, dual), Some(InterfaceKind::Dual));
+        assert_eq!(InterfaceKind::from_type_attr(TKIND_DISPATCH, 0), Some(InterfaceKind::Dispatch));
+        assert_eq!(InterfaceKind::from_type_attr(TKIND_INTERFACE, 0), Some(InterfaceKind::Vtable));
+        assert_eq!(InterfaceKind::from_type_attr(TKIND_COCLASS, 0), None);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_check_coinit_only_counts_successful_initialization() {
+        use windows::Win32::Foundation::{E_OUTOFMEMORY, S_FALSE, S_OK};
+
+        assert!(check_coinit(S_OK, Apartment::Mta).unwrap());
+        assert!(check_coinit(S_FALSE, Apartment::Sta).unwrap());
+        let changed = check_coinit(RPC_E_CHANGED_MODE, Apartment::Sta).unwrap_err().to_string();
+        assert!(changed.contains("single-threaded apartment (STA)"), "{}", changed);
+        assert!(check_coinit(E_OUTOFMEMORY, Apartment::Mta).is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_guid_from_str_validates_clsids() {
+        let expected = GUID::from_u128(0x00024500_0000_0000_C000_000000000046);
+        assert_eq!(guid_from_str("{00024500-0000-0000-C000-000000000046}").unwrap(), expected);
+        assert_eq!(guid_from_str("00024500-0000-0000-C000-000000000046").unwrap(), expected);
+        assert_eq!(guid_from_str(" {00024500-0000-0000-c000-000000000046} ").unwrap(), expected);
+
+        for malformed in ["", "{}", "Excel.Application", "{00024500-0000-0000-C000-000000000046", "00024500-0000-0000-C000-00000000004G", "{0002450-00000-0000-C000-000000000046}"] {
+            let err = guid_from_str(malformed).unwrap_err();
+            assert!(format!("{}", err).contains("invalid CLSID"), "{:?} was accepted", malformed);
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_retry_transient_retries_only_launch_failures() {
+        let mut calls = 0;
+        let result = retry_transient(2, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 { Err(windows::core::Error::from_hresult(CO_E_SERVER_EXEC_FAILURE)) } else { Ok(calls) }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: windows::core::Result<()> = retry_transient(2, Duration::ZERO, || {
+            calls += 1;
+            Err(windows::core::Error::from_hresult(RPC_E_CALL_REJECTED))
+        });
+        assert_eq!(result.unwrap_err().code(), RPC_E_CALL_REJECTED);
+        assert_eq!(calls, 3);
+
+        // Permanent failures are reported at once
+        let mut calls = 0;
+        let result: windows::core::Result<()> = retry_transient(2, Duration::ZERO, || {
+            calls += 1;
+            Err(windows::core::Error::from_hresult(E_ACCESSDENIED))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_run_with_timeout_gives_up_on_slow_jobs() {
+        let options = InspectOptions { instantiate_timeout: Duration::from_millis(20), ..Default::default() };
+
+        let err = run_with_timeout(&options, || {
+            thread::sleep(Duration::from_millis(500));
+            Ok(())
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<InspectError>(),
+            Some(InspectError::Instantiation { hresult: None, .. })
+        ));
+
+        assert_eq!(run_with_timeout(&options, || Ok(7)).unwrap(), 7);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_vartype_mapping() {
+        assert_eq!(vartype_to_string(VT_BSTR.0), "String");
+        assert_eq!(vartype_to_string(VT_I4.0 | 0x4000), "Long&");
+        assert_eq!(vartype_to_string(VT_VARIANT.0 | 0x2000), "Variant[]");
+        assert_eq!(vartype_to_string(VT_DECIMAL.0), "Decimal");
+        assert_eq!(vartype_to_string(VT_I8.0), "Int64");
+        assert_eq!(vartype_to_string(VT_UI8.0), "UInt64");
+        assert_eq!(vartype_to_string(VT_FILETIME.0), "FileTime");
+        assert_eq!(vartype_to_string(VT_RECORD.0), "Record");
+        assert_eq!(vartype_to_string(VT_CLSID.0), "GUID");
+        assert_eq!(vartype_to_string(VT_STREAM.0), "IStream");
+        assert_eq!(vartype_to_string(VT_STORAGE.0), "IStorage");
+        assert_eq!(vartype_to_string(VT_BLOB.0), "Blob");
+        assert_eq!(vartype_to_string(0x0FFF), "Unknown");
+    }
+
+    #[test]
+    fn test_pointer_and_array_types_map_to_idl_and_rust() {
+        assert_eq!(idl_type("String**"), "BSTR**");
+        assert_eq!(idl_type("Long[]*"), "SAFEARRAY(long)*");
+        assert_eq!(idl_type("Range*"), "Range*");
+        assert_eq!(rust_type("Long**").as_deref(), Some("*mut *mut i32"));
+        assert_eq!(rust_type("String[]").as_deref(), Some("*mut SAFEARRAY"));
+        assert_eq!(rust_type("Range*"), None);
+    }
+
+    #[test]
+    fn test_coalesce_properties_merges_accessors_in_order() {
+        let property = |access, hidden| Member::property("Visible", "Boolean", access).with_hidden(hidden);
+        let method = Member::method("Quit", Vec::new(), "Void");
+
+        let merged = coalesce_properties(vec![
+            property(AccessMode::Read, false),
+            method.clone(),
+            property(AccessMode::Write, true),
+        ]);
+
+        assert_eq!(merged, vec![property(AccessMode::ReadWrite, false), method]);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_coalesce_properties_prefers_the_getter_type_and_matches_by_dispid() {
+        let accessor = |invkind, params, return_type: &str| {
+            property_accessor(invkind, "Font".to_string(), params, return_type.to_string(), 3, false, String::new())
+        };
+        let setter = accessor(INVOKE_PROPERTYPUTREF, vec![Param::new("value", "IDispatch*", PARAMFLAG_FIN)], "Void");
+        let getter = accessor(INVOKE_PROPERTYGET, Vec::new(), "Font*");
+        let other = Member::property("Font", "Long", AccessMode::Read).with_dispid(4);
+
+        let merged = coalesce_properties(vec![setter, getter, other.clone()]);
+
+        assert_eq!(merged, vec![Member::property("Font", "Font*", AccessMode::ReadWrite).with_dispid(3), other]);
+    }
+
+    #[test]
+    fn test_is_invocable() {
+        let method = |params: Vec<Param>| Member::method("M", params, "HResult");
+        let property = |access| Member::property("P", "String", access);
+
+        assert!(method(Vec::new()).is_invocable());
+        assert!(method(vec![Param::new("r", "String&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL)]).is_invocable());
+        assert!(!method(vec![Param::new("x", "Long", PARAMFLAG_FIN)]).is_invocable());
+        assert!(property(AccessMode::Read).is_invocable());
+        assert!(property(AccessMode::ReadWrite).is_invocable());
+        assert!(!property(AccessMode::Write).is_invocable());
+        assert!(!property(AccessMode::Read).with_index(vec![Param::new("index", "Variant", PARAMFLAG_FIN)]).is_invocable());
+        assert!(!Member::Constant { name: "C".to_string(), value: 1, value_type: "Long".to_string(), hidden: false, doc: String::new() }.is_invocable());
+    }
+
+    #[test]
+    fn test_param_display_marks_optional_and_variadic() {
+        assert_eq!(Param::new("x", "Long", PARAMFLAG_FIN).display(), "[in] x: Long");
+        assert_eq!(
+            Param::new("x", "Variant", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(None).display(),
+            "[in, optional] x?: Variant"
+        );
+        assert_eq!(Param::new("rest", "Variant[]", 0).variadic().display(), "...rest: Variant[]");
+        assert_eq!(Param::new("n", "Long", 0).optional(Some("5")).display(), "n?: Long = 5");
+    }
+
+    #[test]
+    fn test_format_signature() {
+        assert_eq!(format_signature(&[], "Long"), "() -> Long");
+        let params = [
+            Param::new("x", "String", PARAMFLAG_FIN),
+            Param::new("count", "Long&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+        ];
+        assert_eq!(format_signature(&params, "HResult"), "([in] x: String, [out, retval] count: Long&) -> HResult");
+    }
+
+    #[test]
+    fn test_param_attributes() {
+        assert!(param_attributes(0).is_empty());
+        assert_eq!(param_attributes(PARAMFLAG_FIN), vec!["in"]);
+        assert_eq!(param_attributes(PARAMFLAG_FOUT | PARAMFLAG_FRETVAL), vec!["out", "retval"]);
+        assert_eq!(param_attributes(PARAMFLAG_FIN | PARAMFLAG_FOPT), vec!["in", "optional"]);
+    }
+
+    #[test]
+    fn test_to_idl() {
+        let idl = to_idl(&details(vec![
+            Member::method("Bar", vec![Param::new("x", "String", 0)], "Long"),
+            Member::method("Fetch", vec![
+                Param::new("key", "String", PARAMFLAG_FIN),
+                Param::new("limit", "Long", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("10")),
+                Param::new("result", "Variant&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+            ], "HResult"),
+            Member::property("Size", "Double[]", AccessMode::ReadWrite),
+            Member::Constant {
+                name: "MaxSize".to_string(),
+                value: 10,
+                value_type: "Long".to_string(),
+                hidden: false,
+                doc: String::new(),
+            },
+        ]));
+
+        assert!(idl.contains("helpstring(\"A widget\")"));
+        assert!(idl.contains("interface IWidget : IDispatch {"));
+        assert!(idl.contains("    HRESULT Bar([in] BSTR x, [out, retval] long* retVal);\n"));
+        assert!(idl.contains(
+            "    HRESULT Fetch([in] BSTR key, [in, optional, defaultvalue(10)] long limit, [out, retval] VARIANT* result);\n"
+        ));
+        assert!(idl.contains("    [propget] HRESULT Size([out, retval] SAFEARRAY(double)* pVal);\n"));
+        assert!(idl.contains("    [propput] HRESULT Size([in] SAFEARRAY(double) newVal);\n"));
+        assert!(idl.contains("module WidgetConstants {\n    const long MaxSize = 10;\n};"));
+        assert!(idl.contains("coclass Widget {\n    [default] interface IWidget;\n};"));
+    }
+
+    #[test]
+    fn test_to_idl_renders_a_pure_dispinterface() {
+        let idl = to_idl(&TypeDetails {
+            interface_kind: Some(InterfaceKind::Dispatch),
+            ..details(vec![
+                Member::property("Visible", "Boolean", AccessMode::ReadWrite).with_dispid(1),
+                Member::property("Count", "Long", AccessMode::Read).with_dispid(2),
+                Member::property("Item", "Variant", AccessMode::Read)
+                    .with_index(vec![Param::new("index", "Long", PARAMFLAG_FIN)])
+                    .with_dispid(0),
+                Member::method("Find", vec![
+                    Param::new("text", "String", PARAMFLAG_FIN),
+                    Param::new("found", "Boolean&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+                ], "HResult").with_dispid(3),
+                Member::method("Quit", Vec::new(), "Void").with_dispid(4),
+            ])
+        });
+
+        assert!(!idl.contains("object"));
+        assert!(idl.contains(concat!(
+            "dispinterface IWidget {\n",
+            "properties:\n",
+            "    [id(1)] VARIANT_BOOL Visible;\n",
+            "    [id(2), readonly] long Count;\n",
+            "methods:\n",
+            "    [id(0), propget] VARIANT Item([in] long index);\n",
+            "    [id(3)] VARIANT_BOOL Find([in] BSTR text);\n",
+            "    [id(4)] void Quit();\n",
+            "};\n",
+        )));        assert!(idl.contains("coclass Widget {\n    [default] dispinterface IWidget;\n};"));
+    }
+
+    #[test]
+    fn test_to_rust_bindings() {
+        let bindings = to_rust_bindings(&details(vec![
+            Member::method("Bar", vec![
+                Param::new("type", "String", 0),
+                Param::new("flag", "Boolean", 0),
+            ], "Long"),
+            Member::method("Fetch", vec![
+                Param::new("key", "String", PARAMFLAG_FIN),
+                Param::new("limit", "Long", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("10")),
+                Param::new("result", "Variant&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+            ], "HResult"),
+            Member::property("Owner", "UserDefined", AccessMode::ReadWrite),
+            Member::Constant {
+                name: "MaxSize".to_string(),
+                value: 10,
+                value_type: "Long".to_string(),
+                hidden: false,
+                doc: String::new(),
+            },
+        ]));
+
+        assert!(bindings.contains("pub const MaxSize: i32 = 10;\n"));
+        assert!(bindings.contains("pub trait IWidget {\n"));
+        assert!(bindings.contains("    fn Bar(&self, r#type: BSTR, flag: VARIANT_BOOL) -> Result<i32>;\n"));
+        assert!(bindings.contains("    fn Fetch(&self, key: BSTR, limit: i32 /* = 10 */) -> Result<VARIANT>;\n"));
+        assert!(bindings.contains("    fn Owner(&self) -> Result<VARIANT /* unmapped: UserDefined */>;\n"));
+        assert!(bindings.contains("    fn SetOwner(&self, value: VARIANT /* unmapped: UserDefined */) -> Result<()>;\n"));
+    }
+
+    /// A type info built from decoded descriptions, keyed by member id for names and docs.
+    #[cfg(windows)]
+    struct MockTypeInfo {
+        attr: TypeAttrInfo,
+        funcs: Vec<FuncInfo>,
+        vars: Vec<VarInfo>,
+        /// memid to the member name followed by its parameter names
+        names: Vec<(i32, Vec<&'static str>)>,
+    }
+
+    #[cfg(windows)]
+    impl TypeInfoSource for MockTypeInfo {
+        fn type_attr(&self) -> Result<TypeAttrInfo> {
+            Ok(self.attr.clone())
+        }
+
+        fn func_desc(&self, index: u32) -> Result<FuncInfo> {
+            self.funcs.get(index as usize).cloned().ok_or_else(|| InspectError::Parsing("no such function".to_string()).into())
+        }
+
+        fn var_desc(&self, index: u32) -> Result<VarInfo> {
+            self.vars.get(index as usize).cloned().ok_or_else(|| InspectError::Parsing("no such variable".to_string()).into())
+        }
+
+        fn names(&self, memid: i32, max_names: usize) -> Vec<String> {
+            // Like GetNames, never fill more than the caller's buffer
+            self.names
+                .iter()
+                .find(|(id, _)| *id == memid)
+                .map(|(_, names)| names.iter().take(max_names).map(|name| name.to_string()).collect())
+                .unwrap_or_default()
+        }
+
+        fn documentation(&self, memid: i32) -> Result<(String, String)> {
+            if memid == -1 {
+                return Ok(("IWidget".to_string(), "A widget".to_string()));
+            }
+            let name = self.names(memid, 1).into_iter().next().ok_or_else(|| InspectError::Parsing("unknown memid".to_string()))?;
+            Ok((name, format!("Docs for member {}", memid)))
+        }
+
+        fn help(&self) -> (Option<String>, Option<u32>) {
+            (Some("widget.chm".to_string()), Some(42))
+        }
+
+        fn implemented_interfaces(&self) -> Vec<ImplementedInterface> {
+            Vec::new()
+        }
+    }
+
+    #[cfg(windows)]
+    fn mock_attr(func_count: u16, var_count: u16) -> TypeAttrInfo {
+        TypeAttrInfo {
+            guid: GUID::from_u128(0x00000000_0000_0000_0000_000000000001),
+            version: (1, 2),
+            typekind: TKIND_DISPATCH,
+            type_flags: TYPEFLAG_FDUAL.0 as u16,
+            func_count,
+            var_count,
+        }
+    }
+
+    #[cfg(windows)]
+    fn func(memid: i32, invkind: INVOKEKIND, flags: u16, params: Vec<ParamInfo>, return_type: &str) -> FuncInfo {
+        FuncInfo { memid, invkind, flags, params, return_type: return_type.to_string(), optional_count: 0 }
+    }
+
+    #[cfg(windows)]
+    fn param(type_name: &str, flags: u16, default: Option<&str>) -> ParamInfo {
+        ParamInfo { type_name: type_name.to_string(), flags, default: default.map(str::to_string) }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_parse_type_info_formats_signatures() {
+        let mut open = func(
+            1,
+            INVOKE_FUNC,
+            0,
+            vec![
+                param("String", PARAMFLAG_FIN, None),
+                param("Boolean", PARAMFLAG_FIN | PARAMFLAG_FOPT, Some("False")),
+                param("Variant", 0, None),
+            ],
+            "Void",
+        );
+        open.optional_count = 1;
+        let mut log = func(2, INVOKE_FUNC, 0, vec![param("Variant[]", PARAMFLAG_FIN, None)], "Void");
+        log.optional_count = -1;
+        let info = MockTypeInfo {
+            attr: mock_attr(2, 0),
+            funcs: vec![open, log],
+            vars: vec![],
+            // The third parameter of Open has no recorded name
+            names: vec![(1, vec!["Open", "path", "readOnly"]), (2, vec!["Log", "values"])],
+        };
+
+        let details = parse_type_info(&info, "Fallback").unwrap();
+        assert_eq!(details.name, "IWidget");
+        assert_eq!(details.description, "A widget");
+        assert_eq!(details.guid, "{00000000-0000-0000-0000-000000000001}");
+        assert_eq!(details.typekind, "Dispatch");
+        assert_eq!(details.interface_kind, Some(InterfaceKind::Dual));
+        assert_eq!(details.help_file.as_deref(), Some("widget.chm"));
+        assert_eq!(details.help_context, Some(42));
+        assert!(details.to_text().contains("Help: widget.chm (context 42)\n"));
+        let open = Member::method("Open", vec![
+            Param::new("path", "String", PARAMFLAG_FIN),
+            Param::new("readOnly", "Boolean", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("False")),
+            Param::new("arg2", "Variant", 0).optional(None),
+        ], "Void");
+        assert_eq!(details.members[0], open.with_dispid(1).with_doc("Docs for member 1"));
+        assert!(matches!(&details.members[1], Member::Method { params, return_type, .. }
+            if format_signature(params, return_type) == "([in] ...values: Variant[]) -> Void"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_parse_type_info_names_every_parameter_of_long_methods() {
+        const NAMES: [&str; 13] = ["Print", "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"];
+        let params = (0..12).map(|_| param("Long", PARAMFLAG_FIN, None)).collect();
+        let info = MockTypeInfo {
+            attr: mock_attr(1, 0),
+            funcs: vec![func(1, INVOKE_FUNC, 0, params, "Void")],
+            vars: vec![],
+            names: vec![(1, NAMES.to_vec())],
+        };
+
+        let details = parse_type_info(&info, "Fallback").unwrap();
+        let Member::Method { params, return_type, .. } = &details.members[0] else {
+            panic!("expected a method");
+        };
+        let signature = format_signature(params, return_type);
+        assert!(signature.starts_with("([in] a: Long, "));
+        assert!(signature.ends_with("[in] j: Long, [in] k: Long, [in] l: Long) -> Void"));
+        assert!(!signature.contains("arg"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_parse_type_info_keeps_property_index_parameters() {
+        let index = || param("Variant", PARAMFLAG_FIN, None);
+        let info = MockTypeInfo {
+            attr: mock_attr(2, 0),
+            funcs: vec![
+                func(1, INVOKE_PROPERTYGET, 0, vec![index()], "IDispatch*"),
+                func(1, INVOKE_PROPERTYPUT, 0, vec![index(), param("IDispatch*", PARAMFLAG_FIN, None)], "Void"),
+            ],
+            vars: vec![],
+            names: vec![(1, vec!["Item", "Index"])],
+        };
+
+        let details = parse_type_info(&info, "Fallback").unwrap();
+        let item = Member::property("Item", "IDispatch*", AccessMode::ReadWrite)
+            .with_index(vec![Param::new("Index", "Variant", PARAMFLAG_FIN)])
+            .with_dispid(1)
+            .with_doc("Docs for member 1");
+        assert_eq!(details.members, vec![item]);
+        assert_eq!(details.members[0].summary(), "Property Item([in] Index: Variant): IDispatch* [Read/Write]");
+        assert!(!details.members[0].is_invocable());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_parse_type_info_merges_accessors_and_hidden_flags() {
+        let hidden = FUNCFLAG_FHIDDEN.0;
+        let info = MockTypeInfo {
+            attr: mock_attr(4, 2),
+            funcs: vec![
+                func(1, INVOKE_PROPERTYGET, 0, vec![], "Boolean"),
+                func(1, INVOKE_PROPERTYPUT, hidden, vec![param("Boolean", PARAMFLAG_FIN, None)], "Void"),
+                func(2, INVOKE_PROPERTYPUTREF, 0, vec![param("IDispatch*", PARAMFLAG_FIN, None)], "Void"),
+                func(3, INVOKE_FUNC, FUNCFLAG_FRESTRICTED.0, vec![], "Void"),
+            ],
+            vars: vec![
+                VarInfo { memid: 4, type_name: "Long".to_string(), flags: 0, constant: Some(7) },
+                VarInfo { memid: 5, type_name: "String".to_string(), flags: VARFLAG_FHIDDEN.0, constant: None },
+            ],
+            names: vec![
+                (1, vec!["Visible"]),
+                (2, vec!["Parent"]),
+                (3, vec!["Internal"]),
+                (4, vec!["xlMaximized"]),
+                (5, vec!["Tag"]),
+            ],
+        };
+
+        let details = parse_type_info(&info, "Fallback").unwrap();
+        let summary: Vec<(String, bool)> = details.members.iter().map(|m| (m.summary(), m.is_hidden())).collect();
+        assert_eq!(summary, vec![
+            // Only hidden when every accessor is
+            ("Property Visible: Boolean [Read/Write]".to_string(), false),
+            ("Property Parent: IDispatch* [Write]".to_string(), false),
+            ("Method Internal() -> Void".to_string(), true),
+            ("Const xlMaximized: Long = 7".to_string(), false),
+            ("Property Tag: String [Read/Write]".to_string(), true),
+        ]);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_parse_type_info_skips_unreadable_members() {
+        let info = MockTypeInfo {
+            // Claims more functions than the mock can describe
+            attr: mock_attr(3, 0),
+            funcs: vec![func(1, INVOKE_FUNC, 0, vec![], "Long")],
+            vars: vec![],
+            names: vec![],
+        };
+
+        let details = parse_type_info(&info, "Fallback").unwrap();
+        assert_eq!(details.members.len(), 1);
+        assert!(matches!(&details.members[0], Member::Method { name, .. } if name == "Unknown"));
+    }
+
+    #[test]
+    fn test_to_python_stub() {
+        let stub = to_python_stub(&details(vec![
+            Member::method("Fetch", vec![
+                Param::new("key", "String", PARAMFLAG_FIN),
+                Param::new("limit", "Long", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("10")),
+                Param::new("from", "Variant", 0),
+                Param::new("result", "Boolean&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+            ], "HResult").with_dispid(7),
+            Member::method("Log", vec![Param::new("values", "Variant[]", PARAMFLAG_FIN).variadic()], "Void"),
+            Member::property("Size", "Double", AccessMode::ReadWrite).with_dispid(-4),
+            Member::property("Password", "String", AccessMode::Write).with_dispid(8),
+            Member::Constant {
+                name: "MaxSize".to_string(),
+                value: 10,
+                value_type: "Long".to_string(),
+                hidden: false,
+                doc: String::new(),
+            },
+        ]));
+
+        assert!(stub.contains("from typing import Any\n\nclass Widget:\n"));
+        assert!(stub.contains(
+            "    # DISPID 7 (0x00000007)\n    def Fetch(self, key: str, limit: int = ..., from_: Any = ...) -> bool: ...\n"
+        ));
+        assert!(stub.contains("    def Log(self, *values: Any) -> None: ...\n"));
+        assert!(stub.contains(
+            "    # DISPID -4 (0xFFFFFFFC)\n    @property\n    def Size(self) -> float: ...\n    @Size.setter\n    def Size(self, value: float) -> None: ...\n"
+        ));
+        assert!(stub.contains("    Password: str  # write-only\n"));
+        assert!(stub.contains("    MaxSize: int = 10\n"));
+    }
+
+    #[test]
+    fn test_to_python_stub_of_empty_type_has_a_body() {
+        let stub = to_python_stub(&details(vec![]));
+        assert!(stub.ends_with("class Widget:\n    \"\"\"A widget\"\"\"\n    ...\n"));
+    }
+
+    #[test]
+    fn test_to_csharp_interop() {
+        let csharp = to_csharp_interop(
+            &details(vec![
+                Member::method("Fetch", vec![
+                    Param::new("string", "String", PARAMFLAG_FIN),
+                    Param::new("limit", "Long", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("10")),
+                    Param::new("count", "Long*", PARAMFLAG_FOUT),
+                    Param::new("result", "Variant&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+                ], "HResult").with_dispid(7),
+                Member::property("Owner", "UserDefined", AccessMode::Read).with_dispid(8),
+                Member::property("Size", "Double[]", AccessMode::ReadWrite),
+                Member::Constant {
+                    name: "MaxSize".to_string(),
+                    value: 10,
+                    value_type: "Long".to_string(),
+                    hidden: false,
+                    doc: String::new(),
+                },
+            ]),
+            "{00000000-0000-0000-0000-0000000000AA}",
+        );
+
+        assert!(csharp.contains(
+            "[ComImport, Guid(\"00000000-0000-0000-0000-000000000001\"), InterfaceType(ComInterfaceType.InterfaceIsDual)]\npublic interface IWidget\n{\n"
+        ));
+        assert!(csharp.contains(
+            "    [DispId(7)]\n    object Fetch(string @string, [Optional] int limit /* = 10 */, out int count);\n"
+        ));
+        assert!(csharp.contains("    [DispId(8)]\n    object /* unmapped: UserDefined */ Owner { get; }\n"));
+        assert!(csharp.contains("    double[] Size { get; set; }\n"));
+        assert!(csharp.contains("public static class WidgetConstants\n{\n    public const int MaxSize = 10;\n}\n"));
+        assert!(csharp.contains("[ComImport, Guid(\"00000000-0000-0000-0000-0000000000AA\")]\npublic class WidgetClass { }\n"));
+    }
+
+    #[test]
+    fn test_generated_files_for_several_types_import_once() {
+        let types = [details(vec![]), TypeDetails { name: "IGadget".to_string(), ..details(vec![]) }];
+
+        let csharp = to_csharp_interop_all(&types);
+        assert_eq!(csharp.matches("using System;").count(), 1);
+        assert!(csharp.contains("public interface IWidget\n") && csharp.contains("public interface IGadget\n"));
+        assert!(!csharp.contains("public class"));
+
+        let rust = to_rust_bindings_all(&types);
+        assert_eq!(rust.matches("use windows::core::").count(), 1);
+        assert!(rust.contains("pub trait IWidget {") && rust.contains("pub trait IGadget {"));
+
+        let python = to_python_stub_all(&types);
+        assert_eq!(python.matches("from typing import Any").count(), 1);
+        assert!(python.contains("class Widget:") && python.contains("class IGadget:"));
+    }
+
+    #[test]
+    fn test_to_powershell_snippet() {
+        let widget = details(vec![
+            Member::method("Fetch", vec![
+                Param::new("key", "String", PARAMFLAG_FIN),
+                Param::new("limit", "Long", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("10")),
+                Param::new("result", "Boolean&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+            ], "HResult").with_dispid(7),
+            Member::method("Reset", Vec::new(), "HResult"),
+            Member::property("Size", "Long", AccessMode::ReadWrite),
+            Member::Constant { name: "MaxSize".to_string(), value: 10, value_type: "Long".to_string(), hidden: false, doc: String::new() },
+        ]);
+
+        let snippet = to_powershell_snippet(&widget, Some("Acme.Widget"), "{A}");
+        assert_eq!(
+            snippet,
+            "# Widget - A widget\n\
+             $obj = New-Object -ComObject Acme.Widget\n\
+             # $result = $obj.Fetch($key, $limit)\n\
+             # $obj.Reset()\n\
+             # $obj.Size\n\
+             # $obj.Size = $value\n\
+             # $MaxSize = 10\n"
+        );
+
+        let by_clsid = to_powershell_snippet(&widget, None, "{A}");
+        assert!(by_clsid.contains("$obj = [Activator]::CreateInstance([Type]::GetTypeFromCLSID('{A}'))\n"));
+        assert!(!to_powershell_snippet(&widget, None, "").contains("$obj ="));
+    }
+
+    #[test]
+    fn test_to_typescript_declarations() {
+        let declarations = to_typescript_declarations(&details(vec![
+            Member::method("Fetch", vec![
+                Param::new("key", "String", PARAMFLAG_FIN),
+                Param::new("limit", "Long", PARAMFLAG_FIN | PARAMFLAG_FOPT).optional(Some("10")),
+                Param::new("default", "Variant", 0),
+                Param::new("result", "Boolean&", PARAMFLAG_FOUT | PARAMFLAG_FRETVAL),
+            ], "HResult").with_dispid(7),
+            Member::method("Log", vec![Param::new("values", "Variant[]", PARAMFLAG_FIN).variadic()], "Void"),
+            Member::property("Size", "Double", AccessMode::ReadWrite),
+            Member::property("Name", "String", AccessMode::Read),
+            Member::Constant {
+                name: "MaxSize".to_string(),
+                value: 10,
+                value_type: "Long".to_string(),
+                hidden: false,
+                doc: String::new(),
+            },
+        ]));
+
+        assert!(declarations.contains("declare namespace Widget {\n    const MaxSize = 10;\n\n    /** A widget */\n    interface IWidget {\n"));
+        assert!(declarations.contains("        Fetch(key: string, limit?: number, default_?: any): boolean;\n"));
+        assert!(declarations.contains("        Log(...values: any[]): void;\n"));
+        assert!(declarations.contains("        get Size(): number;\n        set Size(value: number);\n"));
+        assert!(declarations.contains("        get Name(): string;\n"));
+        assert!(!declarations.contains("set Name"));
+        assert!(declarations.ends_with("    }\n}\n"));
+    }
+
+    #[test]
+    fn test_to_dot_links_coclasses_to_interfaces() {
+        let interface = |name: &str, guid: &str, source: bool| ImplementedInterface {
+            name: name.to_string(),
+            guid: guid.to_string(),
+            source,
+            default: true,
+        };
+        let coclass = |name: &str, guid: &str| TypeDetails {
+            name: name.to_string(),
+            guid: guid.to_string(),
+            typekind: "Coclass".to_string(),
+            interface_kind: None,
+            interfaces: vec![interface("IWidget", "{0000000A-0000-0000-0000-000000000000}", false), interface("DWidgetEvents", "{0000000b-0000-0000-0000-000000000000}", true)],
+            ..details(Vec::new())
+        };
+        let types = vec![coclass("Widget", "{00000001-0000-0000-0000-000000000000}"), coclass("Say \"Hi\"", "")];
+
+        let dot = to_dot(&types);
+
+        assert!(dot.starts_with("digraph com {\n"));
+        assert!(dot.contains("    \"{00000001-0000-0000-0000-000000000000}\" [label=\"Widget\", shape=box, tooltip=\"{00000001-0000-0000-0000-000000000000}\"];\n"));
+        assert!(dot.contains("    \"Say \\\"Hi\\\"\" [label=\"Say \\\"Hi\\\"\", shape=box, tooltip=\"\"];\n"));
+        // Shared interfaces are drawn once; GUID case does not matter
+        assert_eq!(dot.matches("[label=\"DWidgetEvents\", shape=ellipse, tooltip=\"{0000000b-0000-0000-0000-000000000000}\"]").count(), 1);
+        assert!(dot.contains("    \"{00000001-0000-0000-0000-000000000000}\" -> \"{0000000A-0000-0000-0000-000000000000}\";\n"));
+        assert!(dot.contains("    \"{00000001-0000-0000-0000-000000000000}\" -> \"{0000000B-0000-0000-0000-000000000000}\" [style=dashed];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_implemented_interface_label() {
+        let interface = ImplementedInterface { name: "DEvents".to_string(), guid: String::new(), source: true, default: true };
+        assert_eq!(interface.label(), "[default, source] DEvents");
+        assert_eq!(ImplementedInterface { source: false, default: false, ..interface }.label(), "DEvents");
+    }
+}
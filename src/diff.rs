@@ -0,0 +1,218 @@
+// src/diff.rs
+use crate::error_handling::{Context, Result};
+use crate::export::EnhancedComObject;
+use crate::scanner::ComObject;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A `list --json` export: category name to the objects in it.
+pub type Export = BTreeMap<String, Vec<EnhancedComObject>>;
+
+/// An object present in only one of the two exports.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObjectRef {
+    pub clsid: String,
+    pub name: String,
+}
+
+/// An object present in both exports whose members differ.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObjectChange {
+    pub clsid: String,
+    pub name: String,
+    /// Member summaries only found in the new export
+    pub added_members: Vec<String>,
+    /// Member summaries only found in the old export
+    pub removed_members: Vec<String>,
+}
+
+/// The differences between two exports, each list sorted by CLSID.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct Changeset {
+    pub added: Vec<ObjectRef>,
+    pub removed: Vec<ObjectRef>,
+    pub changed: Vec<ObjectChange>,
+}
+
+impl Changeset {
+    /// Whether the exports describe the same objects and members.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Renders the changeset with `+` (added), `-` (removed) and `~` (changed) prefixes.
+    pub fn to_text(&self) -> String {
+        let mut buffer = String::new();
+        for obj in &self.added {
+            buffer.push_str(&format!("+ {} {}\n", obj.clsid, obj.name));
+        }
+        for obj in &self.removed {
+            buffer.push_str(&format!("- {} {}\n", obj.clsid, obj.name));
+        }
+        for change in &self.changed {
+            buffer.push_str(&format!("~ {} {}\n", change.clsid, change.name));
+            for member in &change.added_members {
+                buffer.push_str(&format!("    + {}\n", member));
+            }
+            for member in &change.removed_members {
+                buffer.push_str(&format!("    - {}\n", member));
+            }
+        }
+        if self.is_empty() {
+            buffer.push_str("No differences.\n");
+        }
+        buffer
+    }
+}
+
+/// Reads a `list --json` export from disk.
+pub fn load_export(path: &Path) -> Result<Export> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read '{}'", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("'{}' is not a list --json export", path.display()))
+}
+
+/// Compares two exports by CLSID, diffing the member sets of objects found in both.
+///
+/// Objects listed under several categories are considered once. Objects whose inspection
+/// failed are treated as having no members.
+pub fn diff_exports(old: &Export, new: &Export) -> Changeset {
+    let old = index_by_clsid(old);
+    let new = index_by_clsid(new);
+    let mut changeset = Changeset::default();
+
+    for (clsid, obj) in &new {
+        if !old.contains_key(clsid) {
+            changeset.added.push(object_ref(obj));
+        }
+    }
+    for (clsid, old_obj) in &old {
+        let Some(new_obj) = new.get(clsid) else {
+            changeset.removed.push(object_ref(old_obj));
+            continue;
+        };
+        let old_members = member_set(old_obj);
+        let new_members = member_set(new_obj);
+        if old_members != new_members {
+            changeset.changed.push(ObjectChange {
+                clsid: new_obj.base.clsid.clone(),
+                name: new_obj.base.name.clone(),
+                added_members: new_members.difference(&old_members).cloned().collect(),
+                removed_members: old_members.difference(&new_members).cloned().collect(),
+            });
+        }
+    }
+
+    changeset
+}
+
+/// Compares two registry scans by CLSID, reporting only added and removed objects.
+pub fn diff_scans(old: &[ComObject], new: &[ComObject]) -> Changeset {
+    let index = |objects: &[ComObject]| -> BTreeMap<String, ObjectRef> {
+        objects
+            .iter()
+            .map(|obj| (obj.clsid.to_ascii_uppercase(), ObjectRef { clsid: obj.clsid.clone(), name: obj.name.clone() }))
+            .collect()
+    };
+    let old = index(old);
+    let new = index(new);
+
+    Changeset {
+        added: new.iter().filter(|(clsid, _)| !old.contains_key(*clsid)).map(|(_, obj)| obj.clone()).collect(),
+        removed: old.iter().filter(|(clsid, _)| !new.contains_key(*clsid)).map(|(_, obj)| obj.clone()).collect(),
+        changed: Vec::new(),
+    }
+}
+
+/// Maps upper-cased CLSIDs to their first occurrence in the export.
+fn index_by_clsid(export: &Export) -> BTreeMap<String, &EnhancedComObject> {
+    let mut index = BTreeMap::new();
+    for obj in export.values().flatten() {
+        index.entry(obj.base.clsid.to_ascii_uppercase()).or_insert(obj);
+    }
+    index
+}
+
+fn object_ref(obj: &EnhancedComObject) -> ObjectRef {
+    ObjectRef { clsid: obj.base.clsid.clone(), name: obj.base.name.clone() }
+}
+
+fn member_set(obj: &EnhancedComObject) -> BTreeSet<String> {
+    obj.details
+        .iter()
+        .flat_map(|details| &details.members)
+        .map(|member| member.summary())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com_interop::{Member, TypeDetails};
+
+    fn method(name: &str) -> Member {
+        Member::method(name, Vec::new(), "void")
+    }
+
+    fn entry(clsid: &str, name: &str, members: Vec<Member>) -> EnhancedComObject {
+        EnhancedComObject {
+            base: ComObject { name: name.to_string(), clsid: clsid.to_string(), ..Default::default() },
+            details: Some(TypeDetails { name: name.to_string(), version: (1, 0), members, ..Default::default() }),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let old: Export = BTreeMap::from([(
+            "Excel".to_string(),
+            vec![
+                entry("{A}", "Excel.Application", vec![method("Quit"), method("Run")]),
+                entry("{B}", "Excel.Chart", vec![]),
+            ],
+        )]);
+        let new: Export = BTreeMap::from([
+            ("Excel".to_string(), vec![entry("{a}", "Excel.Application", vec![method("Quit"), method("Calculate")])]),
+            ("Word".to_string(), vec![entry("{C}", "Word.Application", vec![])]),
+        ]);
+
+        let changeset = diff_exports(&old, &new);
+        assert_eq!(changeset.added, vec![ObjectRef { clsid: "{C}".to_string(), name: "Word.Application".to_string() }]);
+        assert_eq!(changeset.removed, vec![ObjectRef { clsid: "{B}".to_string(), name: "Excel.Chart".to_string() }]);
+        assert_eq!(changeset.changed.len(), 1);
+        assert_eq!(changeset.changed[0].added_members, vec!["Method Calculate() -> void"]);
+        assert_eq!(changeset.changed[0].removed_members, vec!["Method Run() -> void"]);
+
+        let text = changeset.to_text();
+        assert!(text.contains("+ {C} Word.Application\n"));
+        assert!(text.contains("- {B} Excel.Chart\n"));
+        assert!(text.contains("~ {a} Excel.Application\n    + Method Calculate() -> void\n    - Method Run() -> void\n"));
+    }
+
+    #[test]
+    fn test_identical_exports_have_no_differences() {
+        let export: Export = BTreeMap::from([("Excel".to_string(), vec![entry("{A}", "Excel.Application", vec![method("Quit")])])]);
+        let changeset = diff_exports(&export, &export);
+        assert!(changeset.is_empty());
+        assert_eq!(changeset.to_text(), "No differences.\n");
+    }
+
+    #[test]
+    fn test_diff_scans_compares_clsids() {
+        let obj = |clsid: &str, name: &str| ComObject { name: name.to_string(), clsid: clsid.to_string(), ..Default::default() };
+        let old = vec![obj("{A}", "Old.Server"), obj("{B}", "Kept.Server")];
+        let new = vec![obj("{b}", "Kept.Server"), obj("{C}", "New.Server")];
+
+        let changeset = diff_scans(&old, &new);
+        assert_eq!(changeset.to_text(), "+ {C} New.Server\n- {A} Old.Server\n");
+    }
+
+    #[test]
+    fn test_export_round_trips_through_json() {
+        let export: Export = BTreeMap::from([("Excel".to_string(), vec![entry("{A}", "Excel.Application", vec![method("Quit")])])]);
+        let json = serde_json::to_string(&export).unwrap();
+        let parsed: Export = serde_json::from_str(&json).unwrap();
+        assert!(diff_exports(&export, &parsed).is_empty());
+    }
+}
@@ -17,7 +17,11 @@ impl fmt::Display for InspectError {
             InspectError::Registry(msg) => write!(f, "Registry Lookup Failed: {}", msg),
             InspectError::Instantiation { message, hresult } => {
                 if let Some(hr) = hresult {
-                    write!(f, "Instantiation Failed (Code: 0x{:08X})\nDetails: {}", hr, message)
+                    write!(
+                        f,
+                        "Instantiation Failed: {} (Code: 0x{:08X})\nDetails: {}",
+                        hresult_to_string(*hr), hr, message
+                    )
                 } else {
                     write!(f, "Instantiation Failed: {}", message)
                 }
@@ -32,4 +36,138 @@ impl fmt::Display for InspectError {
 
 impl std::error::Error for InspectError {}
 
-pub use anyhow::{Context, Result, Error};
\ No newline at end of file
+impl InspectError {
+    /// Classifies a failed registry operation: access-denied (`ERROR_ACCESS_DENIED`)
+    /// becomes `Permission`, anything else `Registry`.
+    pub fn from_io(context: impl fmt::Display, err: std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::PermissionDenied {
+            InspectError::Permission(format!("{}: {}", context, err))
+        } else {
+            InspectError::Registry(format!("{}: {}", context, err))
+        }
+    }
+}
+
+/// Exit codes of the CLI. Scripts rely on these, so existing values must not change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    /// Invalid arguments or any failure not covered below
+    Failure = 1,
+    /// The registry could not be scanned or a ProgID/CLSID could not be resolved
+    Scan = 2,
+    /// A type library could not be loaded or parsed, or instantiation failed
+    Inspection = 3,
+    /// Reading input or writing output failed
+    Output = 4,
+    /// Access was denied
+    Permission = 5,
+}
+
+impl ExitCode {
+    /// The numeric process exit code.
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Classifies an error by the first [`InspectError`] in its chain.
+    ///
+    /// `fallback` is the code for the step that failed, used for `Generic` and unclassified errors.
+    /// An access-denied I/O error anywhere in the chain maps to `Permission`.
+    pub fn for_error(err: &Error, fallback: ExitCode) -> ExitCode {
+        for cause in err.chain() {
+            if let Some(inspect_error) = cause.downcast_ref::<InspectError>() {
+                return match inspect_error {
+                    InspectError::Registry(_) => ExitCode::Scan,
+                    InspectError::Instantiation { .. } | InspectError::Parsing(_) | InspectError::Safety(_) => ExitCode::Inspection,
+                    InspectError::Permission(_) => ExitCode::Permission,
+                    InspectError::Generic(_) => fallback,
+                };
+            }
+            if let Some(io_error) = cause.downcast_ref::<std::io::Error>()
+                && io_error.kind() == std::io::ErrorKind::PermissionDenied
+            {
+                return ExitCode::Permission;
+            }
+        }
+        fallback
+    }
+}
+
+/// Explains an HRESULT in plain words.
+///
+/// Common COM activation failures get a hand-written explanation; anything else
+/// falls back to the system message table (`FormatMessageW`). Without Windows there
+/// is no message table, so only the code is shown.
+pub fn hresult_to_string(hr: i32) -> String {
+    #[cfg(windows)]
+    {
+        use windows::core::HRESULT;
+        use windows::Win32::Foundation::{
+            CLASS_E_NOAGGREGATION, CLASS_E_NOTLICENSED, CO_E_CLASSSTRING, CO_E_SERVER_EXEC_FAILURE,
+            E_ACCESSDENIED, E_NOINTERFACE, REGDB_E_CLASSNOTREG,
+        };
+
+        let hresult = HRESULT(hr);
+        let known = match hresult {
+            REGDB_E_CLASSNOTREG => Some("Class not registered"),
+            E_ACCESSDENIED => Some("Access denied; the server may require elevation or different launch permissions"),
+            CO_E_SERVER_EXEC_FAILURE => Some("The out-of-process server failed to start"),
+            E_NOINTERFACE => Some("The object does not support IDispatch"),
+            CLASS_E_NOAGGREGATION => Some("The class does not support aggregation"),
+            CLASS_E_NOTLICENSED => Some("The class is not licensed for use"),
+            CO_E_CLASSSTRING => Some("Invalid class string"),
+            _ => None,
+        };
+
+        match known {
+            Some(text) => text.to_string(),
+            None => {
+                let message = hresult.message();
+                let message = message.trim();
+                if message.is_empty() { format!("Unknown error 0x{:08X}", hr) } else { message.to_string() }
+            }
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        format!("Unknown error 0x{:08X}", hr)
+    }
+}
+
+pub use anyhow::{Context, Result, Error};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_follows_inspect_error_variant() {
+        let registry: Error = InspectError::Registry("missing".to_string()).into();
+        assert_eq!(ExitCode::for_error(&registry, ExitCode::Failure), ExitCode::Scan);
+
+        let parsing = Err::<(), _>(InspectError::Parsing("bad".to_string())).context("Failed to inspect").unwrap_err();
+        assert_eq!(ExitCode::for_error(&parsing, ExitCode::Failure), ExitCode::Inspection);
+
+        let generic: Error = InspectError::Generic("other".to_string()).into();
+        assert_eq!(ExitCode::for_error(&generic, ExitCode::Output), ExitCode::Output);
+    }
+
+    #[test]
+    fn test_instantiation_error_shows_the_hresult() {
+        let err = InspectError::Instantiation { message: "CoCreateInstance failed".to_string(), hresult: Some(0x80040154u32 as i32) };
+        let text = err.to_string();
+        assert!(text.starts_with("Instantiation Failed: "), "{}", text);
+        assert!(text.contains("(Code: 0x80040154)\nDetails: CoCreateInstance failed"), "{}", text);
+    }
+
+    #[test]
+    fn test_access_denied_io_error_is_a_permission_failure() {
+        let denied: Error = std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
+        assert_eq!(ExitCode::for_error(&denied, ExitCode::Output), ExitCode::Permission);
+
+        let missing: Error = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+        assert_eq!(ExitCode::for_error(&missing, ExitCode::Output), ExitCode::Output);
+        assert_eq!(ExitCode::Permission.code(), 5);
+    }
+}
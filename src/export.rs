@@ -0,0 +1,728 @@
+// src/export.rs
+use crate::scanner::ComObject;
+use crate::com_interop::{format_signature, AccessMode, Member, TypeDetails};
+use crate::style::Styler;
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// An object as written by `list --json`: the scan record plus deep inspection details.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EnhancedComObject {
+    #[serde(flatten)]
+    pub base: ComObject,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<TypeDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A report format of `list` and of exports from the TUI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Plain text grouped by category
+    Text,
+    /// JSON with deep inspection details
+    Json,
+    /// RFC 4180 CSV, one row per object
+    Csv,
+    /// GitHub-flavored Markdown tables grouped by category
+    Markdown,
+    /// One JSON object per line, each an [`NdjsonRecord`]
+    Ndjson,
+    /// XML document of categories and objects
+    Xml,
+    /// GraphViz digraph of every inspected coclass and its interfaces
+    Dot,
+    /// Self-contained HTML page with search
+    Html,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 8] = [
+        OutputFormat::Text,
+        OutputFormat::Json,
+        OutputFormat::Csv,
+        OutputFormat::Markdown,
+        OutputFormat::Ndjson,
+        OutputFormat::Xml,
+        OutputFormat::Dot,
+        OutputFormat::Html,
+    ];
+
+    /// The format's `--format` value, e.g. `markdown`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Xml => "xml",
+            OutputFormat::Dot => "dot",
+            OutputFormat::Html => "html",
+        }
+    }
+
+    /// File extension appended to `--output` paths.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Text => "txt",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Markdown => "md",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Xml => "xml",
+            OutputFormat::Dot => "dot",
+            OutputFormat::Html => "html",
+        }
+    }
+
+    /// The format whose [`extension`](Self::extension) is `ext`, compared case-insensitively.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.extension().eq_ignore_ascii_case(ext))
+    }
+}
+
+/// One line of `--format ndjson` output.
+#[derive(Debug, Serialize)]
+pub struct NdjsonRecord {
+    pub category: String,
+    #[serde(flatten)]
+    pub object: EnhancedComObject,
+}
+
+/// Version of the `list --json` output contract, bumped whenever a field is renamed or removed.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// JSON Schema of `list --json` output: category names mapped to arrays of [`EnhancedComObject`].
+///
+/// Each line of `--format ndjson` output matches the `EnhancedComObject` definition.
+pub fn json_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(BTreeMap<String, Vec<EnhancedComObject>>);
+    schema.insert("$id".to_string(), format!("urn:rustcom-explorer:list-export:v{}", SCHEMA_VERSION).into());
+    schema.insert("title".to_string(), "RustCOM Explorer list export".into());
+    schema.insert("version".to_string(), SCHEMA_VERSION.into());
+    schema.to_value()
+}
+
+/// Renders objects as RFC 4180 CSV with a header row.
+///
+/// Each row is `(category, object, member_count)`. The `member_count` column is only
+/// emitted when `include_member_count` is set; a `None` count (failed inspection) is left blank.
+pub fn to_csv<'a>(
+    rows: impl IntoIterator<Item = (&'a str, &'a ComObject, Option<usize>)>,
+    include_member_count: bool,
+) -> String {
+    let mut buffer = String::from("category,name,clsid,description");
+    if include_member_count {
+        buffer.push_str(",member_count");
+    }
+    buffer.push_str("\r\n");
+
+    for (category, obj, member_count) in rows {
+        let fields = [category, &obj.name, &obj.clsid, &obj.description];
+        let line: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+        buffer.push_str(&line.join(","));
+
+        if include_member_count {
+            buffer.push(',');
+            if let Some(count) = member_count {
+                buffer.push_str(&count.to_string());
+            }
+        }
+        buffer.push_str("\r\n");
+    }
+
+    buffer
+}
+
+/// Quotes a field if it contains a comma, quote, or line break, doubling embedded quotes.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Renders grouped objects as one GitHub-flavored Markdown table per category.
+pub fn to_markdown(groups: &BTreeMap<String, Vec<ComObject>>) -> String {
+    let mut buffer = String::from("# COM Objects\n");
+
+    for (category, objects) in groups {
+        buffer.push_str(&format!("\n## {}\n\n", category));
+        buffer.push_str("| Name | CLSID | Description |\n");
+        buffer.push_str("| :--- | :--- | :--- |\n");
+        for obj in objects {
+            buffer.push_str(&format!(
+                "| {} | `{}` | {} |\n",
+                markdown_escape(&obj.name),
+                obj.clsid,
+                markdown_escape(&obj.description)
+            ));
+        }
+    }
+
+    buffer
+}
+
+/// Renders a type's members as a GitHub-flavored Markdown table under a heading.
+pub fn members_to_markdown(details: &TypeDetails) -> String {
+    let mut buffer = format!("## {}\n\n", details.name);
+    if !details.description.is_empty() {
+        buffer.push_str(&format!("{}\n\n", markdown_escape(&details.description)));
+    }
+    buffer.push_str("| Kind | Name | Type / Signature | Description |\n");
+    buffer.push_str("| :--- | :--- | :--- | :--- |\n");
+
+    for member in &details.members {
+        let (kind, detail) = match member {
+            Member::Method { params, return_type, .. } => ("Method", format_signature(params, return_type)),
+            Member::Property { value_type, access, .. } => {
+                let access = match access {
+                    AccessMode::Read => "read-only",
+                    AccessMode::Write => "write-only",
+                    AccessMode::ReadWrite => "read/write",
+                };
+                ("Property", format!("{} ({})", value_type, access))
+            }
+            Member::Constant { value, value_type, .. } => ("Constant", format!("{} = {}", value_type, value)),
+        };
+        buffer.push_str(&format!(
+            "| {} | {} | `{}` | {} |\n",
+            kind,
+            markdown_escape(member.name()),
+            markdown_escape(&detail),
+            markdown_escape(member.doc())
+        ));
+    }
+
+    buffer
+}
+
+/// Escapes pipes and flattens line breaks so a value stays inside its table cell.
+fn markdown_escape(field: &str) -> String {
+    field.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Renders grouped objects as an XML document.
+///
+/// Each `<category name="...">` holds its `<object>` elements; objects with inspection
+/// details get one `<member>` child per member. Attribute values are escaped.
+pub fn to_xml(groups: &BTreeMap<String, Vec<EnhancedComObject>>) -> String {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    write_xml(&mut writer, groups).expect("writing XML to memory cannot fail");
+    String::from_utf8(writer.into_inner()).expect("XML output is UTF-8")
+}
+
+fn write_xml(writer: &mut Writer<Vec<u8>>, groups: &BTreeMap<String, Vec<EnhancedComObject>>) -> std::io::Result<()> {
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer.write_event(Event::Start(BytesStart::new("com_objects")))?;
+
+    for (category, objects) in groups {
+        let count = objects.len().to_string();
+        let element = BytesStart::new("category").with_attributes([("name", category.as_str()), ("count", count.as_str())]);
+        writer.write_event(Event::Start(element))?;
+
+        for obj in objects {
+            let base = &obj.base;
+            let mut element = BytesStart::new("object");
+            element.push_attribute(("name", base.name.as_str()));
+            element.push_attribute(("clsid", base.clsid.as_str()));
+            element.push_attribute(("description", base.description.as_str()));
+            element.push_attribute(("server_kind", format!("{:?}", base.server_kind).as_str()));
+            if let Some(path) = &base.server_path {
+                element.push_attribute(("server_path", path.as_str()));
+            }
+            if let Some(typelib) = &base.typelib {
+                element.push_attribute(("typelib", typelib.as_str()));
+            }
+
+            let members = obj.details.as_ref().map(|details| details.members.as_slice()).unwrap_or_default();
+            if members.is_empty() {
+                writer.write_event(Event::Empty(element))?;
+                continue;
+            }
+            writer.write_event(Event::Start(element))?;
+            for member in members {
+                writer.write_event(Event::Empty(member_element(member)))?;
+            }
+            writer.write_event(Event::End(BytesEnd::new("object")))?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("category")))?;
+    }
+
+    writer.write_event(Event::End(BytesEnd::new("com_objects")))?;
+    Ok(())
+}
+
+fn member_element(member: &Member) -> BytesStart<'static> {
+    let mut element = BytesStart::new("member");
+    match member {
+        Member::Method { name, params, return_type, .. } => {
+            element.push_attribute(("kind", "method"));
+            element.push_attribute(("name", name.as_str()));
+            element.push_attribute(("signature", format_signature(params, return_type).as_str()));
+            element.push_attribute(("return_type", return_type.as_str()));
+        }
+        Member::Property { name, value_type, access, .. } => {
+            element.push_attribute(("kind", "property"));
+            element.push_attribute(("name", name.as_str()));
+            element.push_attribute(("type", value_type.as_str()));
+            element.push_attribute(("access", format!("{:?}", access).as_str()));
+        }
+        Member::Constant { name, value, value_type, .. } => {
+            element.push_attribute(("kind", "constant"));
+            element.push_attribute(("name", name.as_str()));
+            element.push_attribute(("type", value_type.as_str()));
+            element.push_attribute(("value", value.to_string().as_str()));
+        }
+    }
+    if member.is_hidden() {
+        element.push_attribute(("hidden", "true"));
+    }
+    if !member.doc().is_empty() {
+        element.push_attribute(("doc", member.doc()));
+    }
+    element
+}
+
+/// Styles of the HTML report.
+const HTML_STYLE: &str = "\
+body { font-family: Segoe UI, sans-serif; margin: 2em; color: #222; }
+input[type=search] { width: 100%; max-width: 40em; padding: 0.4em; font-size: 1em; }
+details.category { margin: 0.8em 0; }
+details.category > summary { font-size: 1.2em; font-weight: bold; cursor: pointer; }
+.count { color: #888; font-weight: normal; }
+table { border-collapse: collapse; width: 100%; margin-top: 0.4em; }
+th, td { border: 1px solid #ddd; padding: 0.3em 0.5em; text-align: left; vertical-align: top; }
+th { background: #f4f4f4; }
+code { font-size: 0.9em; }
+ul.members { margin: 0.3em 0; padding-left: 1.2em; font-family: Consolas, monospace; font-size: 0.85em; }
+";
+
+/// Filters rows as the user types: every whitespace-separated term must occur in the row.
+/// Categories without a visible row are hidden, and all others open while a query is active.
+const HTML_SCRIPT: &str = "\
+const search = document.getElementById('search');
+search.addEventListener('input', () => {
+  const terms = search.value.toLowerCase().split(/\\s+/).filter(Boolean);
+  let shown = 0;
+  for (const section of document.querySelectorAll('details.category')) {
+    let visible = 0;
+    for (const row of section.querySelectorAll('tbody tr')) {
+      const text = row.textContent.toLowerCase();
+      row.hidden = !terms.every(term => text.includes(term));
+      if (!row.hidden) visible++;
+    }
+    section.hidden = visible === 0;
+    if (terms.length) section.open = true;
+    shown += visible;
+  }
+  document.getElementById('shown').textContent = shown;
+});
+";
+
+/// Renders grouped objects as a self-contained HTML page with a client-side search box.
+///
+/// Each category is a collapsible section holding a table of its objects; objects with
+/// inspection details get an expandable member list. Every registry-derived string is escaped.
+pub fn to_html(groups: &BTreeMap<String, Vec<EnhancedComObject>>) -> String {
+    let total: usize = groups.values().map(Vec::len).sum();
+    let mut buffer = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>COM Objects</title>\n");
+    buffer.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n<h1>COM Objects</h1>\n", HTML_STYLE));
+    buffer.push_str("<input type=\"search\" id=\"search\" placeholder=\"Filter by name, CLSID, description or member\" autofocus>\n");
+    buffer.push_str(&format!(
+        "<p><span id=\"shown\">{}</span> of {} objects in {} categories</p>\n",
+        total,
+        total,
+        groups.len()
+    ));
+
+    for (category, objects) in groups {
+        buffer.push_str(&format!(
+            "<details class=\"category\" open>\n<summary>{} <span class=\"count\">({})</span></summary>\n",
+            html_escape(category),
+            objects.len()
+        ));
+        buffer.push_str("<table>\n<thead><tr><th>Name</th><th>CLSID</th><th>Description</th><th>Server</th><th>Members</th></tr></thead>\n<tbody>\n");
+        for obj in objects {
+            let members = match &obj.details {
+                Some(details) => {
+                    let items: String = details
+                        .members
+                        .iter()
+                        .map(|member| format!("<li>{}</li>", html_escape(&member.summary())))
+                        .collect();
+                    format!(
+                        "<details><summary>{} members</summary><ul class=\"members\">{}</ul></details>",
+                        details.members.len(),
+                        items
+                    )
+                }
+                None => String::new(),
+            };
+            buffer.push_str(&format!(
+                "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                html_escape(&obj.base.name),
+                html_escape(&obj.base.clsid),
+                html_escape(&obj.base.description),
+                html_escape(obj.base.server_path.as_deref().unwrap_or("")),
+                members
+            ));
+        }
+        buffer.push_str("</tbody>\n</table>\n</details>\n");
+    }
+
+    buffer.push_str(&format!("<script>\n{}</script>\n</body>\n</html>\n", HTML_SCRIPT));
+    buffer
+}
+
+/// Escapes text for use in HTML element content and quoted attribute values.
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One report row: the object's category, the object, and its type details when deep-inspected.
+pub type ReportRow = (String, ComObject, Option<TypeDetails>);
+
+/// Renders rows in any format. `deep` tells whether the rows carry type details.
+///
+/// NDJSON is rendered in row order here; `list` streams it in completion order instead.
+pub fn render_report(format: OutputFormat, rows: Vec<ReportRow>, deep: bool) -> String {
+    let enhanced_groups = |rows: Vec<ReportRow>| {
+        let mut groups: BTreeMap<String, Vec<EnhancedComObject>> = BTreeMap::new();
+        for (category, base, details) in rows {
+            groups.entry(category).or_default().push(EnhancedComObject { base, details, error: None });
+        }
+        groups
+    };
+    let plain_groups = |rows: Vec<ReportRow>| {
+        let mut groups: BTreeMap<String, Vec<ComObject>> = BTreeMap::new();
+        for (category, obj, _) in rows {
+            groups.entry(category).or_default().push(obj);
+        }
+        groups
+    };
+
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&enhanced_groups(rows))
+                .expect("Failed to serialize COM objects to JSON")
+        }
+        OutputFormat::Csv => to_csv(
+            rows.iter().map(|(category, obj, details)| (category.as_str(), obj, details.as_ref().map(|d| d.members.len()))),
+            deep,
+        ),
+        OutputFormat::Markdown => to_markdown(&plain_groups(rows)),
+        OutputFormat::Xml => to_xml(&enhanced_groups(rows)),
+        OutputFormat::Html => to_html(&enhanced_groups(rows)),
+        OutputFormat::Dot => {
+            let types: Vec<TypeDetails> = rows.into_iter().filter_map(|(_, _, details)| details).collect();
+            crate::com_interop::to_dot(&types)
+        }
+        OutputFormat::Ndjson => rows
+            .into_iter()
+            .map(|(category, base, details)| {
+                let record = NdjsonRecord { category, object: EnhancedComObject { base, details, error: None } };
+                serde_json::to_string(&record).expect("Failed to serialize a COM object to JSON") + "\n"
+            })
+            .collect(),
+        OutputFormat::Text => {
+            let mut buffer = String::new();
+            for (category, objects) in plain_groups(rows) {
+                buffer.push_str(&format!("[{}]\n", category));
+                for obj in objects {
+                    buffer.push_str(&format!("  {} ({}) - {}\n", obj.name, obj.clsid, obj.description));
+                }
+            }
+            buffer
+        }
+    }
+}
+
+/// Renders grouped objects as an indented tree using box-drawing characters.
+///
+/// A `depth` of 1 prints only the category nodes with their object counts.
+pub fn to_tree(groups: &BTreeMap<String, Vec<ComObject>>, depth: u8) -> String {
+    to_tree_styled(groups, depth, &Styler::PLAIN)
+}
+
+/// Like [`to_tree`], with bold category names and dimmed counts and CLSIDs.
+pub fn to_tree_styled(groups: &BTreeMap<String, Vec<ComObject>>, depth: u8, style: &Styler) -> String {
+    let mut buffer = String::new();
+    let category_count = groups.len();
+
+    for (i, (category, objects)) in groups.iter().enumerate() {
+        let last_category = i + 1 == category_count;
+        let branch = if last_category { "└── " } else { "├── " };
+        buffer.push_str(&format!(
+            "{}{} {}\n",
+            branch,
+            style.strong(category),
+            style.dim(&format!("({})", objects.len()))
+        ));
+
+        if depth < 2 {
+            continue;
+        }
+
+        let indent = if last_category { "    " } else { "│   " };
+        for (j, obj) in objects.iter().enumerate() {
+            let leaf = if j + 1 == objects.len() { "└── " } else { "├── " };
+            buffer.push_str(&format!("{}{}{} {}\n", indent, leaf, obj.name, style.dim(&format!("({})", obj.clsid))));
+        }
+    }
+
+    buffer
+}
+
+/// One file written by `list --split-by-category`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ManifestPart {
+    pub category: String,
+    /// File name relative to the output directory
+    pub file: String,
+    pub count: usize,
+}
+
+/// The `index.json` written next to the parts of a split export.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Manifest {
+    /// Output format of every part, e.g. `json`
+    pub format: String,
+    pub parts: Vec<ManifestPart>,
+}
+
+/// Windows device names that cannot be used as file names, with or without an extension.
+const RESERVED_FILE_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Turns a category name into a file stem that is valid on Windows.
+///
+/// Characters outside letters, digits, `-`, `_` and `.` become `_`; leading and trailing dots
+/// are dropped, and reserved device names get a `_` prefix.
+pub fn sanitize_file_stem(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect();
+    let stem = replaced.trim_matches('.');
+    if stem.is_empty() {
+        "_".to_string()
+    } else if RESERVED_FILE_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem.split('.').next().unwrap_or(stem))) {
+        format!("_{}", stem)
+    } else {
+        stem.to_string()
+    }
+}
+
+/// File names for the parts of a split export, one per category and in the same order.
+///
+/// Categories that sanitize to the same stem, ignoring case, get `-2`, `-3`, ... suffixes.
+pub fn category_file_names<'a>(categories: impl IntoIterator<Item = &'a str>, ext: &str) -> Vec<String> {
+    let mut taken = std::collections::HashSet::new();
+    categories
+        .into_iter()
+        .map(|category| {
+            let stem = sanitize_file_stem(category);
+            let mut candidate = stem.clone();
+            let mut n = 1;
+            while !taken.insert(candidate.to_lowercase()) {
+                n += 1;
+                candidate = format!("{}-{}", stem, n);
+            }
+            format!("{}.{}", candidate, ext)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com_interop::{Param, PARAMFLAG_FIN};
+
+    fn obj(name: &str, description: &str) -> ComObject {
+        ComObject {
+            name: name.to_string(),
+            clsid: "{GUID}".to_string(),
+            description: description.to_string(),
+            has_progid: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_special_fields() {
+        let plain = obj("Excel.Application", "Microsoft Excel");
+        let tricky = obj("Odd.Object", "Says \"hi\", then\nleaves");
+
+        let csv = to_csv([("Excel", &plain, Some(3)), ("Odd", &tricky, None)], true);
+
+        assert_eq!(
+            csv,
+            "category,name,clsid,description,member_count\r\n\
+             Excel,Excel.Application,{GUID},Microsoft Excel,3\r\n\
+             Odd,Odd.Object,{GUID},\"Says \"\"hi\"\", then\nleaves\",\r\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_pipes() {
+        let mut groups = BTreeMap::new();
+        groups.insert("Shell".to_string(), vec![obj("Shell.Application", "Files | Folders")]);
+
+        let md = to_markdown(&groups);
+
+        assert!(md.contains("## Shell\n"));
+        assert!(md.contains("| Shell.Application | `{GUID}` | Files \\| Folders |\n"));
+    }
+
+    #[test]
+    fn test_xml_nests_members_and_escapes_attributes() {
+        let details = TypeDetails {
+            name: "Widget".to_string(),
+            version: (1, 0),
+            members: vec![Member::method("Open", vec![Param::new("path", "String", 0)], "Void").with_hidden(true)],
+            ..Default::default()
+        };
+        let mut groups = BTreeMap::new();
+        groups.insert("R&D".to_string(), vec![
+            EnhancedComObject { base: obj("Widget.App", "Says \"<hi>\""), details: Some(details), error: None },
+            EnhancedComObject { base: obj("Widget.Doc", ""), details: None, error: None },
+        ]);
+
+        let xml = to_xml(&groups);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<com_objects>\n"));
+        assert!(xml.contains("<category name=\"R&amp;D\" count=\"2\">"));
+        assert!(xml.contains("<object name=\"Widget.App\" clsid=\"{GUID}\" description=\"Says &quot;&lt;hi&gt;&quot;\" server_kind=\"None\">\n      <member kind=\"method\" name=\"Open\" signature=\"(path: String) -&gt; Void\" return_type=\"Void\" hidden=\"true\"/>\n    </object>"));
+        assert!(xml.contains("<object name=\"Widget.Doc\" clsid=\"{GUID}\" description=\"\" server_kind=\"None\"/>"));
+        assert!(xml.trim_end().ends_with("</com_objects>"));
+    }
+
+    #[test]
+    fn test_html_escapes_registry_strings_and_lists_members() {
+        let details = TypeDetails {
+            name: "Widget".to_string(),
+            version: (1, 0),
+            members: vec![Member::method("Open", vec![Param::new("path", "String", 0)], "Void")],
+            ..Default::default()
+        };
+        let mut groups = BTreeMap::new();
+        groups.insert("R&D".to_string(), vec![
+            EnhancedComObject { base: obj("Widget.App", "<script>alert('x')</script>"), details: Some(details), error: None },
+            EnhancedComObject { base: obj("Widget.Doc", ""), details: None, error: None },
+        ]);
+
+        let html = to_html(&groups);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<summary>R&amp;D <span class=\"count\">(2)</span></summary>"));
+        assert!(html.contains("<td>&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;</td>"));
+        assert!(!html.contains("<script>alert"));
+        assert!(html.contains("<summary>1 members</summary><ul class=\"members\"><li>Method Open(path: String) -&gt; Void</li></ul>"));
+        assert!(html.contains("<span id=\"shown\">2</span> of 2 objects in 1 categories"));
+        // No external assets
+        assert!(!html.contains("src=") && !html.contains("href="));
+    }
+
+    #[test]
+    fn test_render_report_writes_ndjson_lines() {
+        let rows = vec![
+            ("Excel".to_string(), obj("Excel.Application", "Excel"), None),
+            ("Word".to_string(), obj("Word.Application", "Word"), None),
+        ];
+        let ndjson = render_report(OutputFormat::Ndjson, rows, false);
+
+        let lines: Vec<serde_json::Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert!(ndjson.ends_with('\n'));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["category"], "Excel");
+        assert_eq!(lines[1]["name"], "Word.Application");
+    }
+
+    #[test]
+    fn test_output_format_from_extension() {
+        assert_eq!(OutputFormat::from_extension("NDJSON"), Some(OutputFormat::Ndjson));
+        assert_eq!(OutputFormat::from_extension("md"), Some(OutputFormat::Markdown));
+        assert_eq!(OutputFormat::from_extension("exe"), None);
+    }
+
+    #[test]
+    fn test_category_file_names_are_safe_and_unique() {
+        assert_eq!(sanitize_file_stem("Excel"), "Excel");
+        assert_eq!(sanitize_file_stem("(No ProgID)"), "_No_ProgID_");
+        assert_eq!(sanitize_file_stem("a/b:c*?"), "a_b_c__");
+        assert_eq!(sanitize_file_stem("..."), "_");
+        assert_eq!(sanitize_file_stem("con"), "_con");
+        assert_eq!(sanitize_file_stem("Com1.Widget"), "_Com1.Widget");
+        assert_eq!(sanitize_file_stem("Console"), "Console");
+
+        let names = category_file_names(["A:B", "A/B", "a_b", "Word"], "csv");
+        assert_eq!(names, vec!["A_B.csv", "A_B-2.csv", "a_b-3.csv", "Word.csv"]);
+    }
+
+    #[test]
+    fn test_json_schema_is_versioned_and_defines_members() {
+        let schema = json_schema();
+
+        assert_eq!(schema["version"], SCHEMA_VERSION);
+        assert_eq!(schema["type"], "object");
+        let defs = schema["$defs"].as_object().unwrap();
+        for name in ["EnhancedComObject", "TypeDetails", "Member"] {
+            assert!(defs.contains_key(name), "missing definition {}", name);
+        }
+        assert!(defs["EnhancedComObject"]["properties"]["clsid"].is_object());
+    }
+
+    #[test]
+    fn test_members_to_markdown() {
+        let details = TypeDetails {
+            name: "Widget".to_string(),
+            description: "A widget".to_string(),
+            version: (1, 0),
+            members: vec![
+                Member::method("Open", vec![Param::new("path", "String", PARAMFLAG_FIN)], "Void").with_doc("Opens a file | folder"),
+                Member::property("Visible", "Boolean", AccessMode::ReadWrite),
+            ],
+            ..Default::default()
+        };
+
+        let md = members_to_markdown(&details);
+
+        assert!(md.starts_with("## Widget\n\nA widget\n\n| Kind | Name |"));
+        assert!(md.contains("| Method | Open | `([in] path: String) -> Void` | Opens a file \\| folder |\n"));
+        assert!(md.contains("| Property | Visible | `Boolean (read/write)` |  |\n"));
+    }
+
+    #[test]
+    fn test_tree_depths() {
+        let mut groups = BTreeMap::new();
+        groups.insert("A".to_string(), vec![obj("A.One", ""), obj("A.Two", "")]);
+        groups.insert("B".to_string(), vec![obj("B.One", "")]);
+
+        assert_eq!(to_tree(&groups, 1), "├── A (2)\n└── B (1)\n");
+        assert_eq!(
+            to_tree(&groups, 2),
+            "├── A (2)\n│   ├── A.One ({GUID})\n│   └── A.Two ({GUID})\n└── B (1)\n    └── B.One ({GUID})\n"
+        );
+    }
+}
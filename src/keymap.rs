@@ -0,0 +1,206 @@
+// src/keymap.rs
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::error_handling::{Context, Result};
+
+/// A key plus the modifiers that must be held, parsed from strings such as `"ctrl+c"` or `"E"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// Whether a key event triggers this binding.
+    ///
+    /// Shift is ignored for character keys because terminals already report it in the
+    /// character itself (`E` rather than `shift+e`).
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let relevant = if matches!(self.code, KeyCode::Char(_)) {
+            KeyModifiers::CONTROL | KeyModifiers::ALT
+        } else {
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT
+        };
+        key.code == self.code && (key.modifiers & relevant) == (self.modifiers & relevant)
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        let mut parts: Vec<&str> = value.split('+').map(str::trim).collect();
+        // A trailing empty part means the key itself is '+', as in "ctrl++"
+        let key = match parts.pop() {
+            Some("") if value.ends_with('+') => "+",
+            Some(key) => key,
+            None => return Err("empty key binding".to_string()),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in parts.iter().filter(|part| !part.is_empty()) {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, value)),
+            };
+        }
+
+        let code = match key.to_ascii_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            lower if lower.len() > 1 && lower.starts_with('f') => {
+                let n = lower[1..].parse().map_err(|_| format!("unknown key '{}'", key))?;
+                KeyCode::F(n)
+            }
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    // "shift+e" means the same as "E"
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        modifiers.remove(KeyModifiers::SHIFT);
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key '{}'", key)),
+                }
+            }
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// User-remappable TUI actions. Actions missing from the file keep their defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyMap {
+    pub quit: KeyBinding,
+    pub inspect: KeyBinding,
+    pub copy_item: KeyBinding,
+    pub copy_all: KeyBinding,
+    pub search: KeyBinding,
+    pub expand_all: KeyBinding,
+    pub collapse_all: KeyBinding,
+    pub go_to: KeyBinding,
+    pub regex_search: KeyBinding,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            quit: KeyBinding::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            inspect: KeyBinding::plain(KeyCode::Enter),
+            copy_item: KeyBinding::plain(KeyCode::Char('c')),
+            copy_all: KeyBinding::plain(KeyCode::Char('C')),
+            search: KeyBinding::plain(KeyCode::Char('/')),
+            expand_all: KeyBinding::plain(KeyCode::Char('E')),
+            collapse_all: KeyBinding::plain(KeyCode::Char('W')),
+            go_to: KeyBinding::plain(KeyCode::Char(':')),
+            regex_search: KeyBinding::plain(KeyCode::Char('R')),
+        }
+    }
+}
+
+impl KeyMap {
+    /// Parses a keymap from TOML, e.g. `quit = "ctrl+q"`.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).context("Invalid keymap")
+    }
+
+    /// Loads the keymap at `path`, falling back to the defaults when the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keymap '{}'", path.display()))?;
+        Self::from_toml(&text).with_context(|| format!("Failed to load keymap '{}'", path.display()))
+    }
+}
+
+/// Returns the keymap file under the user's config directory.
+pub fn default_keymap_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rustcom_explorer").join("keymap.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(text: &str) -> KeyBinding {
+        KeyBinding::try_from(text.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bindings() {
+        assert_eq!(binding("ctrl+q"), KeyBinding::new(KeyCode::Char('q'), KeyModifiers::CONTROL));
+        assert_eq!(binding("shift+e"), KeyBinding::plain(KeyCode::Char('E')));
+        assert_eq!(binding("Enter"), KeyBinding::plain(KeyCode::Enter));
+        assert_eq!(binding("alt+F5"), KeyBinding::new(KeyCode::F(5), KeyModifiers::ALT));
+        assert_eq!(binding("ctrl++"), KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL));
+        assert!(KeyBinding::try_from("hyper+x".to_string()).is_err());
+        assert!(KeyBinding::try_from("nope".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_char_bindings_ignore_shift() {
+        let expand = binding("E");
+        assert!(expand.matches(&KeyEvent::new(KeyCode::Char('E'), KeyModifiers::SHIFT)));
+        assert!(!expand.matches(&KeyEvent::new(KeyCode::Char('E'), KeyModifiers::CONTROL)));
+        assert!(!binding("ctrl+c").matches(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn test_keymap_overrides_and_defaults() {
+        let keymap = KeyMap::from_toml("quit = \"ctrl+q\"\nsearch = \"f\"\n").unwrap();
+
+        assert_eq!(keymap.quit, binding("ctrl+q"));
+        assert_eq!(keymap.search, binding("f"));
+        assert_eq!(keymap.copy_all, KeyMap::default().copy_all);
+        assert!(KeyMap::from_toml("launch = \"x\"").is_err());
+    }
+}
@@ -3,4 +3,14 @@ pub mod scanner;
 pub mod com_interop;
 pub mod app;
 pub mod processor;
-pub mod cli;
\ No newline at end of file
+pub mod cli;
+pub mod export;
+pub mod keymap;
+pub mod diff;
+pub mod style;
+pub mod mock;
+pub mod pipeline;
+
+pub use pipeline::{inventory, inventory_by_category, InventoryOptions, InventoryOptionsBuilder};
+pub use scanner::{ScanOptions, ScanOptionsBuilder};
+
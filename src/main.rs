@@ -1,20 +1,27 @@
 // src/main.rs
-use std::io;
-use std::fmt::Write as FmtWrite; 
-use std::path::PathBuf;
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::sync::{mpsc, Mutex};
+use std::thread;
 use std::collections::BTreeMap;
-use serde::Serialize;
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use clap::Parser;
-use rustcom_explorer::{app::App, com_interop, scanner, error_handling::Result, cli::{Args, Commands}};
+use clap::{CommandFactory, Parser};
+use indicatif::{ProgressBar, ProgressStyle};
+use tracing::info;
+use tracing_subscriber::{filter::LevelFilter, fmt::format::FmtSpan, prelude::*};
+use rustcom_explorer::{InventoryOptions, processor::FilterOptions, app::{self, App, ScanUpdate}, com_interop, diff, export::{self, EnhancedComObject, NdjsonRecord, OutputFormat, ReportRow}, mock, pipeline, scanner, error_handling::{Context, ExitCode, Result}, cli::{Args, Commands, InspectFormat}};
+use rustcom_explorer::com_interop::Apartment;
+use rustcom_explorer::keymap::{self, KeyMap};
 
-// Parallelism & COM Imports
+// Parallelism
 use rayon::prelude::*;
-use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_MULTITHREADED};
 
 /// RAII wrapper for TUI terminal setup and teardown.
 pub struct Tui {
@@ -25,7 +32,7 @@ impl Tui {
     pub fn new() -> Result<Self> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
@@ -33,7 +40,7 @@ impl Tui {
         let original_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |panic_info| {
             let _ = disable_raw_mode();
-            let _ = execute!(io::stdout(), LeaveAlternateScreen);
+            let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
             original_hook(panic_info);
         }));
 
@@ -44,190 +51,672 @@ impl Tui {
 impl Drop for Tui {
     fn drop(&mut self) {
         let _ = disable_raw_mode();
-        let _ = execute!(self.terminal.backend_mut(), LeaveAlternateScreen);
+        let _ = execute!(self.terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture);
         let _ = self.terminal.show_cursor();
     }
 }
 
-/// Helper struct for JSON export that includes deep inspection details.
-#[derive(Serialize)]
-struct EnhancedComObject {
-    #[serde(flatten)]
-    base: scanner::ComObject,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    details: Option<com_interop::TypeDetails>,
+/// Configures the Rayon global thread pool with COM initialization.
+fn configure_rayon_pool(apartment: Apartment, jobs: Option<NonZeroUsize>) -> Result<()> {
+    pipeline::com_thread_pool(apartment, jobs)
+        .build_global()
+        .map_err(|e| anyhow::anyhow!("Failed to configure Rayon thread pool: {}", e))
 }
 
-/// Configures the Rayon global thread pool with COM initialization.
-fn configure_rayon_pool() -> Result<()> {
-    rayon::ThreadPoolBuilder::new()
-        .start_handler(|_| unsafe {
-            // Initialize COM as Multi-Threaded on each worker thread
-            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+/// Flattens the grouped structure into `(category, object)` pairs.
+fn flatten_groups(grouped_objects: BTreeMap<String, Vec<scanner::ComObject>>) -> Vec<(String, scanner::ComObject)> {
+    grouped_objects
+        .into_iter()
+        .flat_map(|(cat, objs)| objs.into_iter().map(move |obj| (cat.clone(), obj)))
+        .collect()
+}
+
+/// Deep-inspects every object in parallel and writes each result as a JSON line as soon as it completes.
+///
+/// Output order follows completion, not category order. Returns the number of lines written.
+fn stream_ndjson(
+    grouped_objects: BTreeMap<String, Vec<scanner::ComObject>>,
+    options: &com_interop::InspectOptions,
+    writer: &mut dyn Write,
+    quiet: bool,
+) -> Result<usize> {
+    let flat_objects = flatten_groups(grouped_objects);
+    let progress = inspection_progress(flat_objects.len(), quiet);
+
+    let options = *options;
+    let (tx, rx) = mpsc::channel();
+    let workers = thread::spawn(move || {
+        flat_objects.into_par_iter().for_each_with(tx, |tx, (category, base)| {
+            let object = match com_interop::get_type_info_with(&base.clsid, &options) {
+                Ok(details) => EnhancedComObject { base, details: Some(details), error: None },
+                Err(e) => EnhancedComObject { base, details: None, error: Some(format!("{:#}", e)) },
+            };
+            // The receiver only hangs up after a write error, which is reported below
+            let _ = tx.send(NdjsonRecord { category, object });
+        });
+    });
+
+    let mut written = 0;
+    for record in rx {
+        serde_json::to_writer(&mut *writer, &record)?;
+        writer.write_all(b"\n")?;
+        written += 1;
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    writer.flush()?;
+    let _ = workers.join();
+    log_type_lib_cache_stats();
+    Ok(written)
+}
+
+/// Whether a buffered report format needs type details: always for JSON and DOT, on `--deep` for CSV, XML and HTML.
+fn needs_deep_inspection(format: OutputFormat, deep_flag: bool) -> bool {
+    match format {
+        OutputFormat::Json | OutputFormat::Dot => true,
+        OutputFormat::Csv | OutputFormat::Xml | OutputFormat::Html => deep_flag,
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Ndjson => false,
+    }
+}
+
+/// Flattens grouped objects into report rows in category order, deep-inspecting them when `deep` is set.
+fn report_rows(grouped_objects: BTreeMap<String, Vec<scanner::ComObject>>, deep: bool, args: &Args) -> Vec<ReportRow> {
+    if deep {
+        deep_inspect(grouped_objects, &args.inspect_options(), args.quiet)
+    } else {
+        flatten_groups(grouped_objects).into_iter().map(|(category, obj)| (category, obj, None)).collect()
+    }
+}
+
+/// Writes one file per category into `dir`, plus an `index.json` manifest. Returns the number of parts.
+///
+/// Objects are deep-inspected once up front when the format needs it, so a single progress bar covers the whole run.
+fn write_split_report(
+    grouped_objects: BTreeMap<String, Vec<scanner::ComObject>>,
+    format: OutputFormat,
+    deep_flag: bool,
+    dir: &Path,
+    args: &Args,
+) -> Result<usize> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create directory '{}'", dir.display()))?;
+    let ext = format.extension();
+    let mut parts = Vec::new();
+
+    if format == OutputFormat::Ndjson {
+        let file_names = export::category_file_names(grouped_objects.keys().map(String::as_str), ext);
+        for ((category, objects), file) in grouped_objects.into_iter().zip(file_names) {
+            let path = dir.join(&file);
+            let writer = std::fs::File::create(&path).with_context(|| format!("Failed to create '{}'", path.display()))?;
+            let group = BTreeMap::from([(category.clone(), objects)]);
+            let count = stream_ndjson(group, &args.inspect_options(), &mut io::BufWriter::new(writer), args.quiet)?;
+            parts.push(export::ManifestPart { category, file, count });
+        }
+    } else {
+        let deep = needs_deep_inspection(format, deep_flag);
+        let mut by_category: BTreeMap<String, Vec<ReportRow>> = BTreeMap::new();
+        for row in report_rows(grouped_objects, deep, args) {
+            by_category.entry(row.0.clone()).or_default().push(row);
+        }
+        let file_names = export::category_file_names(by_category.keys().map(String::as_str), ext);
+        for ((category, rows), file) in by_category.into_iter().zip(file_names) {
+            let path = dir.join(&file);
+            let count = rows.len();
+            std::fs::write(&path, export::render_report(format, rows, deep))
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            parts.push(export::ManifestPart { category, file, count });
+        }
+    }
+
+    let manifest = export::Manifest {
+        format: format.name().to_string(),
+        parts,
+    };
+    let index_path = dir.join("index.json");
+    std::fs::write(&index_path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write '{}'", index_path.display()))?;
+    Ok(manifest.parts.len())
+}
+
+/// Appends `.ext` to an `--output` path unless it already ends with it.
+fn output_path(raw_path: String, ext: &str) -> PathBuf {
+    let mut path = PathBuf::from(raw_path);
+    let should_append = path.file_name()
+        .map(|name| {
+            !name.to_string_lossy()
+                .to_lowercase()
+                .ends_with(&format!(".{}", ext))
         })
-        .exit_handler(|_| unsafe {
-            CoUninitialize();
+        .unwrap_or(false);
+
+    if should_append
+        && let Some(stem) = path.file_name() {
+            let mut new_name = stem.to_os_string();
+            new_name.push(format!(".{}", ext));
+            path.set_file_name(new_name);
+        }
+    path
+}
+
+/// Runs deep inspection on every object in parallel, preserving category order.
+fn deep_inspect(
+    grouped_objects: BTreeMap<String, Vec<scanner::ComObject>>,
+    options: &com_interop::InspectOptions,
+    quiet: bool,
+) -> Vec<ReportRow> {
+    let flat_objects = flatten_groups(grouped_objects);
+    let progress = inspection_progress(flat_objects.len(), quiet);
+    let results = pipeline::inspect_objects(flat_objects, options, &|| progress.inc(1))
+        .into_iter()
+        .map(|(category, obj, details)| (category, obj, details.ok()))
+        .collect();
+    progress.finish_and_clear();
+    results
+}
+
+/// A stderr progress bar with an ETA for inspecting `total` objects; hidden when `quiet`.
+fn inspection_progress(total: usize, quiet: bool) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+    let style = ProgressStyle::with_template("Inspecting {pos}/{len} objects {msg} [{bar:40}] {elapsed_precise} (ETA {eta})")
+        .expect("Progress bar template is valid")
+        .progress_chars("=> ");
+    ProgressBar::new(total as u64)
+        .with_style(style)
+        .with_message(format!("on {} threads", rayon::current_num_threads()))
+}
+
+/// Logs how many `LoadRegTypeLib` calls the type library cache saved.
+fn log_type_lib_cache_stats() {
+    let stats = com_interop::type_lib_cache_stats();
+    info!(loads = stats.loads, hits = stats.hits, "Type library cache");
+}
+
+/// Reads one CLSID/ProgID per line, skipping blank lines and `#` comments.
+fn read_batch_inputs(reader: impl BufRead) -> Result<Vec<String>> {
+    let mut inputs = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            inputs.push(trimmed.to_string());
+        }
+    }
+    Ok(inputs)
+}
+
+/// Drops hidden/restricted members unless `show_hidden` is set.
+fn filter_hidden(mut details: com_interop::TypeDetails, show_hidden: bool) -> com_interop::TypeDetails {
+    if !show_hidden {
+        details.members.retain(|m| !m.is_hidden());
+    }
+    details
+}
+
+/// Resolves and inspects every input in parallel, recording failures per entry.
+fn inspect_batch(
+    inputs: Vec<String>,
+    options: &com_interop::InspectOptions,
+    show_hidden: bool,
+) -> Vec<EnhancedComObject> {
+    inputs
+        .into_par_iter()
+        .map(|input| {
+            let clsid = match scanner::resolve_clsid(&input) {
+                Ok(clsid) => clsid,
+                Err(e) => {
+                    return EnhancedComObject {
+                        base: scanner::ComObject { name: input, ..Default::default() },
+                        details: None,
+                        error: Some(format!("Could not find COM object: {:#}", e)),
+                    };
+                }
+            };
+
+            let base = scanner::ComObject { name: input, clsid, ..Default::default() };
+            match com_interop::get_type_info_with(&base.clsid, options) {
+                Ok(details) => EnhancedComObject {
+                    base,
+                    details: Some(filter_hidden(details, show_hidden)),
+                    error: None,
+                },
+                Err(e) => EnhancedComObject { base, details: None, error: Some(format!("{:#}", e)) },
+            }
         })
-        .build_global()
-        .map_err(|e| anyhow::anyhow!("Failed to configure Rayon thread pool: {}", e))
+        .collect()
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    
-    // Logging and Verbosity
-    if args.verbose {
-        eprintln!("[INFO] Verbose logging enabled.");
-        if args.unsafe_mode {
-            eprintln!("[WARN] UNSAFE MODE ENABLED. Objects missing Type Libraries will be instantiated.");
-        } else {
-            eprintln!("[INFO] Safety mode engaged. Only Registry Type Libraries will be inspected.");
+/// Builds a progress callback that logs every 10% step at info level.
+fn verbose_progress() -> impl FnMut(usize, usize) {
+    let mut last_total = 0;
+    let mut last_step = 0;
+    move |processed, total| {
+        if total == 0 {
+            return;
+        }
+        // Totals grow when a second registry view starts; restart the step tracking
+        if total != last_total {
+            last_total = total;
+            last_step = 0;
+        }
+        let step = processed * 10 / total;
+        if step > last_step {
+            last_step = step;
+            info!("Scan progress: {}% ({}/{} keys)", step * 10, processed, total);
+        }
+    }
+}
+
+/// Installs the global tracing subscriber.
+///
+/// Events at the `-v`/`-vv` level go to stderr (errors only with `--quiet`, nothing while the
+/// TUI owns the terminal) and, with `--log-file`, to that file along with span timings.
+fn init_tracing(args: &Args) -> Result<()> {
+    let level = LevelFilter::from_level(args.log_level());
+    let stderr_level = if args.command.is_none() {
+        LevelFilter::OFF
+    } else if args.quiet {
+        LevelFilter::ERROR
+    } else {
+        level
+    };
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(args.log_style().is_enabled())
+        .without_time()
+        .with_target(false)
+        .with_filter(stderr_level);
+
+    let file_layer = match &args.log_file {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create log file '{}'", path.display()))?;
+            let layer = tracing_subscriber::fmt::layer()
+                .with_writer(Mutex::new(file))
+                .with_ansi(false)
+                .with_thread_ids(true)
+                .with_span_events(FmtSpan::CLOSE)
+                .with_filter(level);
+            Some(layer)
         }
+        None => None,
+    };
+
+    tracing_subscriber::registry().with(stderr_layer).with(file_layer).init();
+    Ok(())
+}
+
+/// Reports a failed CLI step and exits with the code for its error class.
+///
+/// `step` is the exit code for the step that failed, used when the error itself is unclassified.
+fn fail(message: impl std::fmt::Display, err: &anyhow::Error, step: ExitCode) -> ! {
+    eprintln!("Error: {}: {:#}", message, err);
+    std::process::exit(ExitCode::for_error(err, step).code());
+}
+
+/// Scans the registry for a CLI report, exiting with the scan error code on failure.
+///
+/// A scan that ran past `--scan-timeout-secs` only warns and reports the objects found so far.
+fn scan_for_report(args: &Args) -> Vec<scanner::ComObject> {
+    match pipeline::scan(&args.inventory_options(), &mut verbose_progress()) {
+        Ok(objs) => objs,
+        Err(e) => {
+            let message = format!("{:#}", e);
+            match e.downcast::<scanner::ScanTimedOut>() {
+                Ok(timed_out) => {
+                    if !args.quiet {
+                        eprintln!("Warning: {}. The report is incomplete.", message);
+                    }
+                    timed_out.partial
+                }
+                Err(e) => fail("Failed to scan COM objects", &e, ExitCode::Scan),
+            }
+        }
+    }
+}
+
+fn main() {
+    // Invalid arguments exit with 1, not clap's default of 2, which is reserved for scan failures
+    let args = Args::try_parse().unwrap_or_else(|e| {
+        let _ = e.print();
+        std::process::exit(if e.use_stderr() { ExitCode::Failure.code() } else { ExitCode::Success.code() });
+    });
+
+    if let Err(e) = run(args) {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(ExitCode::for_error(&e, ExitCode::Failure).code());
+    }
+}
+
+fn run(mut args: Args) -> Result<()> {
+    init_tracing(&args)?;
+
+    if let Some(path) = &args.mock {
+        mock::install(path)?;
+    }
+
+    if args.unsafe_mode {
+        info!("Unsafe mode enabled. Objects missing Type Libraries will be instantiated.");
+    } else {
+        info!("Safety mode engaged. Only Registry Type Libraries will be inspected.");
     }
 
-    // 1. Configure Thread Pool (Must be done before any parallel ops)
-    configure_rayon_pool()?;
+    // 1. Configure Thread Pool (Must be done before any parallel ops); mock data needs no COM
+    if mock::active().is_none() {
+        configure_rayon_pool(args.apartment(), args.jobs)?;
+    }
 
     // 2. Main Thread COM Init
-    let _com_guard = com_interop::initialize_com()?;
+    let _com_guard = com_interop::initialize_com(args.apartment())?;
     
-    match args.command {
+    match args.command.take() {
         Some(Commands::List(list_args)) => {
             // --- CLI Mode: List ---
-            
-            if args.verbose {
-                eprintln!("[INFO] Scanning Registry for COM Objects...");
+            if list_args.schema {
+                let schema = serde_json::to_string_pretty(&export::json_schema()).expect("Failed to serialize the JSON schema");
+                println!("{}", schema);
+                return Ok(());
             }
+            
+            info!("Scanning Registry for COM Objects...");
 
             // A. Scan
-            let objects = match scanner::scan_com_objects() {
-                Ok(objs) => objs,
-                Err(e) => {
-                    eprintln!("Error: Failed to scan COM objects: {:#}", e);
-                    std::process::exit(1);
+            let mut objects = scan_for_report(&args);
+            if list_args.only_orphans {
+                if !args.check_orphans {
+                    scanner::mark_orphans(&mut objects);
                 }
-            };
-            
-            if args.verbose {
-                eprintln!("[INFO] Found {} objects. Filtering...", objects.len());
+                objects.retain(|obj| obj.orphaned);
             }
+            if list_args.only_with_typelib {
+                scanner::mark_registered_typelibs(&mut objects);
+                objects.retain(|obj| obj.typelib_registered);
+            }
+            
+            info!("Found {} objects. Filtering...", objects.len());
 
             // B. Filter
-            let filter_query = list_args.filter.as_deref().unwrap_or("");
-            let grouped_objects = rustcom_explorer::processor::process_objects(objects, filter_query);
+            let grouped_objects = rustcom_explorer::processor::process(objects, &list_args.filter_options(args.group_by()));
 
             // C. Format
-            let (output_content, ext) = if list_args.json {
-                let mut enhanced_groups = BTreeMap::new();
-                
-                // 1. Flatten the grouped structure for parallel processing
-                let flat_objects: Vec<(String, scanner::ComObject)> = grouped_objects
-                    .into_iter()
-                    .flat_map(|(cat, objs)| objs.into_iter().map(move |obj| (cat.clone(), obj)))
-                    .collect();
+            let format = list_args.output_format();
+            let ext = format.extension();
 
-                let total_objects = flat_objects.len();
-                let num_threads = rayon::current_num_threads();
-                
-                // UI: Progress Feedback
-                eprintln!("Processing {} objects on {} threads...", total_objects, num_threads);
-
-                // 2. Parallel Deep Inspection
-                let allow_unsafe = args.unsafe_mode;
-                
-                let enhanced_flat: Vec<(String, EnhancedComObject)> = flat_objects
-                    .into_par_iter()
-                    .map(|(category, obj)| {
-                        // Perform the COM/Registry lookup here, respecting safety flag
-                        let details = com_interop::get_type_info(&obj.clsid, allow_unsafe).ok();
-                        
-                        (category, EnhancedComObject {
-                            base: obj,
-                            details,
-                        })
-                    })
-                    .collect();
-
-                // 3. Re-group into BTreeMap
-                for (category, obj) in enhanced_flat {
-                    enhanced_groups.entry(category).or_insert_with(Vec::new).push(obj);
+            if list_args.split_by_category {
+                let dir = PathBuf::from(list_args.output.as_deref().unwrap_or_default());
+                match write_split_report(grouped_objects, format, list_args.deep, &dir, &args) {
+                    Ok(parts) if !args.quiet => println!("Successfully wrote {} category files to '{}'", parts, dir.display()),
+                    Ok(_) => {}
+                    Err(e) => fail(format_args!("Failed to write split report to '{}'", dir.display()), &e, ExitCode::Output),
                 }
+                return Ok(());
+            }
 
-                (
-                    serde_json::to_string_pretty(&enhanced_groups)
-                        .expect("Failed to serialize COM objects to JSON"),
-                    "json"
-                )
-            } else {
-                let mut buffer = String::new();
-                for (category, objects) in grouped_objects {
-                    writeln!(&mut buffer, "[{}]", category).unwrap();
-                    for obj in objects {
-                        writeln!(
-                            &mut buffer, 
-                            "  {} ({}) - {}", 
-                            obj.name, obj.clsid, obj.description
-                        ).unwrap();
+            if format == OutputFormat::Ndjson {
+                // Stream straight to the destination instead of buffering the whole report
+                let path = list_args.output.map(|raw_path| output_path(raw_path, ext));
+                let result = match &path {
+                    Some(path) => std::fs::File::create(path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|file| stream_ndjson(grouped_objects, &args.inspect_options(), &mut io::BufWriter::new(file), args.quiet)),
+                    None => stream_ndjson(grouped_objects, &args.inspect_options(), &mut io::stdout().lock(), args.quiet),
+                };
+                match (result, path) {
+                    (Err(e), _) => fail("Failed to write NDJSON output", &e, ExitCode::Output),
+                    (Ok(count), Some(path)) if !args.quiet => {
+                        println!("Successfully wrote {} objects to '{}'", count, path.display())
                     }
+                    (Ok(_), _) => {}
                 }
-                (buffer, "txt")
-            };
+                return Ok(());
+            }
+
+            let deep = needs_deep_inspection(format, list_args.deep);
+            let rows = report_rows(grouped_objects, deep, &args);
+            let output_content = export::render_report(format, rows, deep);
 
             // D. Output to File
             if let Some(raw_path) = list_args.output {
-                let mut path = PathBuf::from(raw_path);
-                
-                // Smart Extension Handling:
-                let should_append = path.file_name()
-                    .map(|name| {
-                        !name.to_string_lossy()
-                            .to_lowercase()
-                            .ends_with(&format!(".{}", ext))
-                    })
-                    .unwrap_or(false);
-
-                if should_append
-                    && let Some(stem) = path.file_name() {
-                        let mut new_name = stem.to_os_string();
-                        new_name.push(format!(".{}", ext));
-                        path.set_file_name(new_name);
-                    }
-
+                let path = output_path(raw_path, ext);
                 if let Err(e) = std::fs::write(&path, output_content) {
-                    eprintln!("Error: Failed to write output to file '{}': {:#}", path.display(), e);
-                    std::process::exit(1);
-                } else {
+                    fail(format_args!("Failed to write output to file '{}'", path.display()), &e.into(), ExitCode::Output);
+                } else if !args.quiet {
                     println!("Successfully wrote report to '{}'", path.display());
                 }
             } else {
                 println!("{}", output_content);
             }
         }
-        None => {
-            // --- TUI Mode ---
-            if args.verbose {
-                eprintln!("[INFO] Starting TUI Mode...");
+        Some(Commands::Inspect(inspect_args)) => {
+            // --- CLI Mode: Inspect ---
+            let Some(clsid_or_progid) = inspect_args.clsid_or_progid.clone() else {
+                // Batch mode: one CLSID/ProgID per line from a file or stdin
+                let inputs = match &inspect_args.from_file {
+                    Some(path) => std::fs::File::open(path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|file| read_batch_inputs(io::BufReader::new(file))),
+                    None => read_batch_inputs(io::stdin().lock()),
+                };
+                let inputs = match inputs {
+                    Ok(inputs) => inputs,
+                    Err(e) => fail("Failed to read batch input", &e, ExitCode::Output),
+                };
+
+                info!("Inspecting {} entries on {} threads...", inputs.len(), rayon::current_num_threads());
+
+                let results = inspect_batch(inputs, &args.inspect_options(), inspect_args.show_hidden);
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&results)
+                        .expect("Failed to serialize inspection results to JSON")
+                );
+                return Ok(());
+            };
+
+            // A. Resolve ProgID -> CLSID
+            let clsid = match scanner::resolve_clsid(&clsid_or_progid) {
+                Ok(clsid) => clsid,
+                Err(e) => fail(format_args!("Could not find COM object '{}'", clsid_or_progid), &e, ExitCode::Scan),
+            };
+
+            // Name the object by its ProgID too when the user passed a CLSID
+            let progid = scanner::progid_for_clsid(&clsid).ok();
+            match &progid {
+                Some(progid) => info!("Inspecting {} ({})...", clsid, progid),
+                None => info!("Inspecting {}...", clsid),
             }
 
-            println!("Scanning for COM objects... (This may take a moment)");
-            let objects = match scanner::scan_com_objects() {
+            // B. Inspect
+            let result = if inspect_args.deep {
+                com_interop::get_type_library(&clsid, &args.inspect_options())
+            } else {
+                com_interop::get_type_info_with(&clsid, &args.inspect_options()).map(|details| vec![details])
+            };
+            let types: Vec<_> = match result {
+                Ok(types) => types.into_iter().map(|details| filter_hidden(details, inspect_args.show_hidden)).collect(),
+                Err(e) => fail(format_args!("Failed to inspect {}", clsid), &e, ExitCode::Inspection),
+            };
+
+            // C. Output
+            let format = inspect_args.output_format();
+            if format == InspectFormat::Json {
+                // A single type stays a JSON object; --deep yields an array of types
+                let json = if inspect_args.deep {
+                    serde_json::to_string_pretty(&types)
+                } else {
+                    serde_json::to_string_pretty(&types[0])
+                };
+                println!("{}", json.expect("Failed to serialize type details to JSON"));
+            } else if format == InspectFormat::Dot {
+                print!("{}", com_interop::to_dot(&types));
+            } else if inspect_args.deep && matches!(format, InspectFormat::Rust | InspectFormat::Python | InspectFormat::Csharp) {
+                // Several types make one source file, so its imports are only emitted once
+                print!("{}", match format {
+                    InspectFormat::Rust => com_interop::to_rust_bindings_all(&types),
+                    InspectFormat::Python => com_interop::to_python_stub_all(&types),
+                    _ => com_interop::to_csharp_interop_all(&types),
+                });
+            } else {
+                let rendered: Vec<String> = types
+                    .iter()
+                    .map(|details| match format {
+                        InspectFormat::Idl => com_interop::to_idl(details),
+                        InspectFormat::Rust => com_interop::to_rust_bindings(details),
+                        InspectFormat::Python => com_interop::to_python_stub(details),
+                        InspectFormat::Typescript => com_interop::to_typescript_declarations(details),
+                        InspectFormat::Csharp => com_interop::to_csharp_interop(details, &clsid),
+                        InspectFormat::Powershell if inspect_args.deep => com_interop::to_powershell_snippet(details, None, ""),
+                        InspectFormat::Powershell => com_interop::to_powershell_snippet(details, progid.as_deref(), &clsid),
+                        _ => details.to_text(),
+                    })
+                    .collect();
+                print!("{}", rendered.join("\n"));
+            }
+        }
+        Some(Commands::Tree(tree_args)) => {
+            // --- CLI Mode: Tree ---
+            let objects = scan_for_report(&args);
+
+            let filter_query = tree_args.filter.as_deref().unwrap_or("");
+            let grouped_objects = rustcom_explorer::processor::process(objects, &FilterOptions::fuzzy(filter_query, args.group_by()));
+            print!("{}", export::to_tree_styled(&grouped_objects, tree_args.depth, &args.output_style()));
+        }
+        Some(Commands::Stats(stats_args)) => {
+            // --- CLI Mode: Stats ---
+            let objects = scan_for_report(&args);
+
+            let grouped_objects = rustcom_explorer::processor::process(objects, &FilterOptions::fuzzy("", args.group_by()));
+            let stats = rustcom_explorer::processor::compute_stats(&grouped_objects, 10);
+
+            if stats_args.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&stats).expect("Failed to serialize statistics to JSON")
+                );
+            } else {
+                print!("{}", stats.to_text_styled(&args.output_style()));
+            }
+        }
+        Some(Commands::Diff(diff_args)) => {
+            // --- CLI Mode: Diff ---
+            let load = |path: &PathBuf| match diff::load_export(path) {
+                Ok(export) => export,
+                Err(e) => fail("Failed to load export", &e, ExitCode::Output),
+            };
+            let changeset = diff::diff_exports(&load(&diff_args.old), &load(&diff_args.new));
+
+            if diff_args.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&changeset).expect("Failed to serialize changeset to JSON")
+                );
+            } else {
+                print!("{}", changeset.to_text());
+            }
+        }
+        Some(Commands::Watch) => {
+            // --- CLI Mode: Watch ---
+            // Arm the notification first so changes made during the initial scan are not missed
+            let watcher = match scanner::RegistryWatcher::new() {
+                Ok(watcher) => watcher,
+                Err(e) => fail("Failed to watch the registry", &e, ExitCode::Scan),
+            };
+            // Always rescan the live registry; the cache would hide the change being watched for
+            let live = InventoryOptions { use_cache: false, ..args.inventory_options() };
+            let rescan = || pipeline::scan(&live, &mut |_, _| {});
+            let mut previous = match rescan() {
                 Ok(objs) => objs,
-                Err(e) => {
-                    eprintln!("Failed to scan COM objects: {:?}", e);
-                    return Err(e);
-                }
+                Err(e) => fail("Failed to scan COM objects", &e, ExitCode::Scan),
             };
+            if !args.quiet {
+                eprintln!("Watching {} COM objects for registry changes. Press Ctrl-C to stop.", previous.len());
+            }
 
-            if objects.is_empty() {
-                println!("No COM objects found. Press Enter to exit.");
-                let mut line = String::new();
-                let _ = std::io::stdin().read_line(&mut line);
-                return Ok(());
+            loop {
+                // Re-armed before returning, so changes made while rescanning trigger the next pass
+                if let Err(e) = watcher.wait() {
+                    fail("Failed to watch the registry", &e, ExitCode::Scan);
+                }
+                // Installers write many keys in a burst; let them settle before rescanning
+                thread::sleep(Duration::from_millis(500));
+
+                let current = match rescan() {
+                    Ok(objs) => objs,
+                    Err(e) => {
+                        if !args.quiet {
+                            eprintln!("Warning: Rescan failed: {:#}", e);
+                        }
+                        continue;
+                    }
+                };
+                let changeset = diff::diff_scans(&previous, &current);
+                if !changeset.is_empty() {
+                    print!("{}", changeset.to_text());
+                }
+                previous = current;
             }
+        }
+        Some(Commands::Completions(completions_args)) => {
+            let mut command = Args::command();
+            let bin_name = command.get_name().to_string();
+            clap_complete::generate(completions_args.shell, &mut command, bin_name, &mut io::stdout());
+        }
+        None => {
+            // --- TUI Mode ---
+            info!("Starting TUI Mode...");
+
+            // Scan in the background so the TUI can render a spinner meanwhile
+            let inspect = args.inspect_options();
+            let group_by = args.group_by();
+            let state_path = if args.no_persist { None } else { app::default_state_path() };
+            let bookmarks_path = app::default_bookmarks_path();
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let progress_tx = tx.clone();
+                let result = pipeline::scan(&args.inventory_options(), &mut |processed, total| {
+                    // Throttle updates; the UI only redraws every tick anyway
+                    if processed % 250 == 0 || processed == total {
+                        let _ = progress_tx.send(ScanUpdate::Progress { processed, total });
+                    }
+                });
+                let _ = tx.send(ScanUpdate::Finished(result));
+            });
 
             let mut tui = Tui::new()?;
-            let mut app = App::new(objects, args.unsafe_mode);
+            let mut app = App::new(rx, inspect);
+            app.group_by = group_by;
+            if let Some(path) = keymap::default_keymap_path() {
+                match KeyMap::load(&path) {
+                    Ok(keymap) => app.keymap = keymap,
+                    Err(e) => app.show_notification(format!("Using default keys: {:#}", e), 4000),
+                }
+            }
+            // A missing state file just means this is the first session
+            if let Some(path) = &state_path
+                && path.exists()
+                && let Err(e) = app.load_state(path)
+            {
+                app.show_notification(format!("Could not restore session: {:#}", e), 3000);
+            }
+            if let Some(path) = &bookmarks_path
+                && path.exists()
+                && let Err(e) = app.load_bookmarks(path)
+            {
+                app.show_notification(format!("Could not load bookmarks: {:#}", e), 3000);
+            }
             app.run(&mut tui.terminal)?;
+            drop(tui);
+
+            if let Some(path) = &state_path
+                && let Err(e) = app.save_state(path)
+            {
+                eprintln!("Warning: Failed to save UI state: {:#}", e);
+            }
+            if let Some(path) = &bookmarks_path
+                && let Err(e) = app.save_bookmarks(path)
+            {
+                eprintln!("Warning: Failed to save bookmarks: {:#}", e);
+            }
         }
     }
 
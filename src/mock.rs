@@ -0,0 +1,96 @@
+// src/mock.rs
+use crate::com_interop::TypeDetails;
+use crate::error_handling::{Context, InspectError, Result};
+use crate::scanner::ComObject;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Fixture data that stands in for the registry and type libraries.
+#[derive(Debug, Clone, Default)]
+pub struct MockData {
+    pub objects: Vec<ComObject>,
+    /// Type details keyed by upper-cased CLSID
+    pub type_details: HashMap<String, TypeDetails>,
+}
+
+/// One fixture entry: a scanned object plus, optionally, what inspecting it returns.
+///
+/// Plain `Vec<ComObject>` files such as the scan cache are valid fixtures too.
+#[derive(Debug, Deserialize)]
+struct MockEntry {
+    #[serde(flatten)]
+    base: ComObject,
+    #[serde(default)]
+    details: Option<TypeDetails>,
+}
+
+static MOCK: OnceLock<MockData> = OnceLock::new();
+
+impl MockData {
+    /// Parses a JSON array of fixture entries.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let entries: Vec<MockEntry> = serde_json::from_str(json).context("Mock data is not a JSON array of COM objects")?;
+        let mut data = MockData::default();
+        for entry in entries {
+            if let Some(details) = entry.details {
+                data.type_details.insert(entry.base.clsid.to_ascii_uppercase(), details);
+            }
+            data.objects.push(entry.base);
+        }
+        Ok(data)
+    }
+
+    /// Reads a fixture file from disk.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mock data '{}'", path.display()))?;
+        Self::from_json(&json).with_context(|| format!("Invalid mock data in '{}'", path.display()))
+    }
+
+    /// The fixture for a CLSID, or an error naming the missing fixture.
+    pub fn details_for(&self, clsid: &str) -> Result<TypeDetails> {
+        self.type_details
+            .get(&clsid.to_ascii_uppercase())
+            .cloned()
+            .ok_or_else(|| InspectError::Registry(format!("No type details fixture for {}", clsid)).into())
+    }
+}
+
+/// Loads `path` and makes it the data source for scanning and inspection for the rest of the process.
+///
+/// Only the first call takes effect.
+pub fn install(path: &Path) -> Result<()> {
+    let data = MockData::load(path)?;
+    tracing::info!(objects = data.objects.len(), path = %path.display(), "Using mock data");
+    let _ = MOCK.set(data);
+    Ok(())
+}
+
+/// The installed mock data, if any.
+pub fn active() -> Option<&'static MockData> {
+    MOCK.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_fixture_parses() {
+        let data = MockData::from_json(include_str!("../fixtures/demo.json")).unwrap();
+        assert!(!data.objects.is_empty());
+        let excel = data.objects.iter().find(|obj| obj.name == "Excel.Application").unwrap();
+        let details = data.details_for(&excel.clsid.to_ascii_lowercase()).unwrap();
+        assert!(details.members.iter().any(|member| member.name() == "Quit"));
+    }
+
+    #[test]
+    fn test_plain_object_list_is_a_fixture() {
+        let objects = vec![ComObject { name: "Word.Application".to_string(), clsid: "{A}".to_string(), ..Default::default() }];
+        let data = MockData::from_json(&serde_json::to_string(&objects).unwrap()).unwrap();
+        assert_eq!(data.objects, objects);
+        assert!(data.details_for("{A}").is_err());
+    }
+}
@@ -0,0 +1,311 @@
+// src/pipeline.rs
+use crate::com_interop::{self, Apartment, ComGuard, InspectOptions, TypeDetails};
+use crate::error_handling::{Context, Result};
+use crate::export::EnhancedComObject;
+use crate::processor::{self, GroupBy};
+use crate::scanner::{self, ComObject, ScanOptions};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Settings for [`inventory`]: the registry scan, its cache and budget, and the inspection of its results.
+///
+/// The defaults match the CLI's: a default [`ScanOptions`] scan, ProgID-prefix grouping, and no
+/// cache, timeout or deep inspection.
+#[derive(Debug, Clone)]
+pub struct InventoryOptions {
+    /// What the registry scan covers
+    pub scan: ScanOptions,
+    /// How objects are grouped into categories
+    pub group_by: GroupBy,
+    /// Read fresh results from, and save new ones to, the on-disk scan cache
+    pub use_cache: bool,
+    /// Rescan even when the cache is fresh
+    pub refresh: bool,
+    /// Maximum age of cached scan results
+    pub cache_ttl: Duration,
+    /// Budget for the registry scan; fails with [`scanner::ScanTimedOut`] when exceeded
+    pub timeout: Option<Duration>,
+    /// Deep-inspect every scanned object
+    pub deep: bool,
+    /// How objects are inspected when `deep` is set
+    pub inspect: InspectOptions,
+    /// Worker threads for deep inspection; see [`com_thread_pool`]
+    pub jobs: Option<NonZeroUsize>,
+}
+
+impl Default for InventoryOptions {
+    fn default() -> Self {
+        Self {
+            scan: ScanOptions::default(),
+            group_by: GroupBy::default(),
+            use_cache: false,
+            refresh: false,
+            cache_ttl: Duration::from_secs(86400),
+            timeout: None,
+            deep: false,
+            inspect: InspectOptions::default(),
+            jobs: None,
+        }
+    }
+}
+
+impl InventoryOptions {
+    /// Starts a builder from the default options.
+    pub fn builder() -> InventoryOptionsBuilder {
+        InventoryOptionsBuilder::default()
+    }
+
+    /// Whether results can be shared through the scan cache, which only holds default-shaped scans.
+    pub fn is_cacheable(&self) -> bool {
+        self.use_cache && self.scan.include_clsid_branch && self.scan.resolve_server_paths
+    }
+}
+
+/// Chainable construction of [`InventoryOptions`]; every setter overrides one default.
+#[derive(Debug, Clone, Default)]
+pub struct InventoryOptionsBuilder {
+    options: InventoryOptions,
+}
+
+impl InventoryOptionsBuilder {
+    pub fn scan(mut self, scan: ScanOptions) -> Self {
+        self.options.scan = scan;
+        self
+    }
+
+    pub fn group_by(mut self, group_by: GroupBy) -> Self {
+        self.options.group_by = group_by;
+        self
+    }
+
+    pub fn use_cache(mut self, use_cache: bool) -> Self {
+        self.options.use_cache = use_cache;
+        self
+    }
+
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.options.refresh = refresh;
+        self
+    }
+
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.options.cache_ttl = ttl;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.options.timeout = timeout;
+        self
+    }
+
+    pub fn deep(mut self, deep: bool) -> Self {
+        self.options.deep = deep;
+        self
+    }
+
+    pub fn inspect(mut self, inspect: InspectOptions) -> Self {
+        self.options.inspect = inspect;
+        self
+    }
+
+    pub fn jobs(mut self, jobs: Option<NonZeroUsize>) -> Self {
+        self.options.jobs = jobs;
+        self
+    }
+
+    pub fn build(self) -> InventoryOptions {
+        self.options
+    }
+}
+
+/// Scans the registry and, when `options.deep` is set, inspects every object in parallel.
+///
+/// Inspection runs on a dedicated thread pool whose workers join the requested apartment and
+/// leave it again when the pool shuts down, so the caller's own COM state is untouched.
+/// Objects that fail to inspect keep their error message instead of type details.
+pub fn inventory(options: InventoryOptions) -> Result<Vec<EnhancedComObject>> {
+    let objects = scan(&options, &mut |_, _| {})?;
+    if !options.deep {
+        return Ok(objects.into_iter().map(|base| EnhancedComObject { base, details: None, error: None }).collect());
+    }
+
+    let inspect = || inspect_all(objects, &|obj| com_interop::get_type_info_with(&obj.clsid, &options.inspect));
+    // Mock data needs no COM
+    if crate::mock::active().is_some() {
+        return Ok(inspect());
+    }
+    let pool = com_thread_pool(options.inspect.apartment, options.jobs)
+        .build()
+        .context("Failed to start the inspection thread pool")?;
+    Ok(pool.install(inspect))
+}
+
+/// Inspects `objects` with `inspect` on the current Rayon pool, keeping failures as error messages.
+fn inspect_all(objects: Vec<ComObject>, inspect: &(dyn Fn(&ComObject) -> Result<TypeDetails> + Sync)) -> Vec<EnhancedComObject> {
+    let keyed = objects.into_iter().map(|obj| ((), obj)).collect();
+    inspect_objects_with(keyed, inspect, &|| {})
+        .into_iter()
+        .map(|((), base, details)| match details {
+            Ok(details) => EnhancedComObject { base, details: Some(details), error: None },
+            Err(e) => EnhancedComObject { base, details: None, error: Some(format!("{:#}", e)) },
+        })
+        .collect()
+}
+
+/// Like [`inventory`], grouped into categories by `options.group_by` the way `list --json` exports are.
+///
+/// Objects in several categories (e.g. with [`processor::GroupBy::ComponentCategory`]) are listed in each.
+pub fn inventory_by_category(options: InventoryOptions) -> Result<BTreeMap<String, Vec<EnhancedComObject>>> {
+    let group_by = options.group_by;
+    Ok(group_inventory(inventory(options)?, group_by))
+}
+
+fn group_inventory(objects: Vec<EnhancedComObject>, group_by: GroupBy) -> BTreeMap<String, Vec<EnhancedComObject>> {
+    let mut groups: BTreeMap<String, Vec<EnhancedComObject>> = BTreeMap::new();
+    for obj in objects {
+        for category in processor::category_keys(&obj.base, group_by) {
+            groups.entry(category).or_default().push(obj.clone());
+        }
+    }
+    groups
+}
+
+/// Scans the registry within `options.timeout`, serving fresh results from the on-disk cache when allowed.
+///
+/// `progress` receives `(processed, total)` key counts during a real scan.
+pub fn scan(options: &InventoryOptions, progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<ComObject>> {
+    let cache_path = if options.is_cacheable() { scanner::default_cache_path(options.scan.bitness) } else { None };
+
+    if let Some(path) = &cache_path
+        && !options.refresh
+        && scanner::is_cache_fresh(path, options.cache_ttl)
+    {
+        match scanner::load_cache(path) {
+            Ok(mut objects) => {
+                info!(count = objects.len(), path = %path.display(), "Loaded objects from cache");
+                if options.scan.check_orphans {
+                    scanner::mark_orphans(&mut objects);
+                }
+                return Ok(objects);
+            }
+            Err(e) => warn!("Ignoring scan cache: {:#}", e),
+        }
+    }
+
+    // Orphans are marked below, after caching
+    let scan_options = ScanOptions { check_orphans: false, ..options.scan.clone() };
+    let mut objects = match options.timeout {
+        Some(budget) => scanner::scan_com_objects_with_timeout(&scan_options, budget, progress)?,
+        None => scanner::scan_com_objects_with_progress(&scan_options, progress)?,
+    };
+
+    if let Some(path) = &cache_path
+        && let Err(e) = scanner::save_cache(&objects, path)
+    {
+        warn!("Failed to update scan cache: {:#}", e);
+    }
+
+    // Files come and go independently of the registry, so this is never cached
+    if options.scan.check_orphans {
+        scanner::mark_orphans(&mut objects);
+    }
+    Ok(objects)
+}
+
+/// Inspects every object on the current Rayon pool, keeping each object's key and input order.
+///
+/// `on_inspected` is called once per object as soon as its inspection finishes.
+pub fn inspect_objects<K: Send>(
+    objects: Vec<(K, ComObject)>,
+    options: &InspectOptions,
+    on_inspected: &(dyn Fn() + Sync),
+) -> Vec<(K, ComObject, Result<TypeDetails>)> {
+    inspect_objects_with(objects, &|obj| com_interop::get_type_info_with(&obj.clsid, options), on_inspected)
+}
+
+fn inspect_objects_with<K: Send>(
+    objects: Vec<(K, ComObject)>,
+    inspect: &(dyn Fn(&ComObject) -> Result<TypeDetails> + Sync),
+    on_inspected: &(dyn Fn() + Sync),
+) -> Vec<(K, ComObject, Result<TypeDetails>)> {
+    let results = objects
+        .into_par_iter()
+        .map(|(key, obj)| {
+            let details = inspect(&obj);
+            on_inspected();
+            (key, obj, details)
+        })
+        .collect();
+    let stats = com_interop::type_lib_cache_stats();
+    info!(loads = stats.loads, hits = stats.hits, "Type library cache");
+    results
+}
+
+/// A Rayon pool builder whose workers initialize COM in `apartment` and uninitialize it on exit.
+///
+/// `jobs` overrides the default worker count. Each worker is its own apartment and only uses
+/// the objects it created, so any count is safe. The default is one worker per CPU core for the
+/// MTA, but a single worker for STAs: STA-only components are mostly out-of-process automation
+/// servers (Office and the like) that start one process per launch, so launching them concurrently
+/// multiplies that cost instead of saving time.
+pub fn com_thread_pool(apartment: Apartment, jobs: Option<NonZeroUsize>) -> rayon::ThreadPoolBuilder {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    match jobs {
+        Some(jobs) => builder = builder.num_threads(jobs.get()),
+        None if apartment == Apartment::Sta => builder = builder.num_threads(1),
+        None => {}
+    }
+
+    builder
+        // Initialize COM in the requested apartment on each worker thread, for the thread's lifetime
+        .start_handler(move |_| match com_interop::initialize_com(apartment) {
+            Ok(guard) => WORKER_COM.set(Some(guard)),
+            Err(e) => warn!("Failed to initialize COM on a worker thread: {:#}", e),
+        })
+        .exit_handler(|_| drop(WORKER_COM.take()))
+}
+
+thread_local! {
+    /// The COM initialization of a [`com_thread_pool`] worker, released when the worker exits.
+    static WORKER_COM: RefCell<Option<ComGuard>> = const { RefCell::new(None) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockData;
+    use crate::scanner::Bitness;
+
+    #[test]
+    fn test_inventory_inspects_mock_objects() {
+        // Fed directly rather than installed, which would switch the whole test binary to mock data
+        let data = MockData::from_json(include_str!("../fixtures/demo.json")).unwrap();
+
+        let deep = inspect_all(data.objects.clone(), &|obj| data.details_for(&obj.clsid));
+        assert_eq!(deep.len(), data.objects.len());
+        let excel = deep.iter().find(|obj| obj.base.name == "Excel.Application").unwrap();
+        assert!(excel.details.as_ref().is_some_and(|details| !details.members.is_empty()));
+        // Objects without a fixture keep the inspection error
+        assert!(deep.iter().all(|obj| obj.details.is_some() != obj.error.is_some()));
+
+        let groups = group_inventory(deep, GroupBy::ProgIdPrefix);
+        assert!(groups["Excel"].iter().any(|obj| obj.base.name == "Excel.Application"));
+    }
+
+    #[test]
+    fn test_inventory_options_builder_overrides_defaults() {
+        let scan = ScanOptions::builder().bitness(Bitness::X86).check_orphans(true).build();
+        let options = InventoryOptions::builder().scan(scan).deep(true).build();
+        assert_eq!(options.scan.bitness, Bitness::X86);
+        assert!(options.scan.check_orphans && options.deep);
+        assert!(options.scan.include_clsid_branch && options.scan.resolve_server_paths);
+
+        let raw_paths = ScanOptions::builder().resolve_server_paths(false).build();
+        assert!(InventoryOptions::builder().use_cache(true).build().is_cacheable());
+        assert!(!InventoryOptions::builder().use_cache(true).scan(raw_paths).build().is_cacheable());
+    }
+}
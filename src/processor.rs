@@ -1,56 +1,540 @@
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use regex::Regex;
+use serde::Serialize;
 use std::collections::BTreeMap;
-use crate::scanner::ComObject;
+use crate::scanner::{Bitness, ComObject};
+use crate::com_interop::Member;
+use crate::style::Styler;
 
-/// Processes a vector of ComObjects by applying fuzzy matching based on the query
-/// and grouping the results by the ProgID prefix (the part before the first dot).
+/// Category used for objects registered only under HKCR\CLSID.
+pub const NO_PROGID_CATEGORY: &str = "(No ProgID)";
+
+/// Category used for objects that implement no component category.
+pub const UNCATEGORIZED: &str = "(Uncategorized)";
+
+/// Category used for objects with no registered server binary.
+pub const NO_SERVER_CATEGORY: &str = "(No Server)";
+
+/// The key used to group objects into categories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    /// The ProgID prefix before the first dot
+    #[default]
+    ProgIdPrefix,
+    /// The DLL or EXE that serves the class
+    ServerPath,
+    /// Each Implemented Category of the object (objects may appear in several groups)
+    ComponentCategory,
+    /// The registry view the object was found in
+    Bitness,
+}
+
+impl GroupBy {
+    /// The next grouping in the TUI's cycle order.
+    pub fn next(self) -> Self {
+        match self {
+            GroupBy::ProgIdPrefix => GroupBy::ServerPath,
+            GroupBy::ServerPath => GroupBy::ComponentCategory,
+            GroupBy::ComponentCategory => GroupBy::Bitness,
+            GroupBy::Bitness => GroupBy::ProgIdPrefix,
+        }
+    }
+
+    /// Human-readable name shown in the TUI.
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::ProgIdPrefix => "ProgID prefix",
+            GroupBy::ServerPath => "server path",
+            GroupBy::ComponentCategory => "component category",
+            GroupBy::Bitness => "bitness",
+        }
+    }
+}
+
+/// The order of objects within each category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    /// Object name
+    #[default]
+    Name,
+    /// CLSID
+    Clsid,
+    /// Fuzzy match score, best first (requires a query)
+    Score,
+    /// First implemented component category, then name
+    Category,
+}
+
+/// A sort key plus direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub reverse: bool,
+}
+
+/// How [`process`] matches objects.
+#[derive(Debug, Clone)]
+pub enum Query {
+    /// Whitespace-separated terms that must each fuzzy-match a field; empty keeps every object
+    Fuzzy(String),
+    /// A pattern matched against the name, ProgIDs, CLSID and description
+    Regex(Regex),
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Query::Fuzzy(String::new())
+    }
+}
+
+/// What [`process`] keeps, and how it files and orders the result.
+#[derive(Debug, Clone, Default)]
+pub struct FilterOptions {
+    pub query: Query,
+    pub group_by: GroupBy,
+    /// Keep only this category, compared case-insensitively
+    pub category: Option<String>,
+    /// Keep at most this many objects: the best fuzzy matches, or else the first by name
+    pub limit: Option<usize>,
+    /// Order within each category
+    pub order: SortOrder,
+}
+
+impl FilterOptions {
+    /// Fuzzy-matches `query` and groups by `group_by`, without a category, limit or custom order.
+    pub fn fuzzy(query: &str, group_by: GroupBy) -> Self {
+        FilterOptions { query: Query::Fuzzy(query.to_string()), group_by, ..Default::default() }
+    }
+}
+
+/// Filters objects by `options.query` and groups them into categories by `options.group_by`.
+///
+/// Objects without a ProgID are collected under [`NO_PROGID_CATEGORY`] when grouping by ProgID prefix.
+/// The category filter runs first, so `limit` only counts objects of that category, and objects
+/// filed under several categories do not bring the others along. Regex matches carry no score,
+/// so [`SortKey::Score`] falls back to name order for them.
+pub fn process(objects: Vec<ComObject>, options: &FilterOptions) -> BTreeMap<String, Vec<ComObject>> {
+    let objects = match &options.category {
+        Some(category) => filter_category(objects, category, options.group_by),
+        None => objects,
+    };
+
+    let mut scored: Vec<(i64, ComObject)> = match &options.query {
+        Query::Fuzzy(query) if !query.is_empty() => {
+            let matcher = SkimMatcherV2::default();
+            let mut scored: Vec<(i64, ComObject)> = objects
+                .into_iter()
+                .filter_map(|obj| score_object(&matcher, &obj, query).map(|score| (score, obj)))
+                .collect();
+            // Best matches first, so a limit keeps them
+            scored.sort_by_key(|b| std::cmp::Reverse(b.0));
+            scored
+        }
+        Query::Fuzzy(_) => objects.into_iter().map(|obj| (0, obj)).collect(),
+        Query::Regex(pattern) => objects
+            .into_iter()
+            .filter(|obj| {
+                pattern.is_match(&obj.name)
+                    || obj.progids.iter().any(|progid| pattern.is_match(progid))
+                    || pattern.is_match(&obj.clsid)
+                    || pattern.is_match(&obj.description)
+            })
+            .map(|obj| (0, obj))
+            .collect(),
+    };
+
+    if let Some(limit) = options.limit {
+        if !matches!(&options.query, Query::Fuzzy(query) if !query.is_empty()) {
+            scored.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+        }
+        scored.truncate(limit);
+    }
+
+    sort_scored(&mut scored, options.order);
+    let mut groups = group_objects(scored.into_iter().map(|(_, obj)| obj), options.group_by);
+    if let Some(category) = &options.category {
+        groups.retain(|key, _| key.eq_ignore_ascii_case(category));
+    }
+    groups
+}
+
+/// Keeps only objects filed under `category`, compared case-insensitively, when grouped by `group_by`.
+fn filter_category(objects: Vec<ComObject>, category: &str, group_by: GroupBy) -> Vec<ComObject> {
+    objects
+        .into_iter()
+        .filter(|obj| category_keys(obj, group_by).iter().any(|key| key.eq_ignore_ascii_case(category)))
+        .collect()
+}
+
+/// Sorts scored objects by `order`. Ties are broken by name so the output is deterministic.
+fn sort_scored(scored: &mut [(i64, ComObject)], order: SortOrder) {
+    scored.sort_by(|(score_a, a), (score_b, b)| {
+        let primary = match order.key {
+            SortKey::Name => std::cmp::Ordering::Equal,
+            SortKey::Clsid => a.clsid.to_ascii_uppercase().cmp(&b.clsid.to_ascii_uppercase()),
+            SortKey::Score => score_b.cmp(score_a),
+            SortKey::Category => a.categories.first().cmp(&b.categories.first()),
+        };
+        primary.then_with(|| a.name.cmp(&b.name))
+    });
+    if order.reverse {
+        scored.reverse();
+    }
+}
+
+/// Groups objects by the selected key, keeping their incoming order within each group.
+fn group_objects(objects: impl IntoIterator<Item = ComObject>, group_by: GroupBy) -> BTreeMap<String, Vec<ComObject>> {
+    let mut groups: BTreeMap<String, Vec<ComObject>> = BTreeMap::new();
+    for obj in objects {
+        for key in category_keys(&obj, group_by) {
+            groups.entry(key).or_default().push(obj.clone());
+        }
+    }
+    groups
+}
+
+/// Scores an object against a whitespace-separated query.
 ///
-/// # Arguments
-/// * `objects` - A vector of ComObject instances to process.
-/// * `query` - The search query string. If empty, all objects are included.
+/// Every term must fuzzy-match at least one of name, other ProgIDs, CLSID or description;
+/// the best field score of each term is summed. Name and ProgID matches are weighted highest.
+fn score_object(matcher: &SkimMatcherV2, obj: &ComObject, query: &str) -> Option<i64> {
+    query.split_whitespace().try_fold(0, |total, term| {
+        let s_name = std::iter::once(&obj.name)
+            .chain(&obj.progids)
+            .filter_map(|name| matcher.fuzzy_match(name, term))
+            .max()
+            .map(|s| s + 10);
+        let s_clsid = matcher.fuzzy_match(&obj.clsid, term).map(|s| s + 5);
+        let s_desc = matcher.fuzzy_match(&obj.description, term);
+
+        [s_name, s_clsid, s_desc].into_iter().flatten().max().map(|score| total + score)
+    })
+}
+
+/// Finds the characters of object names matched by a fuzzy query.
 ///
-/// # Returns
-/// A BTreeMap where keys are the ProgID prefixes and values are vectors of matching ComObjects,
-/// sorted by fuzzy match score in descending order when a query is provided.
-pub fn process_objects(objects: Vec<ComObject>, query: &str) -> BTreeMap<String, Vec<ComObject>> {
-    let matcher = SkimMatcherV2::default();
+/// Built once per view so the matcher and the split query are shared by every row.
+pub struct Highlighter {
+    matcher: SkimMatcherV2,
+    terms: Vec<String>,
+}
 
-    // Filter and score the objects based on fuzzy matching
-    let mut scored: Vec<(i64, ComObject)> = objects
+impl Highlighter {
+    pub fn new(query: &str) -> Self {
+        Self { matcher: SkimMatcherV2::default(), terms: query.split_whitespace().map(str::to_string).collect() }
+    }
+
+    /// Character positions in `text` matched by any term, sorted and deduplicated.
+    ///
+    /// Used to highlight why an object matched; terms that match no character are ignored.
+    pub fn indices(&self, text: &str) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .terms
+            .iter()
+            .filter_map(|term| self.matcher.fuzzy_indices(text, term))
+            .flat_map(|(_, positions)| positions)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+}
+
+/// Keeps the members whose name fuzzy-matches `query`, preserving their order.
+pub fn filter_members<'a>(members: Vec<&'a Member>, query: &str) -> Vec<&'a Member> {
+    if query.is_empty() {
+        return members;
+    }
+    let matcher = SkimMatcherV2::default();
+    members
         .into_iter()
-        .filter_map(|obj| {
-            if query.is_empty() {
-                return Some((0, obj));
+        .filter(|member| matcher.fuzzy_match(member.name(), query).is_some())
+        .collect()
+}
+
+/// Derives the category (or categories) an object is grouped under.
+pub fn category_keys(obj: &ComObject, group_by: GroupBy) -> Vec<String> {
+    match group_by {
+        GroupBy::ProgIdPrefix => {
+            let prefix = if obj.has_progid {
+                obj.name.split('.').next().unwrap_or("Misc").to_string()
+            } else {
+                NO_PROGID_CATEGORY.to_string()
+            };
+            vec![prefix]
+        }
+        GroupBy::ServerPath => {
+            vec![obj.server_path.clone().unwrap_or_else(|| NO_SERVER_CATEGORY.to_string())]
+        }
+        GroupBy::ComponentCategory => {
+            if obj.categories.is_empty() {
+                vec![UNCATEGORIZED.to_string()]
+            } else {
+                obj.categories.clone()
             }
+        }
+        GroupBy::Bitness => {
+            let view = match obj.bitness {
+                Bitness::Native => "Native",
+                Bitness::X86 => "32-bit (Wow6432Node)",
+                Bitness::Both => "Both views",
+            };
+            vec![view.to_string()]
+        }
+    }
+}
 
-            let s_name = matcher.fuzzy_match(&obj.name, query).map(|s| s + 10);
-            let s_clsid = matcher.fuzzy_match(&obj.clsid, query).map(|s| s + 5);
-            let s_desc = matcher.fuzzy_match(&obj.description, query);
+/// Summary statistics over a grouped object map.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ScanStats {
+    pub total_objects: usize,
+    pub category_count: usize,
+    /// The largest categories as `(name, object count)`, biggest first
+    pub top_categories: Vec<(String, usize)>,
+    pub with_progid: usize,
+    pub without_progid: usize,
+    pub with_typelib: usize,
+    pub without_typelib: usize,
+}
+
+/// Aggregates [`ScanStats`] over the output of [`process`], keeping the `top_n` largest categories.
+pub fn compute_stats(groups: &BTreeMap<String, Vec<ComObject>>, top_n: usize) -> ScanStats {
+    let objects: Vec<&ComObject> = groups.values().flatten().collect();
+    let with_progid = objects.iter().filter(|obj| obj.has_progid).count();
+    let with_typelib = objects.iter().filter(|obj| obj.typelib.is_some()).count();
 
-            let max_score = [s_name, s_clsid, s_desc]
-                .iter()
-                .filter_map(|&s| s)
-                .max();
-            max_score.map(|score| (score, obj))
-        })
+    let mut top_categories: Vec<(String, usize)> = groups
+        .iter()
+        .map(|(name, objs)| (name.clone(), objs.len()))
         .collect();
+    // Stable sort keeps ties in alphabetical order
+    top_categories.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    top_categories.truncate(top_n);
 
-    // Sort by score descending if searching
-    if !query.is_empty() {
-        scored.sort_by(|a, b| b.0.cmp(&a.0));
+    ScanStats {
+        total_objects: objects.len(),
+        category_count: groups.len(),
+        top_categories,
+        with_progid,
+        without_progid: objects.len() - with_progid,
+        with_typelib,
+        without_typelib: objects.len() - with_typelib,
     }
+}
 
-    // Group by ProgID prefix
-    let mut groups: BTreeMap<String, Vec<ComObject>> = BTreeMap::new();
-    for (_, obj) in scored {
-        let prefix = obj.name.split('.').next().unwrap_or("Misc").to_string();
-        groups.entry(prefix).or_default().push(obj);
+/// Breakdown of one category, shown when the category is selected in the TUI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CategoryStats {
+    pub count: usize,
+    /// Objects with a registered TypeLib
+    pub with_typelib: usize,
+    /// Objects with a registered server DLL/EXE path
+    pub with_server: usize,
+    /// Objects found only in the native registry view
+    pub native: usize,
+    /// Objects found only in the 32-bit view
+    pub x86: usize,
+    /// Objects registered in both views
+    pub both: usize,
+}
+
+/// Aggregates [`CategoryStats`] over the objects of one category.
+pub fn category_stats(objects: &[ComObject]) -> CategoryStats {
+    let mut stats = CategoryStats { count: objects.len(), ..Default::default() };
+    for obj in objects {
+        stats.with_typelib += obj.typelib.is_some() as usize;
+        stats.with_server += obj.server_path.is_some() as usize;
+        match obj.bitness {
+            Bitness::Native => stats.native += 1,
+            Bitness::X86 => stats.x86 += 1,
+            Bitness::Both => stats.both += 1,
+        }
     }
+    stats
+}
 
-    // Sort within each group by name
-    for group in groups.values_mut() {
-        group.sort_by(|a, b| a.name.cmp(&b.name));
+impl ScanStats {
+    /// Renders the statistics as a human-readable report.
+    pub fn to_text(&self) -> String {
+        self.to_text_styled(&Styler::PLAIN)
     }
 
-    groups
+    /// Like [`ScanStats::to_text`], highlighting the figures with `style`.
+    pub fn to_text_styled(&self, style: &Styler) -> String {
+        let mut buffer = String::new();
+        let rows = [
+            ("Total objects:     ", self.total_objects),
+            ("Categories:        ", self.category_count),
+            ("With ProgID:       ", self.with_progid),
+            ("Without ProgID:    ", self.without_progid),
+            ("With TypeLib:      ", self.with_typelib),
+            ("Without TypeLib:   ", self.without_typelib),
+        ];
+        for (label, value) in rows {
+            buffer.push_str(&format!("{}{}\n", label, style.strong(&value.to_string())));
+        }
+        buffer.push_str(&format!("\n{}\n", style.strong(&format!("Top {} categories:", self.top_categories.len()))));
+        for (name, count) in &self.top_categories {
+            buffer.push_str(&format!("  {}  {}\n", style.dim(&format!("{:>6}", count)), name));
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obj(name: &str, description: &str) -> ComObject {
+        ComObject {
+            name: name.to_string(),
+            clsid: "{00000000-0000-0000-0000-000000000000}".to_string(),
+            description: description.to_string(),
+            has_progid: true,
+            ..Default::default()
+        }
+    }
+
+    fn names(groups: &BTreeMap<String, Vec<ComObject>>) -> Vec<String> {
+        groups.values().flatten().map(|obj| obj.name.clone()).collect()
+    }
+
+    fn mock_objects() -> Vec<ComObject> {
+        vec![
+            obj("Excel.Application", "Microsoft Excel Application"),
+            obj("Excel.Chart", "Microsoft Excel Chart"),
+            obj("Word.Application", "Microsoft Word Application"),
+        ]
+    }
+
+    /// [`mock_objects`] with distinct CLSIDs and component categories, for sorting and category filters.
+    fn categorized_objects() -> Vec<ComObject> {
+        let with = |mut obj: ComObject, clsid: &str, category: &str| {
+            obj.clsid = clsid.to_string();
+            obj.categories = vec![category.to_string()];
+            obj
+        };
+        vec![
+            with(obj("Excel.Application", "Microsoft Excel Application"), "{00024500-0000-0000-C000-000000000046}", "Automation"),
+            with(obj("Excel.Chart", "Microsoft Excel Chart"), "{00020821-0000-0000-C000-000000000046}", "Charts"),
+            with(obj("Word.Application", "Microsoft Word Application"), "{000209FF-0000-0000-C000-000000000046}", "Automation"),
+        ]
+    }
+
+    fn fuzzy(objects: Vec<ComObject>, query: &str) -> BTreeMap<String, Vec<ComObject>> {
+        process(objects, &FilterOptions::fuzzy(query, GroupBy::ProgIdPrefix))
+    }
+
+    fn regex(pattern: &str, limit: Option<usize>) -> Vec<String> {
+        let options = FilterOptions { query: Query::Regex(Regex::new(pattern).unwrap()), limit, ..Default::default() };
+        names(&process(mock_objects(), &options))
+    }
+
+    fn sorted(query: &str, key: SortKey, reverse: bool) -> Vec<String> {
+        // Group by bitness so every mock object lands in the same category
+        let options = FilterOptions { order: SortOrder { key, reverse }, ..FilterOptions::fuzzy(query, GroupBy::Bitness) };
+        names(&process(categorized_objects(), &options))
+    }
+
+    #[test]
+    fn test_multi_term_query_requires_every_term() {
+        let groups = fuzzy(mock_objects(), "excel chart");
+        assert_eq!(names(&groups), vec!["Excel.Chart"]);
+
+        // Terms may match different fields: "word" in the name, "microsoft" in the description
+        let groups = fuzzy(mock_objects(), "word microsoft");
+        assert_eq!(names(&groups), vec!["Word.Application"]);
+
+        assert!(fuzzy(mock_objects(), "excel zzz").is_empty());
+    }
+
+    #[test]
+    fn test_regex_filter() {
+        assert_eq!(regex(r"^Excel\.", None), vec!["Excel.Application", "Excel.Chart"]);
+        // Descriptions are searched too
+        assert_eq!(regex("Word Application$", None), vec!["Word.Application"]);
+    }
+
+    #[test]
+    fn test_limit_keeps_best_matches_or_first_by_name() {
+        let limited = |query: &str, limit| {
+            let options = FilterOptions { limit: Some(limit), ..FilterOptions::fuzzy(query, GroupBy::ProgIdPrefix) };
+            names(&process(mock_objects(), &options))
+        };
+        assert_eq!(limited("", 2), vec!["Excel.Application", "Excel.Chart"]);
+        assert_eq!(limited("chart", 1), vec!["Excel.Chart"]);
+        assert_eq!(regex("Application", Some(1)), vec!["Excel.Application"]);
+    }
+
+    #[test]
+    fn test_category_filter_is_exact_and_case_insensitive() {
+        let in_category = |objects, category: &str, group_by| {
+            process(objects, &FilterOptions { category: Some(category.to_string()), ..FilterOptions::fuzzy("", group_by) })
+        };
+        assert_eq!(names(&in_category(mock_objects(), "excel", GroupBy::ProgIdPrefix)).len(), 2);
+        assert!(in_category(mock_objects(), "Exc", GroupBy::ProgIdPrefix).is_empty());
+
+        // Excel.Application also implements "Charts", which must not become a second group
+        let mut objects = categorized_objects();
+        objects[0].categories.push("Charts".to_string());
+        let groups = in_category(objects, "automation", GroupBy::ComponentCategory);
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["Automation"]);
+        assert_eq!(names(&groups), vec!["Excel.Application", "Word.Application"]);
+    }
+
+    #[test]
+    fn test_category_stats_breaks_down_objects() {
+        let mut objects = mock_objects();
+        objects[0].typelib = Some("{LIB}".to_string());
+        objects[0].server_path = Some("C:\\Office\\EXCEL.EXE".to_string());
+        objects[1].bitness = Bitness::X86;
+        objects[2].bitness = Bitness::Both;
+
+        let stats = category_stats(&objects);
+        assert_eq!(stats, CategoryStats { count: 3, with_typelib: 1, with_server: 1, native: 1, x86: 1, both: 1 });
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        assert_eq!(sorted("", SortKey::Name, false), vec!["Excel.Application", "Excel.Chart", "Word.Application"]);
+        assert_eq!(sorted("", SortKey::Name, true), vec!["Word.Application", "Excel.Chart", "Excel.Application"]);
+    }
+
+    #[test]
+    fn test_sort_by_clsid() {
+        assert_eq!(sorted("", SortKey::Clsid, false), vec!["Excel.Chart", "Word.Application", "Excel.Application"]);
+        assert_eq!(sorted("", SortKey::Clsid, true), vec!["Excel.Application", "Word.Application", "Excel.Chart"]);
+    }
+
+    #[test]
+    fn test_sort_by_score() {
+        let best_first = sorted("chart", SortKey::Score, false);
+        assert_eq!(best_first.first().map(String::as_str), Some("Excel.Chart"));
+        let worst_first = sorted("chart", SortKey::Score, true);
+        assert_eq!(worst_first.last().map(String::as_str), Some("Excel.Chart"));
+        // Without a query every score is equal, so names break the tie
+        assert_eq!(sorted("", SortKey::Score, false), sorted("", SortKey::Name, false));
+    }
+
+    #[test]
+    fn test_sort_by_category() {
+        assert_eq!(sorted("", SortKey::Category, false), vec!["Excel.Application", "Word.Application", "Excel.Chart"]);
+        assert_eq!(sorted("", SortKey::Category, true), vec!["Excel.Chart", "Word.Application", "Excel.Application"]);
+    }
+
+    #[test]
+    fn test_highlighter_indices() {
+        let highlighter = Highlighter::new("exc cht");
+        assert_eq!(highlighter.indices("Excel.Chart"), vec![0, 1, 2, 6, 7, 10]);
+        assert_eq!(highlighter.indices("Excel.Chart"), vec![0, 1, 2, 6, 7, 10]);
+        assert!(Highlighter::new("").indices("Excel.Chart").is_empty());
+        assert!(Highlighter::new("zzz").indices("Excel.Chart").is_empty());
+    }
+
+    #[test]
+    fn test_single_term_query_unchanged() {
+        let groups = fuzzy(mock_objects(), "excel");
+        assert_eq!(names(&groups), vec!["Excel.Application", "Excel.Chart"]);
+        assert_eq!(names(&fuzzy(mock_objects(), "")).len(), 3);
+    }
 }
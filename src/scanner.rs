@@ -1,17 +1,214 @@
-use crate::error_handling::{Result, Context};
+use crate::error_handling::{Result, Context, InspectError};
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Represents a COM Object found in the registry.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(default)]
 pub struct ComObject {
-    /// The Program ID (e.g., "Excel.Application")
+    /// The Program ID (e.g., "Excel.Application"), preferring the version-independent one
     pub name: String,
+    /// Every ProgID known for the CLSID, version-independent first (e.g., "Excel.Application", "Excel.Application.16")
+    pub progids: Vec<String>,
     /// The CLSID GUID (e.g., "{00024500-0000-0000-C000-000000000046}")
     pub clsid: String,
     /// The description of the object (e.g., "Microsoft Excel Application")
     pub description: String,
+    /// Whether the object was found via a ProgID key (false for CLSID-only registrations)
+    pub has_progid: bool,
+    /// The kind of server registered for the CLSID
+    pub server_kind: ServerKind,
+    /// The DLL or EXE implementing the CLSID, with environment variables expanded
+    pub server_path: Option<String>,
+    /// The `ThreadingModel` of an in-process server ("Single" when the value is absent)
+    pub threading_model: Option<String>,
+    /// The registry view(s) the object was found in
+    pub bitness: Bitness,
+    /// DCOM application settings, when the class references an AppID
+    pub app_id: Option<AppIdInfo>,
+    /// Implemented Categories, as friendly names where known or raw CATIDs otherwise
+    pub categories: Vec<String>,
+    /// The registered TypeLib GUID (`CLSID\{clsid}\TypeLib`), if any
+    pub typelib: Option<String>,
+    /// Set by [`mark_orphans`] when the registered server binary does not exist on disk
+    pub orphaned: bool,
+    /// Set by [`mark_registered_typelibs`] when `typelib` is registered under `HKCR\TypeLib`,
+    /// so the object can be inspected without being instantiated
+    pub typelib_registered: bool,
 }
 
+/// Well-known component category IDs and their friendly names.
+#[cfg_attr(not(windows), allow(dead_code))]
+const KNOWN_CATEGORIES: &[(&str, &str)] = &[
+    ("{00021490-0000-0000-C000-000000000046}", "Browsable Shell Extension"),
+    ("{00021491-0000-0000-C000-000000000046}", "Browse In Place"),
+    ("{00021492-0000-0000-C000-000000000046}", "Desk Band"),
+    ("{00021493-0000-0000-C000-000000000046}", "Info Band"),
+    ("{00021494-0000-0000-C000-000000000046}", "Communication Band"),
+    ("{40FC6ED3-2438-11CF-A3DB-080036F12502}", "Automation Objects"),
+    ("{40FC6ED4-2438-11CF-A3DB-080036F12502}", "Insertable Objects"),
+    ("{40FC6ED5-2438-11CF-A3DB-080036F12502}", "Controls"),
+    ("{7DD95801-9882-11CF-9FA9-00AA006C42C4}", "Safe for Scripting"),
+    ("{7DD95802-9882-11CF-9FA9-00AA006C42C4}", "Safe for Initializing"),
+    ("{F0B7A1A1-9847-11CF-8F20-00805F2CD064}", "Active Scripting Engine"),
+    ("{F0B7A1A2-9847-11CF-8F20-00805F2CD064}", "Active Scripting Engine with Parsing"),
+    ("{62C8FE65-4EBB-45E7-B440-6E39B2CDBF29}", ".NET Category"),
+];
+
+/// Maps a CATID to its friendly name, or returns it unchanged if unknown.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn category_name(catid: &str) -> String {
+    KNOWN_CATEGORIES
+        .iter()
+        .find(|(id, _)| id.eq_ignore_ascii_case(catid))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| catid.to_string())
+}
+
+/// DCOM settings from `HKCR\AppID\{appid}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct AppIdInfo {
+    /// The AppID GUID referenced by the class
+    pub app_id: String,
+    /// Windows service hosting the server (`LocalService`)
+    pub local_service: Option<String>,
+    /// Identity the server runs as (`RunAs`)
+    pub run_as: Option<String>,
+    /// Surrogate process for DLL servers; empty means the default `dllhost.exe` (`DllSurrogate`)
+    pub dll_surrogate: Option<String>,
+}
+
+/// A registry view on 64-bit Windows.
+///
+/// Used both to select which views to scan and to tag where an object was found.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum Bitness {
+    /// The process's native view (64-bit on 64-bit Windows)
+    #[default]
+    Native,
+    /// The 32-bit view (`Wow6432Node`)
+    X86,
+    /// Both views
+    Both,
+}
+
+/// How a COM class is served, based on the subkeys of `CLSID\{clsid}`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum ServerKind {
+    /// `InprocServer32`: a DLL loaded into the caller's process
+    InProc,
+    /// `LocalServer32`: a separate EXE process
+    Local,
+    /// No server registration found
+    #[default]
+    None,
+}
+
+/// Settings for a registry scan.
+///
+/// The defaults match the CLI's: both registry views, the `HKCR\CLSID` pass, expanded server
+/// paths, and no orphan check. Caching, timeouts and inspection are [`crate::pipeline::InventoryOptions`].
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Registry view(s) to scan
+    pub bitness: Bitness,
+    /// Also walk `HKCR\CLSID` for servers that no ProgID points at
+    pub include_clsid_branch: bool,
+    /// Expand `%VAR%` references in server paths; raw registry values are kept otherwise
+    pub resolve_server_paths: bool,
+    /// Flag objects whose registered server binary is missing
+    pub check_orphans: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            bitness: Bitness::Both,
+            include_clsid_branch: true,
+            resolve_server_paths: true,
+            check_orphans: false,
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Starts a builder from the default options.
+    pub fn builder() -> ScanOptionsBuilder {
+        ScanOptionsBuilder::default()
+    }
+}
+
+/// Chainable construction of [`ScanOptions`]; every setter overrides one default.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptionsBuilder {
+    options: ScanOptions,
+}
+
+impl ScanOptionsBuilder {
+    pub fn bitness(mut self, bitness: Bitness) -> Self {
+        self.options.bitness = bitness;
+        self
+    }
+
+    pub fn include_clsid_branch(mut self, include: bool) -> Self {
+        self.options.include_clsid_branch = include;
+        self
+    }
+
+    pub fn resolve_server_paths(mut self, resolve: bool) -> Self {
+        self.options.resolve_server_paths = resolve;
+        self
+    }
+
+    pub fn check_orphans(mut self, check: bool) -> Self {
+        self.options.check_orphans = check;
+        self
+    }
+
+    pub fn build(self) -> ScanOptions {
+        self.options
+    }
+}
+
+/// The error of a scan that ran past its [`scan_com_objects_with_timeout`] budget.
+///
+/// Its source is an [`InspectError::Generic`] describing the budget.
+#[derive(Debug)]
+pub struct ScanTimedOut {
+    /// Objects found before the scan was stopped; empty when it could not be stopped in time
+    pub partial: Vec<ComObject>,
+    source: InspectError,
+}
+
+impl ScanTimedOut {
+    fn new(partial: Vec<ComObject>, budget: Duration) -> Self {
+        let source = InspectError::Generic(format!("The registry scan did not finish within {} seconds", budget.as_secs_f32()));
+        Self { partial, source }
+    }
+}
+
+impl std::fmt::Display for ScanTimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let noun = if self.partial.len() == 1 { "object" } else { "objects" };
+        write!(f, "Scan aborted after finding {} {}", self.partial.len(), noun)
+    }
+}
+
+impl std::error::Error for ScanTimedOut {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// How long a timed-out scan may take to stop and hand back its partial results.
+const SCAN_STOP_GRACE: Duration = Duration::from_secs(2);
+
 /// Trait to abstract registry key operations for mocking.
 pub trait RegistryKey {
     /// Opens a subkey.
@@ -20,6 +217,9 @@ pub trait RegistryKey {
     fn get_sub_key_names(&self) -> Result<Vec<String>>;
     /// Gets the default string value of the key (name = "").
     fn get_value(&self, name: &str) -> Result<String>;
+    /// Returns every value as `(name, data)`, with non-string data rendered as text
+    /// and the default value named "".
+    fn get_values(&self) -> Result<Vec<(String, String)>>;
 }
 
 /// Trait to abstract the source of registry keys (specifically HKCR).
@@ -29,37 +229,217 @@ pub trait RegistryReader {
 
 /// The main entry point for scanning COM objects.
 ///
-/// On Windows, this uses the real registry.
+/// When mock data is installed (see [`crate::mock::install`]), its objects are returned on any platform.
+/// Otherwise, on Windows, this uses the real registry, scanning the view(s) selected by `options.bitness`.
 /// On other platforms, it returns an empty list or error (here, empty for safety).
-pub fn scan_com_objects() -> Result<Vec<ComObject>> {
+/// Nothing is cached; see [`crate::pipeline::scan`] for a cached scan.
+pub fn scan_com_objects(options: &ScanOptions) -> Result<Vec<ComObject>> {
+    scan_com_objects_with_progress(options, &mut |_, _| {})
+}
+
+/// Like [`scan_com_objects`], but reports `(processed, total)` registry keys as it goes.
+///
+/// When both views are scanned, counts accumulate across them, so `total` grows
+/// once the second view starts.
+#[tracing::instrument(level = "info", skip_all, fields(bitness = ?options.bitness))]
+pub fn scan_com_objects_with_progress(
+    options: &ScanOptions,
+    progress: &mut dyn FnMut(usize, usize),
+) -> Result<Vec<ComObject>> {
+    let mut objects = scan_views(options, &AtomicBool::new(false), progress)?;
+    if options.check_orphans {
+        mark_orphans(&mut objects);
+    }
+    Ok(objects)
+}
+
+/// Like [`scan_com_objects_with_progress`], but fails with [`ScanTimedOut`] once `budget` has elapsed.
+#[tracing::instrument(level = "info", skip_all, fields(bitness = ?options.bitness, ?budget))]
+pub fn scan_com_objects_with_timeout(
+    options: &ScanOptions,
+    budget: Duration,
+    progress: &mut dyn FnMut(usize, usize),
+) -> Result<Vec<ComObject>> {
+    let mut objects = scan_views_with_timeout(options, budget, progress)?;
+    if options.check_orphans {
+        mark_orphans(&mut objects);
+    }
+    Ok(objects)
+}
+
+/// Runs [`scan_views`] on a worker thread, stopping it once `budget` has elapsed.
+///
+/// A stopped worker finishes the key it is on and hands back what it found so far; one stuck
+/// inside a registry call for longer than [`SCAN_STOP_GRACE`] is abandoned and nothing is returned.
+fn scan_views_with_timeout(options: &ScanOptions, budget: Duration, progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<ComObject>> {
+    enum Message {
+        Progress(usize, usize),
+        Done(Result<Vec<ComObject>>),
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let worker_options = options.clone();
+    let worker_cancel = Arc::clone(&cancel);
+    std::thread::Builder::new()
+        .name("registry-scan".to_string())
+        .spawn(move || {
+            let progress_tx = tx.clone();
+            let result = scan_views(&worker_options, &worker_cancel, &mut |processed, total| {
+                let _ = progress_tx.send(Message::Progress(processed, total));
+            });
+            let _ = tx.send(Message::Done(result));
+        })
+        .context("Failed to start the scan thread")?;
+
+    let deadline = Instant::now() + budget;
+    loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Message::Progress(processed, total)) => progress(processed, total),
+            Ok(Message::Done(result)) => return result,
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(InspectError::Generic("The scan thread stopped unexpectedly".to_string()).into());
+            }
+        }
+    }
+
+    tracing::warn!(?budget, "Scan timed out; stopping it");
+    cancel.store(true, Ordering::Relaxed);
+    let grace = Instant::now() + SCAN_STOP_GRACE;
+    let partial = loop {
+        match rx.recv_timeout(grace.saturating_duration_since(Instant::now())) {
+            Ok(Message::Progress(..)) => continue,
+            Ok(Message::Done(result)) => break result.unwrap_or_default(),
+            Err(_) => break Vec::new(),
+        }
+    };
+    Err(ScanTimedOut::new(partial, budget).into())
+}
+
+/// Scans the registry view(s) selected by `options.bitness`, merging them when both are scanned.
+///
+/// Once `cancel` is set, the scan stops at the next key and returns what it has found.
+fn scan_views(options: &ScanOptions, cancel: &AtomicBool, progress: &mut dyn FnMut(usize, usize)) -> Result<Vec<ComObject>> {
+    if let Some(mock) = crate::mock::active() {
+        progress(mock.objects.len(), mock.objects.len());
+        return Ok(mock.objects.clone());
+    }
     #[cfg(windows)]
     {
-        let reader = windows_impl::WindowsRegistryReader;
-        scan_com_objects_internal(&reader)
+        let mut offset = 0;
+        let mut scan_view = |view: Bitness| -> Result<Vec<ComObject>> {
+            let reader = windows_impl::WindowsRegistryReader::new(view);
+            let mut view_total = 0;
+            let base = offset;
+            let mut objects = scan_com_objects_internal(&reader, options, cancel, &mut |processed, total| {
+                view_total = total;
+                progress(base + processed, base + total);
+            })?;
+            offset += view_total;
+            objects.iter_mut().for_each(|obj| obj.bitness = view);
+            tracing::debug!(?view, count = objects.len(), "Scanned registry view");
+            Ok(objects)
+        };
+
+        match options.bitness {
+            Bitness::Both => {
+                let native = scan_view(Bitness::Native)?;
+                if cancel.load(Ordering::Relaxed) {
+                    return Ok(native);
+                }
+                let x86 = scan_view(Bitness::X86)?;
+                Ok(merge_views(native, x86))
+            }
+            view => scan_view(view),
+        }
     }
     #[cfg(not(windows))]
     {
         // Graceful handling for non-Windows environments
+        let _ = (options, cancel, progress);
         Ok(Vec::new())
     }
 }
 
+// --- Scan Cache ---
+
+/// Returns the cache file for a registry view under the user's cache directory.
+pub fn default_cache_path(bitness: Bitness) -> Option<PathBuf> {
+    let file_name = format!("scan_cache_{:?}.json", bitness).to_lowercase();
+    dirs::cache_dir().map(|dir| dir.join("rustcom_explorer").join(file_name))
+}
+
+/// Serializes scan results to `path` as JSON, creating parent directories as needed.
+pub fn save_cache(objects: &[ComObject], path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create cache directory '{}'", parent.display()))?;
+    }
+    let json = serde_json::to_string(objects).context("Failed to serialize scan cache")?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write scan cache '{}'", path.display()))
+}
+
+/// Loads scan results previously written by [`save_cache`].
+pub fn load_cache(path: &Path) -> Result<Vec<ComObject>> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read scan cache '{}'", path.display()))?;
+    serde_json::from_str(&json).context("Scan cache is corrupt or from an incompatible version")
+}
+
+/// Checks whether the cache file exists and was written less than `ttl` ago.
+pub fn is_cache_fresh(path: &Path, ttl: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < ttl)
+}
+
 /// Internal scanning logic using the RegistryReader trait.
 /// 
 /// Iterates over HKEY_CLASSES_ROOT subkeys.
 /// Filters for keys that have a "CLSID" subkey.
 /// Extracts ProgID (key name), CLSID (default value of CLSID subkey),
 /// and Description (default value of the key itself).
-fn scan_com_objects_internal(reader: &impl RegistryReader) -> Result<Vec<ComObject>> {
+/// A second pass over HKEY_CLASSES_ROOT\CLSID picks up servers without a ProgID,
+/// unless `options.include_clsid_branch` is off.
+///
+/// `progress` is called with `(processed, total)` after each key across both passes; both
+/// passes stop early once `cancel` is set.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn scan_com_objects_internal(
+    reader: &impl RegistryReader,
+    options: &ScanOptions,
+    cancel: &AtomicBool,
+    progress: &mut dyn FnMut(usize, usize),
+) -> Result<Vec<ComObject>> {
     let root = reader.get_classes_root().context("Failed to open HKEY_CLASSES_ROOT")?;
-    let mut objects = Vec::new();
+    let clsid_root = root.open_subkey("CLSID").ok();
+    let appid_root = root.open_subkey("AppID").ok();
+    let mut objects: Vec<ComObject> = Vec::new();
     
     // We get all subkey names first.
     // In a real optimized scenario with millions of keys, we might prefer an iterator,
     // but Vec<String> is sufficient for standard HKCR sizes (~10-100k entries).
     let keys = root.get_sub_key_names().context("Failed to enumerate subkeys")?;
+    let clsids = match &clsid_root {
+        Some(clsid_root) if options.include_clsid_branch => {
+            clsid_root.get_sub_key_names().context("Failed to enumerate CLSID subkeys")?
+        }
+        _ => Vec::new(),
+    };
+    let total = keys.len() + clsids.len();
+    // Upper-cased CLSID to its index in `objects`
+    let mut by_clsid: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+    for (index, name) in keys.into_iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(objects);
+        }
+        progress(index + 1, total);
 
-    for name in keys {
         // Filter: Check if "CLSID" subkey exists.
         // Logic: Open HKCR\<name>. Then try to open "CLSID".
         
@@ -76,18 +456,670 @@ fn scan_com_objects_internal(reader: &impl RegistryReader) -> Result<Vec<ComObje
                 // Description is the default value of the ProgID key
                 let description_val = progid_key.get_value("").unwrap_or_default();
 
-                objects.push(ComObject {
-                    name, // The ProgID is the key name itself
+                // Versioned and version-independent ProgIDs of one CLSID share an object
+                if let Some(&existing) = by_clsid.get(&clsid_val.to_uppercase()) {
+                    let obj = &mut objects[existing];
+                    add_progid(&mut obj.progids, name);
+                    if obj.description.is_empty() {
+                        obj.description = description_val;
+                    }
+                    continue;
+                }
+
+                let mut obj = ComObject {
+                    name: name.clone(), // The ProgID is the key name itself
                     clsid: clsid_val,
                     description: description_val,
-                });
+                    has_progid: true,
+                    ..Default::default()
+                };
+
+                // Step 4: Enrich from HKCR\CLSID\{clsid}
+                if let Some(clsid_root) = &clsid_root
+                    && let Ok(class_key) = clsid_root.open_subkey(&obj.clsid) {
+                        read_class_details(class_key.as_ref(), appid_root.as_deref(), options, &mut obj);
+                    }
+                add_progid(&mut obj.progids, name);
+                // The registered names are authoritative; the key name is a fallback
+                obj.name = obj.progids[0].clone();
+
+                by_clsid.insert(obj.clsid.to_uppercase(), objects.len());
+                objects.push(obj);
             }
         }
     }
 
+    if let Some(clsid_root) = &clsid_root
+        && options.include_clsid_branch
+    {
+        let offset = total - clsids.len();
+        scan_clsid_branch(clsid_root.as_ref(), appid_root.as_deref(), options, cancel, clsids, &mut objects, &mut |processed| {
+            progress(offset + processed, total)
+        });
+    }
+
     Ok(objects)
 }
 
+/// Merges the native and 32-bit scan results, deduplicating by ProgID and CLSID.
+///
+/// An object registered with a server in both views is tagged `Bitness::Both`.
+/// When only the 32-bit view has a server, the 32-bit entry replaces the native one.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn merge_views(native: Vec<ComObject>, x86: Vec<ComObject>) -> Vec<ComObject> {
+    let key = |obj: &ComObject| (obj.name.clone(), obj.clsid.to_uppercase());
+    let mut merged = native;
+    let index: std::collections::HashMap<(String, String), usize> =
+        merged.iter().enumerate().map(|(i, obj)| (key(obj), i)).collect();
+
+    for obj in x86 {
+        match index.get(&key(&obj)) {
+            Some(&i) => {
+                let existing = &mut merged[i];
+                if existing.server_kind == ServerKind::None {
+                    if obj.server_kind != ServerKind::None {
+                        *existing = obj;
+                    }
+                } else if obj.server_kind != ServerKind::None {
+                    existing.bitness = Bitness::Both;
+                }
+            }
+            None => merged.push(obj),
+        }
+    }
+
+    merged
+}
+
+/// Fills in server registration details from an opened `CLSID\{clsid}` key.
+/// In-process servers also report their threading model, an `AppID` value
+/// is resolved against `appid_root` (HKCR\AppID), and the TypeLib and
+/// Implemented Categories are collected.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn read_class_details(
+    class_key: &dyn RegistryKey,
+    appid_root: Option<&dyn RegistryKey>,
+    options: &ScanOptions,
+    obj: &mut ComObject,
+) {
+    // The version-independent name goes first so it becomes the primary name
+    for subkey in ["VersionIndependentProgID", "ProgID"] {
+        if let Ok(progid) = class_key.open_subkey(subkey).and_then(|key| key.get_value(""))
+            && !progid.is_empty()
+        {
+            add_progid(&mut obj.progids, progid);
+        }
+    }
+
+    if let Ok(app_id) = class_key.get_value("AppID")
+        && !app_id.is_empty() {
+            obj.app_id = Some(read_app_id(appid_root, app_id));
+        }
+
+    obj.typelib = class_key
+        .open_subkey("TypeLib")
+        .and_then(|key| key.get_value(""))
+        .ok()
+        .filter(|guid| !guid.is_empty());
+
+    if let Ok(categories_key) = class_key.open_subkey("Implemented Categories") {
+        obj.categories = categories_key
+            .get_sub_key_names()
+            .unwrap_or_default()
+            .iter()
+            .map(|catid| category_name(catid))
+            .collect();
+    }
+
+    for (subkey, kind) in [("InprocServer32", ServerKind::InProc), ("LocalServer32", ServerKind::Local)] {
+        if let Ok(server_key) = class_key.open_subkey(subkey) {
+            obj.server_kind = kind;
+            obj.server_path = server_key
+                .get_value("")
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(|path| {
+                    if options.resolve_server_paths {
+                        expand_env_vars(&path, |var| std::env::var(var).ok())
+                    } else {
+                        path
+                    }
+                });
+
+            // A missing ThreadingModel means the legacy single-threaded model
+            if kind == ServerKind::InProc {
+                let model = server_key.get_value("ThreadingModel").unwrap_or_default();
+                obj.threading_model = Some(if model.is_empty() { "Single".to_string() } else { model });
+            }
+            return;
+        }
+    }
+}
+
+/// Appends a ProgID unless it is already listed; ProgIDs compare case-insensitively.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn add_progid(progids: &mut Vec<String>, progid: String) {
+    if !progids.iter().any(|known| known.eq_ignore_ascii_case(&progid)) {
+        progids.push(progid);
+    }
+}
+
+/// Reads the DCOM settings for an AppID. Missing keys yield an info with only the GUID set.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn read_app_id(appid_root: Option<&dyn RegistryKey>, app_id: String) -> AppIdInfo {
+    let app_key = appid_root.and_then(|root| root.open_subkey(&app_id).ok());
+    let value = |name: &str| app_key.as_ref().and_then(|key| key.get_value(name).ok());
+
+    AppIdInfo {
+        local_service: value("LocalService"),
+        run_as: value("RunAs"),
+        dll_surrogate: value("DllSurrogate"),
+        app_id,
+    }
+}
+
+/// Expands `%VAR%` references the way `ExpandEnvironmentStrings` does.
+///
+/// Unknown variables and unmatched `%` signs are left untouched.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn expand_env_vars(input: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        match after.find('%') {
+            Some(end) => {
+                let var = &after[..end];
+                match lookup(var).filter(|_| !var.is_empty()) {
+                    Some(value) => {
+                        result.push_str(&value);
+                        rest = &after[end + 1..];
+                    }
+                    None => {
+                        // Keep the literal and let the closing '%' start the next match
+                        result.push('%');
+                        result.push_str(var);
+                        rest = &after[end..];
+                    }
+                }
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Second pass: walks the HKCR\CLSID subkeys for registered servers that no ProgID points at.
+///
+/// Only keys with an `InprocServer32` or `LocalServer32` subkey are kept, and CLSIDs
+/// already found via a ProgID are skipped (compared case-insensitively).
+#[cfg_attr(not(windows), allow(dead_code))]
+fn scan_clsid_branch(
+    clsid_root: &dyn RegistryKey,
+    appid_root: Option<&dyn RegistryKey>,
+    options: &ScanOptions,
+    cancel: &AtomicBool,
+    clsids: Vec<String>,
+    objects: &mut Vec<ComObject>,
+    progress: &mut dyn FnMut(usize),
+) {
+    let mut known: HashSet<String> = objects.iter().map(|o| o.clsid.to_uppercase()).collect();
+
+    for (index, clsid) in clsids.into_iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        progress(index + 1);
+
+        if known.contains(&clsid.to_uppercase()) {
+            continue;
+        }
+
+        let Ok(clsid_key) = clsid_root.open_subkey(&clsid) else {
+            continue;
+        };
+
+        let mut obj = ComObject::default();
+        read_class_details(clsid_key.as_ref(), appid_root, options, &mut obj);
+        if obj.server_kind == ServerKind::None {
+            continue;
+        }
+
+        // Prefer a registered ProgID, then the default value (the friendly name), then the CLSID itself
+        let name = clsid_key.get_value("").unwrap_or_default();
+        obj.name = match obj.progids.first() {
+            Some(progid) => progid.clone(),
+            None if name.is_empty() => clsid.clone(),
+            None => name,
+        };
+
+        known.insert(clsid.to_uppercase());
+        obj.clsid = clsid;
+        objects.push(obj);
+    }
+}
+
+/// Watches HKEY_CLASSES_ROOT for changes to keys or values.
+///
+/// The notification is armed on creation and re-armed as soon as [`wait`](Self::wait) returns,
+/// so a change made while the caller is still rescanning wakes the next `wait`.
+/// Not supported on non-Windows platforms, where [`new`](Self::new) returns an error.
+pub struct RegistryWatcher {
+    #[cfg(windows)]
+    event: windows::Win32::Foundation::HANDLE,
+}
+
+impl RegistryWatcher {
+    pub fn new() -> Result<Self> {
+        #[cfg(windows)]
+        {
+            use windows::Win32::System::Threading::CreateEventW;
+            // Auto-reset, so each wait consumes the changes signalled so far
+            let event = unsafe { CreateEventW(None, false, false, None) }
+                .map_err(|e| InspectError::Registry(format!("Failed to create a registry change event: {}", e.message())))?;
+            let watcher = Self { event };
+            watcher.arm()?;
+            Ok(watcher)
+        }
+        #[cfg(not(windows))]
+        {
+            Err(InspectError::Registry("Watching the registry is not supported on this platform".to_string()).into())
+        }
+    }
+
+    /// Blocks until something under HKEY_CLASSES_ROOT changes, then re-arms the notification.
+    pub fn wait(&self) -> Result<()> {
+        #[cfg(windows)]
+        {
+            use windows::Win32::Foundation::WAIT_OBJECT_0;
+            use windows::Win32::System::Threading::{INFINITE, WaitForSingleObject};
+            if unsafe { WaitForSingleObject(self.event, INFINITE) } != WAIT_OBJECT_0 {
+                return Err(InspectError::Registry("Failed to wait for a registry change".to_string()).into());
+            }
+            self.arm()
+        }
+        #[cfg(not(windows))]
+        {
+            Err(InspectError::Registry("Watching the registry is not supported on this platform".to_string()).into())
+        }
+    }
+
+    /// Requests a one-shot signal of `event` on the next change. The request lives as long as the calling thread.
+    #[cfg(windows)]
+    fn arm(&self) -> Result<()> {
+        use windows::Win32::System::Registry::{
+            HKEY_CLASSES_ROOT, REG_NOTIFY_CHANGE_LAST_SET, REG_NOTIFY_CHANGE_NAME, RegNotifyChangeKeyValue,
+        };
+        unsafe {
+            RegNotifyChangeKeyValue(
+                HKEY_CLASSES_ROOT,
+                true,
+                REG_NOTIFY_CHANGE_NAME | REG_NOTIFY_CHANGE_LAST_SET,
+                Some(self.event),
+                true,
+            )
+        }
+        .ok()
+        .map_err(|e| InspectError::Registry(format!("Failed to watch HKEY_CLASSES_ROOT: {}", e.message())).into())
+    }
+}
+
+#[cfg(windows)]
+impl Drop for RegistryWatcher {
+    fn drop(&mut self) {
+        let _ = unsafe { windows::Win32::Foundation::CloseHandle(self.event) };
+    }
+}
+
+/// Resolves user input to a braced CLSID string.
+///
+/// Accepts either a CLSID (with or without braces) or a ProgID, which is looked up
+/// with [`clsid_for_progid`].
+pub fn resolve_clsid(input: &str) -> Result<String> {
+    resolve_clsid_with(input, clsid_for_progid)
+}
+
+/// Resolution logic shared by [`resolve_clsid`] and its tests.
+///
+/// Braced input is returned unchanged when it holds a GUID and rejected otherwise, before any
+/// registry or COM call sees it. Otherwise `lookup` resolves the input as a ProgID, and a bare
+/// GUID falls back to being wrapped in braces.
+fn resolve_clsid_with(input: &str, lookup: impl FnOnce(&str) -> Result<String>) -> Result<String> {
+    let input = input.trim();
+    if let Some(rest) = input.strip_prefix('{') {
+        return match rest.strip_suffix('}') {
+            Some(bare) if is_bare_guid(bare) => Ok(input.to_string()),
+            _ => Err(InspectError::Generic(format!("invalid CLSID: '{}'", input)).into()),
+        };
+    }
+
+    match lookup(input) {
+        Ok(clsid) => Ok(clsid),
+        Err(_) if is_bare_guid(input) => Ok(format!("{{{}}}", input)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Looks up the CLSID registered for a ProgID (`HKCR\<progid>\CLSID`), or among the
+/// objects of installed mock data.
+pub fn clsid_for_progid(progid: &str) -> Result<String> {
+    if let Some(mock) = crate::mock::active() {
+        return mock
+            .objects
+            .iter()
+            .find(|obj| obj.has_progid && obj.name.eq_ignore_ascii_case(progid))
+            .map(|obj| obj.clsid.clone())
+            .ok_or_else(|| InspectError::Registry(format!("No COM object registered as '{}'", progid)).into());
+    }
+    #[cfg(windows)]
+    {
+        let reader = windows_impl::WindowsRegistryReader::new(Bitness::Native);
+        clsid_for_progid_internal(&reader, progid)
+    }
+    #[cfg(not(windows))]
+    {
+        Err(InspectError::Registry(format!("Cannot resolve '{}' on this platform", progid)).into())
+    }
+}
+
+/// Looks up the ProgID registered for a CLSID (`HKCR\CLSID\{clsid}\ProgID`), or among the
+/// objects of installed mock data. Braces around `clsid` are optional.
+pub fn progid_for_clsid(clsid: &str) -> Result<String> {
+    if let Some(mock) = crate::mock::active() {
+        let braced = braced_clsid(clsid);
+        return mock
+            .objects
+            .iter()
+            .find(|obj| obj.has_progid && obj.clsid.eq_ignore_ascii_case(&braced))
+            .map(|obj| obj.name.clone())
+            .ok_or_else(|| InspectError::Registry(format!("No ProgID registered for {}", braced)).into());
+    }
+    #[cfg(windows)]
+    {
+        let reader = windows_impl::WindowsRegistryReader::new(Bitness::Native);
+        progid_for_clsid_internal(&reader, clsid)
+    }
+    #[cfg(not(windows))]
+    {
+        Err(InspectError::Registry(format!("Cannot resolve '{}' on this platform", clsid)).into())
+    }
+}
+
+/// Internal ProgID lookup using the RegistryReader trait.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn clsid_for_progid_internal(reader: &impl RegistryReader, progid: &str) -> Result<String> {
+    let root = reader.get_classes_root().context("Failed to open HKEY_CLASSES_ROOT")?;
+    root.open_subkey(progid.trim())
+        .and_then(|progid_key| progid_key.open_subkey("CLSID"))
+        .and_then(|clsid_key| clsid_key.get_value(""))
+        .ok()
+        .filter(|clsid| !clsid.is_empty())
+        .ok_or_else(|| InspectError::Registry(format!("No COM object registered as '{}'", progid.trim())).into())
+}
+
+/// Internal CLSID lookup using the RegistryReader trait.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn progid_for_clsid_internal(reader: &impl RegistryReader, clsid: &str) -> Result<String> {
+    let braced = braced_clsid(clsid);
+    let root = reader.get_classes_root().context("Failed to open HKEY_CLASSES_ROOT")?;
+    root.open_subkey("CLSID")
+        .and_then(|clsid_root| clsid_root.open_subkey(&braced))
+        .and_then(|class_key| class_key.open_subkey("ProgID"))
+        .and_then(|progid_key| progid_key.get_value(""))
+        .ok()
+        .filter(|progid| !progid.is_empty())
+        .ok_or_else(|| InspectError::Registry(format!("No ProgID registered for {}", braced)).into())
+}
+
+/// Wraps a CLSID in braces unless it already has them.
+fn braced_clsid(clsid: &str) -> String {
+    let clsid = clsid.trim();
+    if clsid.starts_with('{') { clsid.to_string() } else { format!("{{{}}}", clsid) }
+}
+
+/// Extracts the binary from a server registration, dropping quotes and command-line
+/// arguments such as `"C:\Program Files\app.exe" /automation`.
+pub fn server_binary(server_path: &str) -> &str {
+    let trimmed = server_path.trim();
+    if let Some(quoted) = trimmed.strip_prefix('"') {
+        return quoted.split('"').next().unwrap_or(quoted);
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    [".exe", ".dll", ".ocx"]
+        .iter()
+        .filter_map(|ext| lower.find(ext).map(|idx| idx + ext.len()))
+        .min()
+        .map_or(trimmed, |end| &trimmed[..end])
+}
+
+/// Flags objects whose server binary is missing, e.g. left behind by an uninstaller.
+///
+/// Objects without a server path are never flagged.
+pub fn mark_orphans(objects: &mut [ComObject]) {
+    let env = |var: &str| std::env::var(var).ok();
+    for obj in objects {
+        obj.orphaned = obj
+            .server_path
+            .as_deref()
+            .is_some_and(|path| !server_exists(path, env, |candidate| candidate.exists()));
+    }
+}
+
+/// Flags objects whose TypeLib GUID is registered under `HKCR\TypeLib` in either registry view.
+///
+/// This only probes registry keys; nothing is loaded or instantiated. With mock data, every
+/// object that names a TypeLib counts as registered.
+pub fn mark_registered_typelibs(objects: &mut [ComObject]) {
+    if crate::mock::active().is_some() {
+        for obj in objects {
+            obj.typelib_registered = obj.typelib.is_some();
+        }
+        return;
+    }
+    #[cfg(windows)]
+    {
+        let mut registered = HashSet::new();
+        for view in [Bitness::Native, Bitness::X86] {
+            registered.extend(registered_typelibs(&windows_impl::WindowsRegistryReader::new(view)));
+        }
+        mark_registered_typelibs_with(objects, &registered);
+    }
+    #[cfg(not(windows))]
+    {
+        mark_registered_typelibs_with(objects, &HashSet::new());
+    }
+}
+
+/// Upper-cased GUIDs of the type libraries registered under `HKCR\TypeLib`; empty if the key is unreadable.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn registered_typelibs(reader: &impl RegistryReader) -> HashSet<String> {
+    reader
+        .get_classes_root()
+        .and_then(|root| root.open_subkey("TypeLib"))
+        .and_then(|typelibs| typelibs.get_sub_key_names())
+        .map(|guids| guids.into_iter().map(|guid| guid.to_uppercase()).collect())
+        .unwrap_or_default()
+}
+
+/// Whether `obj`'s type library is registered under `HKCR\TypeLib` in either registry view.
+///
+/// Opens only that key, so it is cheap enough for the UI thread; it does not check that the library loads.
+pub fn has_registered_typelib(obj: &ComObject) -> bool {
+    let Some(guid) = obj.typelib.as_deref() else {
+        return false;
+    };
+    if crate::mock::active().is_some() {
+        return true;
+    }
+    #[cfg(windows)]
+    {
+        [Bitness::Native, Bitness::X86]
+            .into_iter()
+            .any(|view| typelib_key_exists(&windows_impl::WindowsRegistryReader::new(view), guid))
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = guid;
+        false
+    }
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn typelib_key_exists(reader: &impl RegistryReader, guid: &str) -> bool {
+    reader
+        .get_classes_root()
+        .and_then(|root| root.open_subkey("TypeLib"))
+        .and_then(|typelibs| typelibs.open_subkey(guid))
+        .is_ok()
+}
+
+fn mark_registered_typelibs_with(objects: &mut [ComObject], registered: &HashSet<String>) {
+    for obj in objects {
+        obj.typelib_registered = obj.typelib.as_ref().is_some_and(|guid| registered.contains(&guid.to_uppercase()));
+    }
+}
+
+/// Whether the binary of a server registration exists.
+///
+/// Bare file names such as `scrrun.dll` are searched for like the loader does:
+/// in `%SystemRoot%\System32`, `%SystemRoot%` and the `PATH` directories.
+fn server_exists(server_path: &str, env: impl Fn(&str) -> Option<String>, exists: impl Fn(&Path) -> bool) -> bool {
+    let binary = Path::new(server_binary(server_path));
+    if binary.is_absolute() || binary.parent().is_some_and(|parent| !parent.as_os_str().is_empty()) {
+        return exists(binary);
+    }
+
+    let mut dirs = Vec::new();
+    if let Some(system_root) = env("SystemRoot") {
+        dirs.push(Path::new(&system_root).join("System32"));
+        dirs.push(PathBuf::from(system_root));
+    }
+    if let Some(path) = env("PATH") {
+        dirs.extend(path.split(';').filter(|dir| !dir.is_empty()).map(PathBuf::from));
+    }
+    dirs.iter().any(|dir| exists(&dir.join(binary)))
+}
+
+/// Opens Windows Explorer on the folder containing `server_path`, with the file selected.
+///
+/// Fails with a descriptive error when the binary does not exist.
+pub fn reveal_in_explorer(server_path: &str) -> Result<()> {
+    let binary = server_binary(server_path);
+    if !Path::new(binary).exists() {
+        return Err(InspectError::Generic(format!("'{}' does not exist", binary)).into());
+    }
+
+    #[cfg(windows)]
+    {
+        use windows::Win32::UI::Shell::ShellExecuteW;
+        use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+        use windows::core::{HSTRING, w};
+
+        let parameters = HSTRING::from(format!("/select,\"{}\"", binary));
+        let result = unsafe { ShellExecuteW(None, w!("open"), w!("explorer.exe"), &parameters, None, SW_SHOWNORMAL) };
+        // Values above 32 indicate success
+        if result.0 as isize <= 32 {
+            return Err(InspectError::Generic(format!("Failed to open Explorer (code {})", result.0 as isize)).into());
+        }
+        Ok(())
+    }
+    #[cfg(not(windows))]
+    {
+        Err(InspectError::Generic("Opening Explorer is not supported on this platform".to_string()).into())
+    }
+}
+
+/// Checks for the 8-4-4-4-12 hex layout of an unbraced GUID.
+pub(crate) fn is_bare_guid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(g, len)| {
+            g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit())
+        })
+}
+
+// --- Raw Registry View ---
+
+/// One registry key with its values and subkeys, as read by [`read_clsid_tree`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegistryNode {
+    pub name: String,
+    /// `(name, data)` pairs sorted by name; the default value is named ""
+    pub values: Vec<(String, String)>,
+    /// Subkeys sorted by name, case-insensitively
+    pub children: Vec<RegistryNode>,
+    /// Why the key could not be read completely
+    pub error: Option<String>,
+}
+
+/// Levels of subkeys read below `CLSID\{clsid}`, which rarely nests more than two deep.
+#[cfg_attr(not(windows), allow(dead_code))]
+const REGISTRY_TREE_DEPTH: usize = 6;
+
+/// Reads the raw `HKCR\CLSID\{clsid}` subtree, every subkey and value included, from the
+/// registry view selected by `bitness` (the native view for `Both`).
+pub fn read_clsid_tree(clsid: &str, bitness: Bitness) -> Result<RegistryNode> {
+    if crate::mock::active().is_some() {
+        return Err(InspectError::Registry("Mock data has no raw registry keys".to_string()).into());
+    }
+    #[cfg(windows)]
+    {
+        let reader = windows_impl::WindowsRegistryReader::new(bitness);
+        read_clsid_tree_internal(&reader, clsid)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = bitness;
+        Err(InspectError::Registry(format!("Cannot read the registry key of '{}' on this platform", clsid)).into())
+    }
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn read_clsid_tree_internal(reader: &impl RegistryReader, clsid: &str) -> Result<RegistryNode> {
+    let path = format!("HKCR\\CLSID\\{}", braced_clsid(clsid));
+    let key = reader
+        .get_classes_root()?
+        .open_subkey("CLSID")
+        .and_then(|clsid_root| clsid_root.open_subkey(&braced_clsid(clsid)))
+        .with_context(|| format!("Failed to open {}", path))?;
+    Ok(read_registry_node(key.as_ref(), path, REGISTRY_TREE_DEPTH))
+}
+
+/// Reads a key's values and, while `depth` allows, its subkeys; unreadable parts are noted in `error`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn read_registry_node(key: &dyn RegistryKey, name: String, depth: usize) -> RegistryNode {
+    let mut node = RegistryNode { name, ..Default::default() };
+    let subkeys = key.get_values().and_then(|values| {
+        node.values = values;
+        node.values.sort();
+        if depth == 0 { Ok(Vec::new()) } else { key.get_sub_key_names() }
+    });
+    let mut subkeys = match subkeys {
+        Ok(subkeys) => subkeys,
+        Err(e) => {
+            node.error = Some(format!("{:#}", e));
+            return node;
+        }
+    };
+
+    subkeys.sort_by_key(|name| name.to_lowercase());
+    for subkey in subkeys {
+        node.children.push(match key.open_subkey(&subkey) {
+            Ok(child) => read_registry_node(child.as_ref(), subkey, depth - 1),
+            Err(e) => RegistryNode { name: subkey, error: Some(format!("{:#}", e)), ..Default::default() },
+        });
+    }
+    node
+}
+
 // --- Windows Implementation ---
 
 #[cfg(windows)]
@@ -96,34 +1128,59 @@ mod windows_impl {
     use winreg::RegKey;
     use winreg::enums::*;
 
-    pub struct WindowsRegistryReader;
+    /// Reads HKCR through a specific registry view.
+    pub struct WindowsRegistryReader {
+        view_flags: u32,
+    }
+
+    impl WindowsRegistryReader {
+        /// `Bitness::X86` redirects into `Wow6432Node`; any other value uses the native view.
+        pub fn new(view: Bitness) -> Self {
+            let view_flags = if view == Bitness::X86 { KEY_WOW64_32KEY } else { 0 };
+            Self { view_flags }
+        }
+    }
 
     impl RegistryReader for WindowsRegistryReader {
         fn get_classes_root(&self) -> Result<Box<dyn RegistryKey>> {
             let key = RegKey::predef(HKEY_CLASSES_ROOT);
-            Ok(Box::new(WindowsKey(key)))
+            Ok(Box::new(WindowsKey(key, self.view_flags)))
         }
     }
 
-    struct WindowsKey(RegKey);
+    /// A registry key plus the WOW64 view flags inherited by its subkeys.
+    struct WindowsKey(RegKey, u32);
 
     impl RegistryKey for WindowsKey {
         fn open_subkey(&self, name: &str) -> Result<Box<dyn RegistryKey>> {
-            // open_subkey_with_flags is often safer/more precise, but open_subkey is standard read
-            let key = self.0.open_subkey(name).map_err(crate::error_handling::Error::from)?;
-            Ok(Box::new(WindowsKey(key)))
+            // The view flag must be passed on every open for WOW64 redirection to apply
+            let key = self.0
+                .open_subkey_with_flags(name, KEY_READ | self.1)
+                .map_err(|e| InspectError::from_io(format!("Failed to open key '{}'", name), e))?;
+            Ok(Box::new(WindowsKey(key, self.1)))
         }
 
         fn get_sub_key_names(&self) -> Result<Vec<String>> {
             let mut names = Vec::new();
             for name in self.0.enum_keys() {
-                names.push(name.map_err(crate::error_handling::Error::from)?);
+                names.push(name.map_err(|e| InspectError::from_io("Failed to enumerate subkeys", e))?);
             }
             Ok(names)
         }
 
         fn get_value(&self, name: &str) -> Result<String> {
-            self.0.get_value(name).map_err(crate::error_handling::Error::from)
+            self.0
+                .get_value(name)
+                .map_err(|e| InspectError::from_io(format!("Failed to read value '{}'", name), e).into())
+        }
+
+        fn get_values(&self) -> Result<Vec<(String, String)>> {
+            let mut values = Vec::new();
+            for value in self.0.enum_values() {
+                let (name, data) = value.map_err(|e| InspectError::from_io("Failed to enumerate values", e))?;
+                values.push((name, data.to_string()));
+            }
+            Ok(values)
         }
     }
 }
@@ -141,6 +1198,8 @@ mod tests {
     struct MockKey {
         subkeys: Arc<Mutex<HashMap<String, MockKey>>>,
         values: Arc<Mutex<HashMap<String, String>>>,
+        /// Simulates a key the current token may not read
+        access_denied: bool,
     }
 
     impl MockKey {
@@ -148,9 +1207,22 @@ mod tests {
             Self {
                 subkeys: Arc::new(Mutex::new(HashMap::new())),
                 values: Arc::new(Mutex::new(HashMap::new())),
+                access_denied: false,
             }
         }
 
+        fn denied() -> Self {
+            Self { access_denied: true, ..Self::new() }
+        }
+
+        fn check_access(&self) -> Result<()> {
+            if self.access_denied {
+                let err = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+                return Err(InspectError::from_io("Mock key", err).into());
+            }
+            Ok(())
+        }
+
         fn add_subkey(&self, name: &str, key: MockKey) {
             self.subkeys.lock().unwrap().insert(name.to_string(), key);
         }
@@ -162,8 +1234,13 @@ mod tests {
 
     impl RegistryKey for MockKey {
         fn open_subkey(&self, name: &str) -> Result<Box<dyn RegistryKey>> {
+            self.check_access()?;
             let map = self.subkeys.lock().unwrap();
-            if let Some(key) = map.get(name) {
+            // Like the registry, key names match case-insensitively, preferring an exact match
+            let key = map
+                .get(name)
+                .or_else(|| map.iter().find(|(known, _)| known.eq_ignore_ascii_case(name)).map(|(_, key)| key));
+            if let Some(key) = key {
                 Ok(Box::new(key.clone()))
             } else {
                 Err(anyhow::anyhow!("Key not found"))
@@ -171,14 +1248,22 @@ mod tests {
         }
 
         fn get_sub_key_names(&self) -> Result<Vec<String>> {
+            self.check_access()?;
             let map = self.subkeys.lock().unwrap();
             Ok(map.keys().cloned().collect())
         }
 
         fn get_value(&self, name: &str) -> Result<String> {
+            self.check_access()?;
             let map = self.values.lock().unwrap();
             map.get(name).cloned().ok_or_else(|| anyhow::anyhow!("Value not found"))
         }
+
+        fn get_values(&self) -> Result<Vec<(String, String)>> {
+            self.check_access()?;
+            let map = self.values.lock().unwrap();
+            Ok(map.iter().map(|(name, data)| (name.clone(), data.clone())).collect())
+        }
     }
 
     struct MockReader {
@@ -220,7 +1305,7 @@ mod tests {
         let reader = MockReader { root };
 
         // Act
-        let results = scan_com_objects_internal(&reader).expect("Scan failed");
+        let results = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).expect("Scan failed");
 
         // Assert
         assert_eq!(results.len(), 1);
@@ -230,6 +1315,58 @@ mod tests {
         assert_eq!(obj.description, "My Description");
     }
 
+    #[test]
+    fn test_read_clsid_tree_lists_every_subkey_and_value() {
+        let class_key = MockKey::new();
+        class_key.set_value("", "Widget Class");
+        class_key.set_value("AppID", "{B}");
+        let inproc = MockKey::new();
+        inproc.set_value("", "C:\\widget.dll");
+        inproc.set_value("ThreadingModel", "Both");
+        class_key.add_subkey("InprocServer32", inproc);
+        class_key.add_subkey("Secret", MockKey::denied());
+        let clsid_root = MockKey::new();
+        clsid_root.add_subkey("{A}", class_key);
+        let root = MockKey::new();
+        root.add_subkey("CLSID", clsid_root);
+        let reader = MockReader { root };
+
+        let tree = read_clsid_tree_internal(&reader, "A").unwrap();
+        assert_eq!(tree.name, "HKCR\\CLSID\\{A}");
+        assert_eq!(tree.values, vec![("".to_string(), "Widget Class".to_string()), ("AppID".to_string(), "{B}".to_string())]);
+        assert_eq!(tree.children.len(), 2);
+        assert_eq!(tree.children[0].name, "InprocServer32");
+        assert_eq!(tree.children[0].values[1], ("ThreadingModel".to_string(), "Both".to_string()));
+        assert!(tree.children[1].error.as_deref().is_some_and(|e| e.contains("Permission Denied")));
+
+        assert!(read_clsid_tree_internal(&reader, "{C}").is_err());
+    }
+
+    #[test]
+    fn test_cancelled_scan_returns_objects_found_so_far() {
+        let root = MockKey::new();
+        for (progid, clsid) in [("a.progid", "{A}"), ("b.progid", "{B}")] {
+            let key = MockKey::new();
+            let clsid_key = MockKey::new();
+            clsid_key.set_value("", clsid);
+            key.add_subkey("CLSID", clsid_key);
+            root.add_subkey(progid, key);
+        }
+        let reader = MockReader { root };
+
+        // Stop right after the first key
+        let cancel = AtomicBool::new(false);
+        let results = scan_com_objects_internal(&reader, &ScanOptions::default(), &cancel, &mut |_, _| {
+            cancel.store(true, Ordering::Relaxed);
+        })
+        .unwrap();
+        assert_eq!(results.len(), 1);
+
+        let err = crate::error_handling::Error::from(ScanTimedOut::new(results, Duration::from_secs(30)));
+        assert_eq!(format!("{:#}", err), "Scan aborted after finding 1 object: Error: The registry scan did not finish within 30 seconds");
+        assert_eq!(err.downcast_ref::<ScanTimedOut>().unwrap().partial.len(), 1);
+    }
+
     #[test]
     fn test_scan_handles_missing_description_gracefully() {
         let root = MockKey::new();
@@ -240,10 +1377,424 @@ mod tests {
         root.add_subkey("test.obj", progid);
 
         let reader = MockReader { root };
-        let results = scan_com_objects_internal(&reader).unwrap();
+        let results = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).unwrap();
 
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].description, ""); // Should be empty, not error
         assert_eq!(results[0].clsid, "{GUID}");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_scan_finds_clsid_only_servers() {
+        // HKCR
+        //  |-- known.progid\CLSID      (Default: "{AAA}")
+        //  |-- CLSID
+        //       |-- {AAA}\InprocServer32   (Default: "C:\Windows\known.dll")
+        //       |-- {aaa}\InprocServer32   (duplicate of the ProgID entry)
+        //       |-- {BBB}                (Default: "Hidden Server")
+        //            |-- LocalServer32
+        //       |-- {CCC}                (no server, ignored)
+        let root = MockKey::new();
+
+        let progid = MockKey::new();
+        let progid_clsid = MockKey::new();
+        progid_clsid.set_value("", "{AAA}");
+        progid.add_subkey("CLSID", progid_clsid);
+        root.add_subkey("known.progid", progid);
+
+        let clsid_root = MockKey::new();
+        let dup = MockKey::new();
+        dup.add_subkey("InprocServer32", MockKey::new());
+        clsid_root.add_subkey("{aaa}", dup);
+
+        let known_class = MockKey::new();
+        let typelib = MockKey::new();
+        typelib.set_value("", "{LIB}");
+        known_class.add_subkey("TypeLib", typelib);
+        let inproc = MockKey::new();
+        inproc.set_value("", "C:\\Windows\\known.dll");
+        inproc.set_value("ThreadingModel", "Apartment");
+        known_class.add_subkey("InprocServer32", inproc);
+        clsid_root.add_subkey("{AAA}", known_class);
+
+        let hidden = MockKey::new();
+        hidden.set_value("", "Hidden Server");
+        hidden.add_subkey("LocalServer32", MockKey::new());
+        clsid_root.add_subkey("{BBB}", hidden);
+
+        clsid_root.add_subkey("{CCC}", MockKey::new());
+        root.add_subkey("CLSID", clsid_root);
+
+        let reader = MockReader { root };
+        let mut calls = Vec::new();
+        let mut results = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |processed, total| {
+            calls.push((processed, total))
+        }).unwrap();
+        results.sort_by(|a, b| a.clsid.cmp(&b.clsid));
+
+        // 2 root keys (known.progid, CLSID) + 4 CLSID keys
+        assert_eq!(calls.len(), 6);
+        assert_eq!(calls.last(), Some(&(6, 6)));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "known.progid");
+        assert!(results[0].has_progid);
+        assert_eq!(results[0].server_kind, ServerKind::InProc);
+        assert_eq!(results[0].server_path.as_deref(), Some("C:\\Windows\\known.dll"));
+        assert_eq!(results[0].threading_model.as_deref(), Some("Apartment"));
+        assert_eq!(results[0].typelib.as_deref(), Some("{LIB}"));
+        assert_eq!(results[1].name, "Hidden Server");
+        assert_eq!(results[1].clsid, "{BBB}");
+        assert!(!results[1].has_progid);
+        assert_eq!(results[1].server_kind, ServerKind::Local);
+        assert_eq!(results[1].server_path, None);
+        assert_eq!(results[1].threading_model, None);
+        assert_eq!(results[1].typelib, None);
+    }
+
+    #[test]
+    fn test_scan_merges_progids_of_one_clsid() {
+        // HKCR
+        //  |-- Excel.Application\CLSID      (Default: "{XL}")
+        //  |-- Excel.Application.16\CLSID   (Default: "{xl}")
+        //  |-- CLSID
+        //       |-- {XL}                     ProgID "Excel.Application.16", VersionIndependentProgID "Excel.Application"
+        //       |-- {DOC}                    ProgID "Doc.Viewer.2" (no ProgID key)
+        let root = MockKey::new();
+        for (progid, clsid) in [("Excel.Application", "{XL}"), ("Excel.Application.16", "{xl}")] {
+            let progid_key = MockKey::new();
+            let clsid_key = MockKey::new();
+            clsid_key.set_value("", clsid);
+            progid_key.add_subkey("CLSID", clsid_key);
+            root.add_subkey(progid, progid_key);
+        }
+
+        let named_value = |value: &str| {
+            let key = MockKey::new();
+            key.set_value("", value);
+            key
+        };
+        let clsid_root = MockKey::new();
+        let excel = MockKey::new();
+        excel.add_subkey("ProgID", named_value("Excel.Application.16"));
+        excel.add_subkey("VersionIndependentProgID", named_value("Excel.Application"));
+        excel.add_subkey("LocalServer32", MockKey::new());
+        clsid_root.add_subkey("{XL}", excel);
+        let viewer = MockKey::new();
+        viewer.set_value("", "Document Viewer");
+        viewer.add_subkey("ProgID", named_value("Doc.Viewer.2"));
+        viewer.add_subkey("InprocServer32", MockKey::new());
+        clsid_root.add_subkey("{DOC}", viewer);
+        root.add_subkey("CLSID", clsid_root);
+
+        let reader = MockReader { root };
+        let mut results = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "Doc.Viewer.2");
+        assert_eq!(results[0].progids, vec!["Doc.Viewer.2"]);
+        assert!(!results[0].has_progid);
+        assert_eq!(results[1].name, "Excel.Application");
+        assert_eq!(results[1].progids, vec!["Excel.Application", "Excel.Application.16"]);
+        assert!(results[1].has_progid);
+    }
+
+    #[test]
+    fn test_scan_options_skip_clsid_branch_and_keep_raw_paths() {
+        let named_value = |value: &str| {
+            let key = MockKey::new();
+            key.set_value("", value);
+            key
+        };
+        let root = MockKey::new();
+        let progid = MockKey::new();
+        progid.add_subkey("CLSID", named_value("{AAA}"));
+        root.add_subkey("Widget.App", progid);
+        let clsid_root = MockKey::new();
+        let widget = MockKey::new();
+        widget.add_subkey("InprocServer32", named_value("%PATH%\\widget.dll"));
+        clsid_root.add_subkey("{AAA}", widget);
+        let orphan_class = MockKey::new();
+        orphan_class.add_subkey("LocalServer32", named_value("C:\\orphan.exe"));
+        clsid_root.add_subkey("{BBB}", orphan_class);
+        root.add_subkey("CLSID", clsid_root);
+        let reader = MockReader { root };
+
+        let default = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+        assert_eq!(default.len(), 2);
+
+        let options = ScanOptions::builder().include_clsid_branch(false).resolve_server_paths(false).build();
+        let mut total_keys = 0;
+        let results = scan_com_objects_internal(&reader, &options, &AtomicBool::new(false), &mut |_, total| total_keys = total).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Widget.App");
+        assert_eq!(results[0].server_path.as_deref(), Some("%PATH%\\widget.dll"));
+        // Only the ProgID pass is counted
+        assert_eq!(total_keys, 2);
+    }
+
+    #[test]
+    fn test_mark_registered_typelibs_probes_hkcr_typelib() {
+        let root = MockKey::new();
+        let typelibs = MockKey::new();
+        typelibs.add_subkey("{00020813-0000-0000-c000-000000000046}", MockKey::new());
+        root.add_subkey("TypeLib", typelibs);
+        let registered = registered_typelibs(&MockReader { root });
+
+        let obj = |typelib: Option<&str>| ComObject { typelib: typelib.map(str::to_string), ..Default::default() };
+        let mut objects = vec![
+            obj(Some("{00020813-0000-0000-C000-000000000046}")),
+            obj(Some("{DEADBEEF-0000-0000-0000-000000000000}")),
+            obj(None),
+        ];
+        mark_registered_typelibs_with(&mut objects, &registered);
+
+        let flags: Vec<bool> = objects.iter().map(|obj| obj.typelib_registered).collect();
+        assert_eq!(flags, vec![true, false, false]);
+        assert!(registered_typelibs(&MockReader { root: MockKey::new() }).is_empty());
+    }
+
+    #[test]
+    fn test_typelib_key_exists_opens_only_that_key() {
+        let root = MockKey::new();
+        let typelibs = MockKey::new();
+        typelibs.add_subkey("{00020813-0000-0000-C000-000000000046}", MockKey::new());
+        root.add_subkey("TypeLib", typelibs);
+        let reader = MockReader { root };
+
+        assert!(typelib_key_exists(&reader, "{00020813-0000-0000-C000-000000000046}"));
+        assert!(!typelib_key_exists(&reader, "{DEADBEEF-0000-0000-0000-000000000000}"));
+        assert!(!typelib_key_exists(&MockReader { root: MockKey::new() }, "{00020813-0000-0000-C000-000000000046}"));
+    }
+
+    #[test]
+    fn test_missing_threading_model_reports_single() {
+        let root = MockKey::new();
+        let clsid_root = MockKey::new();
+        let class_key = MockKey::new();
+        class_key.add_subkey("InprocServer32", MockKey::new());
+        clsid_root.add_subkey("{DDD}", class_key);
+        root.add_subkey("CLSID", clsid_root);
+
+        let reader = MockReader { root };
+        let results = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].threading_model.as_deref(), Some("Single"));
+    }
+
+    #[test]
+    fn test_scan_resolves_app_id_and_categories() {
+        // HKCR
+        //  |-- CLSID\{EEE}        (AppID: "{APP}")
+        //       |-- LocalServer32
+        //       |-- Implemented Categories\{40fc6ed5-...}, {CUSTOM}
+        //  |-- AppID\{APP}        (LocalService: "MySvc", DllSurrogate: "")
+        let root = MockKey::new();
+
+        let clsid_root = MockKey::new();
+        let class_key = MockKey::new();
+        class_key.set_value("AppID", "{APP}");
+        let categories = MockKey::new();
+        categories.add_subkey("{40fc6ed5-2438-11cf-a3db-080036f12502}", MockKey::new());
+        categories.add_subkey("{CUSTOM}", MockKey::new());
+        class_key.add_subkey("Implemented Categories", categories);
+        class_key.add_subkey("LocalServer32", MockKey::new());
+        clsid_root.add_subkey("{EEE}", class_key);
+        root.add_subkey("CLSID", clsid_root);
+
+        let appid_root = MockKey::new();
+        let app_key = MockKey::new();
+        app_key.set_value("LocalService", "MySvc");
+        app_key.set_value("DllSurrogate", "");
+        appid_root.add_subkey("{APP}", app_key);
+        root.add_subkey("AppID", appid_root);
+
+        let reader = MockReader { root };
+        let results = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+
+        assert_eq!(
+            results[0].app_id,
+            Some(AppIdInfo {
+                app_id: "{APP}".to_string(),
+                local_service: Some("MySvc".to_string()),
+                run_as: None,
+                dll_surrogate: Some(String::new()),
+            })
+        );
+        let mut categories = results[0].categories.clone();
+        categories.sort();
+        assert_eq!(categories, vec!["Controls".to_string(), "{CUSTOM}".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_views_deduplicates() {
+        let obj = |name: &str, clsid: &str, server_kind: ServerKind, bitness: Bitness| ComObject {
+            name: name.to_string(),
+            clsid: clsid.to_string(),
+            has_progid: true,
+            server_kind,
+            bitness,
+            ..Default::default()
+        };
+
+        let native = vec![
+            obj("Both.Object", "{AAA}", ServerKind::InProc, Bitness::Native),
+            obj("Wow.Object", "{BBB}", ServerKind::None, Bitness::Native),
+        ];
+        let x86 = vec![
+            obj("Both.Object", "{aaa}", ServerKind::InProc, Bitness::X86),
+            obj("Wow.Object", "{BBB}", ServerKind::Local, Bitness::X86),
+            obj("Only32.Object", "{CCC}", ServerKind::InProc, Bitness::X86),
+        ];
+
+        let merged = merge_views(native, x86);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].bitness, Bitness::Both);
+        assert_eq!(merged[1].bitness, Bitness::X86);
+        assert_eq!(merged[1].server_kind, ServerKind::Local);
+        assert_eq!(merged[2].name, "Only32.Object");
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let objects = vec![ComObject {
+            name: "Cached.Object".to_string(),
+            clsid: "{CACHE}".to_string(),
+            has_progid: true,
+            server_kind: ServerKind::InProc,
+            categories: vec!["Controls".to_string()],
+            ..Default::default()
+        }];
+        let path = std::env::temp_dir()
+            .join(format!("rustcom_explorer_test_{}", std::process::id()))
+            .join("cache.json");
+
+        save_cache(&objects, &path).unwrap();
+        assert!(is_cache_fresh(&path, Duration::from_secs(60)));
+        assert!(!is_cache_fresh(&path, Duration::ZERO));
+        assert_eq!(load_cache(&path).unwrap(), objects);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| match name {
+            "SystemRoot" => Some("C:\\Windows".to_string()),
+            _ => None,
+        };
+
+        assert_eq!(
+            expand_env_vars("%SystemRoot%\\System32\\shell32.dll", lookup),
+            "C:\\Windows\\System32\\shell32.dll"
+        );
+        assert_eq!(expand_env_vars("%Missing%\\a.dll", lookup), "%Missing%\\a.dll");
+        assert_eq!(expand_env_vars("100% %SystemRoot%", lookup), "100% C:\\Windows");
+    }
+
+    #[test]
+    fn test_server_exists_searches_system_dirs_for_bare_names() {
+        let env = |var: &str| match var {
+            "SystemRoot" => Some("C:/Windows".to_string()),
+            "PATH" => Some("C:/Tools;;D:/Bin".to_string()),
+            _ => None,
+        };
+        let on_disk = ["C:/Windows/System32/scrrun.dll", "D:/Bin/tool.exe", "C:/Apps/app.exe"];
+        let exists = |path: &Path| on_disk.iter().any(|known| Path::new(known) == path);
+
+        assert!(server_exists("scrrun.dll", env, exists));
+        assert!(server_exists("tool.exe /automation", env, exists));
+        assert!(server_exists("\"C:/Apps/app.exe\" -Embedding", env, exists));
+        assert!(!server_exists("C:/Apps/gone.exe", env, exists));
+        assert!(!server_exists("missing.dll", env, exists));
+    }
+
+    #[test]
+    fn test_server_binary_strips_quotes_and_arguments() {
+        assert_eq!(server_binary("\"C:\\Program Files\\Office\\EXCEL.EXE\" /automation"), "C:\\Program Files\\Office\\EXCEL.EXE");
+        assert_eq!(server_binary("C:\\Windows\\app.exe -Embedding"), "C:\\Windows\\app.exe");
+        assert_eq!(server_binary("C:\\Windows\\System32\\shell32.dll"), "C:\\Windows\\System32\\shell32.dll");
+        assert_eq!(server_binary("mscoree.dll"), "mscoree.dll");
+    }
+
+    /// `Excel.Application` registered in both directions.
+    fn excel_registry() -> MockReader {
+        let root = MockKey::new();
+        let progid = MockKey::new();
+        let clsid = MockKey::new();
+        clsid.set_value("", "{00024500-0000-0000-C000-000000000046}");
+        progid.add_subkey("CLSID", clsid);
+        root.add_subkey("Excel.Application", progid);
+
+        let clsid_root = MockKey::new();
+        let class_key = MockKey::new();
+        let progid_value = MockKey::new();
+        progid_value.set_value("", "Excel.Application.16");
+        class_key.add_subkey("ProgID", progid_value);
+        clsid_root.add_subkey("{00024500-0000-0000-C000-000000000046}", class_key);
+        // Registered, but without a ProgID
+        clsid_root.add_subkey("{00000000-0000-0000-0000-000000000001}", MockKey::new());
+        root.add_subkey("CLSID", clsid_root);
+
+        MockReader { root }
+    }
+
+    #[test]
+    fn test_resolve_clsid_from_progid_and_guid() {
+        let reader = excel_registry();
+        let resolve = |input: &str| resolve_clsid_with(input, |progid| clsid_for_progid_internal(&reader, progid));
+
+        assert_eq!(resolve("Excel.Application").unwrap(), "{00024500-0000-0000-C000-000000000046}");
+        assert_eq!(
+            resolve(" {00024500-0000-0000-C000-000000000046} ").unwrap(),
+            "{00024500-0000-0000-C000-000000000046}"
+        );
+        for malformed in ["{ABC}", "{00024500-0000-0000-C000-000000000046", "{garbage}"] {
+            let err = resolve(malformed).unwrap_err();
+            assert!(matches!(err.downcast_ref::<InspectError>(), Some(InspectError::Generic(msg)) if msg.contains("invalid CLSID")), "{}", malformed);
+        }
+        assert_eq!(
+            resolve("00024500-0000-0000-c000-000000000046").unwrap(),
+            "{00024500-0000-0000-c000-000000000046}"
+        );
+        let err = resolve("Missing.Object").unwrap_err();
+        assert!(matches!(err.downcast_ref::<InspectError>(), Some(InspectError::Registry(_))));
+    }
+
+    #[test]
+    fn test_clsid_for_progid() {
+        let reader = excel_registry();
+
+        assert_eq!(
+            clsid_for_progid_internal(&reader, " Excel.Application ").unwrap(),
+            "{00024500-0000-0000-C000-000000000046}"
+        );
+        let err = clsid_for_progid_internal(&reader, "Word.Application").unwrap_err();
+        assert!(matches!(err.downcast_ref::<InspectError>(), Some(InspectError::Registry(_))));
+        assert!(err.to_string().contains("'Word.Application'"));
+    }
+
+    #[test]
+    fn test_progid_for_clsid() {
+        let reader = excel_registry();
+
+        assert_eq!(progid_for_clsid_internal(&reader, "{00024500-0000-0000-C000-000000000046}").unwrap(), "Excel.Application.16");
+        assert_eq!(progid_for_clsid_internal(&reader, "00024500-0000-0000-C000-000000000046").unwrap(), "Excel.Application.16");
+        // The CLSID key exists, but has no ProgID subkey
+        let err = progid_for_clsid_internal(&reader, "{00000000-0000-0000-0000-000000000001}").unwrap_err();
+        assert!(matches!(err.downcast_ref::<InspectError>(), Some(InspectError::Registry(_))));
+        assert!(progid_for_clsid_internal(&reader, "{FFFFFFFF-0000-0000-0000-000000000000}").is_err());
+    }
+
+    #[test]
+    fn test_access_denied_maps_to_permission_error() {
+        let reader = MockReader { root: MockKey::denied() };
+
+        let err = scan_com_objects_internal(&reader, &ScanOptions::default(), &AtomicBool::new(false), &mut |_, _| {}).unwrap_err();
+        assert!(matches!(err.downcast_ref::<InspectError>(), Some(InspectError::Permission(_))));
+        assert!(format!("{}", err.root_cause()).contains("Administrator"));
+    }
+}
@@ -0,0 +1,74 @@
+// src/style.rs
+use crossterm::style::Stylize;
+use std::ffi::OsString;
+use std::io::IsTerminal;
+
+/// Whether colored output is allowed at all: `--no-color` is not passed and
+/// `NO_COLOR` is unset or empty (see <https://no-color.org>).
+pub fn color_allowed(no_color_flag: bool) -> bool {
+    color_allowed_with(no_color_flag, std::env::var_os("NO_COLOR"))
+}
+
+fn color_allowed_with(no_color_flag: bool, no_color_env: Option<OsString>) -> bool {
+    !no_color_flag && no_color_env.is_none_or(|value| value.is_empty())
+}
+
+/// Applies ANSI styling to CLI output, or passes text through unchanged when disabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Styler {
+    enabled: bool,
+}
+
+impl Styler {
+    /// A styler that never emits escape codes.
+    pub const PLAIN: Styler = Styler { enabled: false };
+
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Styling for stdout: only when color is allowed and stdout is a terminal.
+    pub fn for_stdout(color_allowed: bool) -> Self {
+        Self::new(color_allowed && std::io::stdout().is_terminal())
+    }
+
+    /// Styling for stderr logs: only when color is allowed and stderr is a terminal.
+    pub fn for_stderr(color_allowed: bool) -> Self {
+        Self::new(color_allowed && std::io::stderr().is_terminal())
+    }
+
+    /// Whether escape codes are emitted.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Bold text for names and headings.
+    pub fn strong(&self, text: &str) -> String {
+        if self.enabled { text.bold().to_string() } else { text.to_string() }
+    }
+
+    /// Dimmed text for secondary details such as CLSIDs and counts.
+    pub fn dim(&self, text: &str) -> String {
+        if self.enabled { text.dark_grey().to_string() } else { text.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_color_env_and_flag() {
+        assert!(color_allowed_with(false, None));
+        assert!(color_allowed_with(false, Some(OsString::new())));
+        assert!(!color_allowed_with(false, Some(OsString::from("1"))));
+        assert!(!color_allowed_with(true, None));
+    }
+
+    #[test]
+    fn test_plain_styler_emits_no_escapes() {
+        assert_eq!(Styler::PLAIN.strong("Excel"), "Excel");
+        assert_eq!(Styler::PLAIN.dim("{GUID}"), "{GUID}");
+        assert!(Styler::new(true).strong("Excel").contains('\x1b'));
+    }
+}